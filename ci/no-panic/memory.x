@@ -0,0 +1,6 @@
+/* i.MX RT 1060 FlexSPI flash and DTCM. Enough to link; never flashed. */
+MEMORY
+{
+    FLASH : ORIGIN = 0x60000000, LENGTH = 1984K
+    RAM   : ORIGIN = 0x20000000, LENGTH = 128K
+}
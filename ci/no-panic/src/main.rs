@@ -0,0 +1,81 @@
+//! Prove that the transfer hot paths can't panic.
+//!
+//! This program configures and enables a channel, and dispatches its interrupts,
+//! with the fallible (`try_`) APIs. Its panic handler calls a function that doesn't exist.
+//! If any panic is reachable from those paths, the release build fails to link:
+//!
+//! ```text
+//! cargo build --release
+//! ```
+//!
+//! The program is never run. Inputs pass through `black_box`, so the compiler
+//! can't prove the checks away from constants.
+
+#![no_std]
+#![no_main]
+
+use core::hint::black_box;
+use imxrt_dma::{
+    channel::{self, Channel, Configuration, MinorLoopOffset},
+    ConfigError, Dma,
+};
+
+// Safety: the i.MX RT 1060 DMA and DMAMUX addresses.
+static DMA: Dma<32> = unsafe { Dma::new(0x400E_8000 as *const (), 0x400E_C000 as *const ()) };
+
+static SOURCE: [u32; 64] = [0; 64];
+static mut DESTINATION: [u32; 64] = [0; 64];
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    // Safety: the only channel, and the only DMA user.
+    if let Ok(mut channel) = unsafe { DMA.try_channel(black_box(7)) } {
+        let _ = configure(&mut channel);
+        // Safety: the channel describes a valid transfer.
+        let _ = unsafe { channel.try_enable() };
+    }
+
+    loop {
+        // Safety: no transfer futures; the channel's state is only inspected.
+        unsafe {
+            let _ = DMA.try_on_interrupt(black_box(7));
+            black_box(DMA.on_error());
+            black_box(DMA.poll());
+        }
+    }
+}
+
+/// Describe a software-started memory copy, linked to another channel
+fn configure(channel: &mut Channel) -> Result<(), ConfigError> {
+    channel.disable();
+    channel.reset();
+    channel.clear_complete();
+    channel.clear_error();
+    channel.set_disable_on_completion(true);
+    channel.set_interrupt_on_completion(true);
+    channel.try_set_channel_configuration(black_box(Configuration::Off))?;
+    // Safety: the buffers are static, and the transfer moves all of them. The
+    // linked channel has no transfer.
+    unsafe {
+        channel::set_source_linear_buffer(channel, &SOURCE);
+        channel::set_destination_linear_buffer(channel, &mut *core::ptr::addr_of_mut!(DESTINATION));
+        channel.try_set_minor_loop_bytes_with_offset(
+            black_box(64),
+            black_box(MinorLoopOffset::Source(0)),
+        )?;
+        channel.set_transfer_iterations(black_box(4));
+        channel.try_set_minor_loop_link(black_box(Some(8)))?;
+        channel.try_set_major_loop_link(black_box(None))?;
+    }
+    Ok(())
+}
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    extern "Rust" {
+        /// Not defined anywhere. Linking fails if a panic is reachable.
+        fn a_panic_is_reachable_from_a_hot_path() -> !;
+    }
+    // Safety: never called, since it doesn't link.
+    unsafe { a_panic_is_reachable_from_a_hot_path() }
+}
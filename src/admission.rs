@@ -0,0 +1,267 @@
+//! Admit transfers against their deadlines.
+//!
+//! A control loop that queues DMA transfers usually learns too late that the queue
+//! couldn't keep up: a sample arrives late, and the loop jitters. [`Admission`]
+//! gives earlier feedback. Before you queue a transfer, declare its size and its
+//! deadline. The admission layer estimates when the transfer would complete, from
+//! the transfers it already admitted and the throughput it measured. If the estimate
+//! misses the deadline, the admission layer warns you, or rejects the transfer,
+//! based on its [`Policy`].
+//!
+//! Deadlines and estimates are in the ticks of a [`Clock`]. Once admitted, run the
+//! transfer with [`Grant::run`]. The admission layer times each transfer it runs,
+//! and refines its throughput estimate.
+//!
+//! ```no_run
+//! use core::cell::Cell;
+//! use imxrt_dma::admission::{Admission, Policy};
+//!
+//! let now = Cell::new(0);
+//! // Initially, assume that 1024 bytes take 100 ticks.
+//! let admission = Admission::new(|| now.get(), Policy::Reject, 1024, 100);
+//! assert_eq!(admission.estimate(2048), 200);
+//!
+//! // Too short a deadline for 1024 bytes.
+//! assert!(admission.admit(1024, 50).is_err());
+//!
+//! let first = admission.admit(1024, 150).unwrap();
+//! assert_eq!(first.expected(), 100);
+//! // The second transfer waits behind the first.
+//! let Err(rejected) = admission.admit(1024, 150) else { panic!() };
+//! assert_eq!(rejected.expected, 200);
+//!
+//! // Dropping a grant releases its share of the queue.
+//! drop(first);
+//! assert_eq!(admission.backlog(), 0);
+//! ```
+//!
+//! The estimate assumes that admitted transfers run one after another, on one
+//! channel, or through one [`shared`](crate::shared) channel. Use one `Admission`
+//! for each channel.
+
+use core::{
+    cell::RefCell,
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{sync::Mutex, timestamp::Clock, Error};
+
+/// What [`Admission::admit`] does with a transfer that would miss its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Policy {
+    /// Admit the transfer, and flag the grant as [late](Grant::is_late)
+    Warn,
+    /// Reject the transfer
+    Reject,
+}
+
+/// A transfer that [`Admission::admit`] rejected
+///
+/// Both times are in clock ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Rejected {
+    /// When the transfer would have completed
+    pub expected: u64,
+    /// When the transfer needed to complete
+    pub deadline: u64,
+}
+
+impl Display for Rejected {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "DMA transfer would miss its deadline")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Rejected {}
+
+/// The bits of fraction in the ticks-per-byte estimate.
+const FRACTION: u32 = 16;
+
+/// The measured throughput, and the work that's admitted
+struct State {
+    /// Ticks per byte, with `FRACTION` bits of fraction.
+    rate: u64,
+    /// The estimated ticks of every admitted transfer that hasn't completed.
+    backlog: u64,
+}
+
+/// Estimates transfer completion times, and admits transfers that meet their
+/// deadlines
+///
+/// See the [module documentation](crate::admission) for more information.
+pub struct Admission<C> {
+    clock: C,
+    policy: Policy,
+    state: Mutex<RefCell<State>>,
+}
+
+impl<C: Clock> Admission<C> {
+    /// Create an admission layer that assumes `bytes` take `ticks` to transfer
+    ///
+    /// The admission layer refines the assumption as it times transfers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    pub const fn new(clock: C, policy: Policy, bytes: u32, ticks: u32) -> Self {
+        assert!(bytes > 0, "Admission needs a non-zero throughput sample");
+        Admission {
+            clock,
+            policy,
+            state: Mutex::new(RefCell::new(State {
+                rate: ((ticks as u64) << FRACTION) / bytes as u64,
+                backlog: 0,
+            })),
+        }
+    }
+
+    /// Returns the estimated number of ticks to transfer `bytes`
+    pub fn estimate(&self, bytes: usize) -> u64 {
+        let rate = self.state.lock(|state| state.borrow().rate);
+        estimate(rate, bytes)
+    }
+
+    /// Returns the estimated number of ticks until every admitted transfer completes
+    pub fn backlog(&self) -> u64 {
+        self.state.lock(|state| state.borrow().backlog)
+    }
+
+    /// Admit a transfer of `bytes` that must complete by `deadline`
+    ///
+    /// The transfer is expected to complete once every admitted transfer completes,
+    /// and it moves its bytes. If that's after `deadline`, the [`Policy`] decides
+    /// whether the transfer is admitted. Otherwise, returns the transfer's grant. The
+    /// grant holds the transfer's place in the backlog until it's dropped.
+    pub fn admit(&self, bytes: usize, deadline: u64) -> Result<Grant<'_, C>, Rejected> {
+        let now = self.clock.now();
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let duration = estimate(state.rate, bytes);
+            let expected = now.saturating_add(state.backlog).saturating_add(duration);
+            if expected > deadline && self.policy == Policy::Reject {
+                return Err(Rejected { expected, deadline });
+            }
+            state.backlog = state.backlog.saturating_add(duration);
+            Ok(Grant {
+                admission: self,
+                bytes,
+                duration,
+                expected,
+                deadline,
+            })
+        })
+    }
+
+    /// Fold a transfer's measured duration into the throughput estimate
+    fn measure(&self, bytes: usize, ticks: u64) {
+        if bytes == 0 {
+            return;
+        }
+        let sample = (ticks << FRACTION) / bytes as u64;
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.rate = (state.rate.saturating_mul(3).saturating_add(sample)) / 4;
+        });
+    }
+}
+
+/// Returns the ticks to transfer `bytes` at `rate`, rounded up
+fn estimate(rate: u64, bytes: usize) -> u64 {
+    let ticks = (bytes as u64).saturating_mul(rate);
+    (ticks >> FRACTION) + (ticks & ((1 << FRACTION) - 1) != 0) as u64
+}
+
+/// An admitted transfer
+///
+/// Use [`Admission::admit`] to create a grant, then [`run`](Self::run) the
+/// transfer. Dropping the grant without running the transfer releases its place
+/// in the backlog.
+pub struct Grant<'a, C: Clock> {
+    admission: &'a Admission<C>,
+    bytes: usize,
+    /// The estimated ticks of this transfer.
+    duration: u64,
+    expected: u64,
+    deadline: u64,
+}
+
+impl<'a, C: Clock> Grant<'a, C> {
+    /// Returns when the transfer is expected to complete, in clock ticks
+    pub fn expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// Returns when the transfer must complete, in clock ticks
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    /// Returns `true` if the transfer is expected to miss its deadline
+    ///
+    /// Only a [`Policy::Warn`] admission layer admits late transfers.
+    pub fn is_late(&self) -> bool {
+        self.expected > self.deadline
+    }
+
+    /// Run the admitted transfer, and time it
+    ///
+    /// The returned future resolves with the transfer's result. Once it resolves,
+    /// the admission layer folds the transfer's duration into its throughput
+    /// estimate.
+    pub fn run<F, R>(self, transfer: F) -> Admitted<'a, C, F>
+    where
+        F: Future<Output = Result<R, Error>>,
+    {
+        Admitted {
+            grant: self,
+            start: None,
+            transfer,
+        }
+    }
+}
+
+impl<C: Clock> Drop for Grant<'_, C> {
+    fn drop(&mut self) {
+        self.admission.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.backlog = state.backlog.saturating_sub(self.duration);
+        });
+    }
+}
+
+/// A transfer run with a [`Grant`]
+///
+/// Use [`Grant::run`] to create this future. Dropping the future drops the
+/// transfer, and releases the grant.
+pub struct Admitted<'a, C: Clock, F> {
+    grant: Grant<'a, C>,
+    start: Option<u64>,
+    transfer: F,
+}
+
+impl<C, F, R> Future for Admitted<'_, C, F>
+where
+    C: Clock,
+    F: Future<Output = Result<R, Error>>,
+{
+    type Output = Result<R, Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        let admission = this.grant.admission;
+        let start = *this.start.get_or_insert_with(|| admission.clock.now());
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        let result = core::task::ready!(transfer.poll(cx));
+        if result.is_ok() {
+            let ticks = admission.clock.now().saturating_sub(start);
+            admission.measure(this.grant.bytes, ticks);
+        }
+        Poll::Ready(result)
+    }
+}
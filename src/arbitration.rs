@@ -0,0 +1,261 @@
+//! DMA arbitration diagnostics.
+//!
+//! The DMA controller arbitrates between channels with pending requests. Use
+//! [`Dma::arbitration`](crate::Dma::arbitration) to capture the controller's
+//! arbitration settings, and every channel's priority, in one [`Arbitration`]
+//! snapshot. The snapshot implements `Debug`, so you can log it when you're
+//! debugging transfer latency.
+//!
+//! ```no_run
+//! use imxrt_dma::Dma;
+//! static DMA: Dma<32> = // Handle to DMA driver.
+//! # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! let arbitration = DMA.arbitration();
+//! for (channel, priority) in arbitration.channels.iter().enumerate() {
+//!     if priority.can_be_preempted {
+//!         // Channel may be preempted by a higher-priority channel...
+//!         # let _ = channel;
+//!     }
+//! }
+//! ```
+//!
+//! To meet a hard deadline, [boost](crate::channel::Channel::with_priority_boost) a
+//! channel's priority for one transfer. The boost restores the previous priorities
+//! when it ends.
+
+use crate::{
+    channel::Channel,
+    ral::{self, dma::DCHPRI},
+    ConfigError,
+};
+
+/// How the controller selects among channels, or among groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// The highest priority is selected
+    Fixed,
+    /// Each is selected in turn, independent of priority
+    RoundRobin,
+}
+
+/// A channel's priority settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelPriority {
+    /// The channel's priority within its group
+    ///
+    /// Larger numbers are higher priorities.
+    pub priority: u8,
+    /// The priority of the channel's group
+    pub group_priority: u8,
+    /// A higher-priority channel can suspend this channel
+    pub can_be_preempted: bool,
+    /// This channel can suspend a lower-priority channel
+    pub can_preempt: bool,
+}
+
+impl ChannelPriority {
+    /// Returns the channel's priority among all channels, under fixed arbitration
+    ///
+    /// The group priority takes precedence over the channel priority.
+    /// Larger numbers are higher priorities.
+    pub const fn effective(&self) -> u8 {
+        (self.group_priority << 4) | self.priority
+    }
+}
+
+/// A snapshot of the DMA controller's arbitration settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Arbitration<const CHANNELS: usize> {
+    /// How the controller selects a channel within a group
+    pub channel_mode: Mode,
+    /// How the controller selects a group
+    pub group_mode: Mode,
+    /// The priority of each channel group, indexed by group
+    ///
+    /// Controllers with 16 or fewer channels only use the first group.
+    pub group_priorities: [u8; 2],
+    /// The priority of each channel, indexed by channel number
+    pub channels: [ChannelPriority; CHANNELS],
+}
+
+impl<const CHANNELS: usize> crate::Dma<CHANNELS> {
+    /// Capture the DMA controller's arbitration settings
+    ///
+    /// See the [`arbitration`](crate::arbitration) module for more information.
+    pub fn arbitration(&self) -> Arbitration<CHANNELS> {
+        let (erca, erga, grp0pri, grp1pri) = ral::read_reg!(
+            crate::ral::dma,
+            self.controller,
+            CR,
+            ERCA,
+            ERGA,
+            GRP0PRI,
+            GRP1PRI
+        );
+        let mode = |round_robin| {
+            if round_robin != 0 {
+                Mode::RoundRobin
+            } else {
+                Mode::Fixed
+            }
+        };
+        Arbitration {
+            channel_mode: mode(erca),
+            group_mode: mode(erga),
+            group_priorities: [grp0pri as u8, grp1pri as u8],
+            channels: core::array::from_fn(|channel| {
+                ChannelPriority::from_register(self.controller.DCHPRI[channel].read())
+            }),
+        }
+    }
+}
+
+impl ChannelPriority {
+    /// Decode a channel priority register
+    pub(crate) const fn from_register(dchpri: u8) -> Self {
+        ChannelPriority {
+            priority: (dchpri & DCHPRI::CHPRI::mask) >> DCHPRI::CHPRI::offset,
+            group_priority: (dchpri & DCHPRI::GRPPRI::mask) >> DCHPRI::GRPPRI::offset,
+            can_be_preempted: dchpri & DCHPRI::ECP::mask != 0,
+            can_preempt: dchpri & DCHPRI::DPA::mask == 0,
+        }
+    }
+
+    /// Encode the writable fields of a channel priority register
+    ///
+    /// The group priority is read-only, so it's not encoded.
+    pub(crate) const fn to_register(self) -> u8 {
+        let mut dchpri = (self.priority << DCHPRI::CHPRI::offset) & DCHPRI::CHPRI::mask;
+        if self.can_be_preempted {
+            dchpri |= DCHPRI::ECP::mask;
+        }
+        if !self.can_preempt {
+            dchpri |= DCHPRI::DPA::mask;
+        }
+        dchpri
+    }
+}
+
+/// The number of channels in a priority group.
+///
+/// i.MX RT controllers have 16 or 32 channels, so every group is full.
+const GROUP_CHANNELS: usize = 16;
+
+impl Channel {
+    /// Raise the channel's priority while `f` runs
+    ///
+    /// See [`boost_priority`](Self::boost_priority) for more information.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::channel::Channel;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// channel.with_priority_boost(15, |channel| {
+    ///     // Run the hard-deadline transfer to completion...
+    ///     # let _ = channel;
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is greater than 15.
+    pub fn with_priority_boost<R>(&mut self, priority: u8, f: impl FnOnce(&mut Channel) -> R) -> R {
+        let mut boost = self.boost_priority(priority);
+        f(&mut boost)
+    }
+
+    /// Raise the channel's priority until the returned boost is dropped
+    ///
+    /// The channel takes `priority` within its group, it may preempt lower-priority
+    /// channels, and it can't be preempted. If another channel in the group has
+    /// `priority`, the two channels swap priorities, since fixed arbitration requires
+    /// unique priorities. Dropping the boost restores both channels' previous settings.
+    /// The boost dereferences to the channel, so you can await a transfer while the
+    /// boost is held.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is greater than 15. See [`try_boost_priority`](Self::try_boost_priority)
+    /// for a non-panicking version.
+    pub fn boost_priority(&mut self, priority: u8) -> PriorityBoost<'_> {
+        self.try_boost_priority(priority)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Raise the channel's priority until the returned boost is dropped
+    ///
+    /// Returns [`ConfigError::InvalidPriority`] if `priority` is greater than 15.
+    pub fn try_boost_priority(&mut self, priority: u8) -> Result<PriorityBoost<'_>, ConfigError> {
+        if priority > DCHPRI::CHPRI::mask >> DCHPRI::CHPRI::offset {
+            return Err(ConfigError::InvalidPriority);
+        }
+        let index = self.channel();
+        let (previous, swapped) = crate::sync::free(|| {
+            let registers = &self.controller().DCHPRI;
+            let previous = registers[index].read();
+            let group = index - index % GROUP_CHANNELS;
+            let swapped = (group..group + GROUP_CHANNELS)
+                .filter(|&other| other != index)
+                .find(|&other| registers[other].read() & DCHPRI::CHPRI::mask == priority)
+                .map(|other| {
+                    let settings = registers[other].read();
+                    let chpri = previous & DCHPRI::CHPRI::mask;
+                    registers[other].write((settings & !DCHPRI::CHPRI::mask) | chpri);
+                    (other, settings)
+                });
+            // Clearing ECP prevents preemption. Clearing DPA allows preemption.
+            let boosted = previous & !(DCHPRI::CHPRI::mask | DCHPRI::ECP::mask | DCHPRI::DPA::mask);
+            registers[index].write(boosted | priority);
+            (previous, swapped)
+        });
+        Ok(PriorityBoost {
+            channel: self,
+            previous,
+            swapped,
+        })
+    }
+}
+
+/// A channel with a temporarily raised priority
+///
+/// Use [`Channel::boost_priority`] to create a boost. Dropping the boost restores
+/// the previous priorities.
+pub struct PriorityBoost<'a> {
+    channel: &'a mut Channel,
+    /// This channel's priority register before the boost.
+    previous: u8,
+    /// The channel that gave up its priority, and its register before the boost.
+    swapped: Option<(usize, u8)>,
+}
+
+impl core::ops::Deref for PriorityBoost<'_> {
+    type Target = Channel;
+    fn deref(&self) -> &Channel {
+        self.channel
+    }
+}
+
+impl core::ops::DerefMut for PriorityBoost<'_> {
+    fn deref_mut(&mut self) -> &mut Channel {
+        self.channel
+    }
+}
+
+impl Drop for PriorityBoost<'_> {
+    fn drop(&mut self) {
+        let index = self.channel.channel();
+        crate::sync::free(|| {
+            let registers = &self.channel.controller().DCHPRI;
+            if let Some((other, settings)) = self.swapped {
+                registers[other].write(settings);
+            }
+            registers[index].write(self.previous);
+        });
+    }
+}
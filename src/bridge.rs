@@ -0,0 +1,288 @@
+//! Move data between peripherals with different element sizes.
+//!
+//! A direct peripheral-to-peripheral transfer needs a source and destination with
+//! the same element size. A [`Bridge`] uses two DMA channels and a small bounce
+//! buffer to convert between element sizes. For example, a bridge packs four
+//! bytes from a `Source<u8>` into each word for a `Destination<u32>`, or unpacks
+//! each word from a `Source<u32>` into four bytes for a `Destination<u8>`.
+//!
+//! The bridge works in *words*. A word is the size of the larger element type.
+//!
+//! 1. The source channel services the source's DMA requests. It gathers one
+//!    word of data into the bounce buffer.
+//! 2. After every word, the source channel links to the destination channel.
+//!    The destination channel moves the word from the bounce buffer to the
+//!    destination.
+//!
+//! The destination channel doesn't wait for the destination's DMA requests.
+//! Instead, the source paces the bridge. Make sure that the destination can accept
+//! each word as soon as the source provides it; a destination with a FIFO that's
+//! faster than the source is a good fit.
+
+use crate::{
+    channel::{self, Channel, Configuration},
+    interrupt::Transfer,
+    peripheral::{Destination, Source},
+    ConfigError, Element, Error,
+};
+
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A DMA transfer between two peripherals with different element sizes
+///
+/// The future resolves when the destination channel has moved all words into
+/// the destination, and the destination [completes](Destination::complete_destination)
+/// the transfer. Use [`bridge`] to construct this future.
+pub struct Bridge<'a, S, ES, D, ED>
+where
+    S: Source<ES>,
+    ES: Element,
+    D: Destination<ED>,
+    ED: Element,
+{
+    source_channel: &'a mut Channel,
+    source: &'a mut S,
+    destination_channel: &'a Channel,
+    destination: &'a mut D,
+    transfer: Transfer<'a>,
+    /// Set once the destination channel completes.
+    transferred: bool,
+    /// The result of enabling the source channel
+    enable: Result<(), ConfigError>,
+    _elem: PhantomData<(&'a mut u32, ES, ED)>,
+}
+
+/// Move `words` words from `source` to `destination` through `bounce`
+///
+/// `source_channel` services the source, and `destination_channel` services the
+/// destination. The bridge converts between the source's and destination's element
+/// sizes. See the [module documentation](crate::bridge) for more information.
+///
+/// The future resolves when the destination channel completes. Consider using the
+/// destination channel's interrupt handler to call [`on_interrupt()`](crate::Dma::on_interrupt)
+/// and wake the executor. Otherwise, poll the future.
+///
+/// # Panics
+///
+/// Panics if both channels are the same, or if `words` is greater than 32767.
+/// A bridge of zero words completes on its first poll, without touching the
+/// channels or the peripherals.
+///
+/// # Example
+///
+/// Forward 64 bytes from a LPUART receiver to a 32-bit LPSPI transmitter.
+///
+/// ```no_run
+/// use imxrt_dma::{bridge, channel::Channel, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X; struct Y;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::Destination<u32> for Y {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u32 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART receiver
+///     # X;
+/// let mut lpspi = // A LPSPI transmitter
+///     # Y;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// let mut channel_8: Channel = // DMA channel 8
+///     # unsafe { DMA.channel(8) };
+///
+/// let mut bounce = 0u32;
+/// bridge::bridge(
+///     &mut channel_7,
+///     &mut lpuart,
+///     &mut channel_8,
+///     &mut lpspi,
+///     &mut bounce,
+///     64 / 4,
+/// )
+/// .await?;
+/// # Ok(()) }
+/// ```
+pub fn bridge<'a, S, ES, D, ED>(
+    source_channel: &'a mut Channel,
+    source: &'a mut S,
+    destination_channel: &'a mut Channel,
+    destination: &'a mut D,
+    bounce: &'a mut u32,
+    words: u16,
+) -> Bridge<'a, S, ES, D, ED>
+where
+    S: Source<ES>,
+    ES: Element,
+    D: Destination<ED>,
+    ED: Element,
+{
+    assert!(
+        source_channel.channel() != destination_channel.channel(),
+        "DMA bridge needs two channels"
+    );
+    assert!(words <= 0x7FFF, "Invalid DMA bridge length");
+    if words == 0 {
+        return Bridge {
+            source_channel,
+            source,
+            transfer: Transfer::empty(destination_channel),
+            destination_channel,
+            destination,
+            transferred: true,
+            enable: Ok(()),
+            _elem: PhantomData,
+        };
+    }
+
+    let source_size = core::mem::size_of::<ES>();
+    let destination_size = core::mem::size_of::<ED>();
+    let word = source_size.max(destination_size);
+    // The bounce buffer wraps around after every word.
+    let modulo = word.trailing_zeros() as u8;
+    let bounce: *const u32 = bounce;
+
+    destination_channel.disable();
+    // The destination channel only runs when linked by the source channel.
+    destination_channel.set_disable_on_completion(true);
+    destination_channel.set_channel_configuration(Configuration::Off);
+
+    source_channel.disable();
+    // The bridge disables the source channel once the destination completes.
+    source_channel.set_disable_on_completion(false);
+    source_channel.set_interrupt_on_completion(false);
+    source_channel.clear_complete();
+    source_channel.clear_error();
+    source_channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+
+    // Safety: hardware addresses must be valid, otherwise impls are unsound. Bounce buffer
+    // lifetime captured by future. Bounce buffer is u32 aligned, and both channels wrap
+    // around in the bounce buffer after every word. The destination transfer iterations
+    // describe the number of words.
+    let enable = unsafe {
+        destination_channel.set_source_address(bounce.cast::<ED>());
+        destination_channel.set_source_offset(destination_size as i16);
+        destination_channel.set_source_attributes::<ED>(modulo);
+        destination_channel.set_source_last_address_adjustment(0);
+        channel::set_destination_hardware(destination_channel, destination.destination_address());
+        destination_channel.set_minor_loop_bytes(word as u32);
+        destination_channel.set_transfer_iterations(words);
+        destination_channel.set_major_loop_link(None);
+
+        channel::set_source_hardware(source_channel, source.source_address());
+        source_channel.set_destination_address(bounce.cast::<ES>());
+        source_channel.set_destination_offset(source_size as i16);
+        source_channel.set_destination_attributes::<ES>(modulo);
+        source_channel.set_destination_last_address_adjustment(0);
+        source_channel.set_minor_loop_bytes(source_size as u32);
+        source_channel.set_transfer_iterations((word / source_size) as u16);
+        source_channel.set_major_loop_link(Some(destination_channel.channel()));
+
+        source_channel.try_enable()
+    };
+
+    if enable.is_ok() {
+        destination.enable_destination();
+        source.enable_source();
+    }
+
+    Bridge {
+        source_channel,
+        source,
+        destination_channel,
+        destination,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(destination_channel) },
+        transferred: false,
+        enable,
+        _elem: PhantomData,
+    }
+}
+
+impl<S, ES, D, ED> Bridge<'_, S, ES, D, ED>
+where
+    S: Source<ES>,
+    ES: Element,
+    D: Destination<ED>,
+    ED: Element,
+{
+    fn stop_source(&mut self) {
+        self.source.disable_source();
+        self.source_channel.disable();
+        while self.source_channel.is_active() {}
+    }
+}
+
+impl<S, ES, D, ED> Future for Bridge<'_, S, ES, D, ED>
+where
+    S: Source<ES>,
+    ES: Element,
+    D: Destination<ED>,
+    ED: Element,
+{
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.transfer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        if !this.transferred {
+            if let Err(err) = this.enable {
+                return Poll::Ready(Err(err.into()));
+            }
+            if this.source_channel.is_error() {
+                let es = this.source_channel.error_status();
+                this.source_channel.clear_error();
+                this.stop_source();
+                return Poll::Ready(Err(es.into()));
+            }
+            // Safety: no movement from transfer future...
+            let poll = unsafe { Pin::new_unchecked(&mut this.transfer) }.poll(cx);
+            match poll {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => {
+                    this.stop_source();
+                    result?;
+                }
+            }
+            this.transferred = true;
+        }
+        this.destination.complete_destination(cx).map(Ok)
+    }
+}
+
+impl<S, ES, D, ED> Drop for Bridge<'_, S, ES, D, ED>
+where
+    S: Source<ES>,
+    ES: Element,
+    D: Destination<ED>,
+    ED: Element,
+{
+    fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
+        self.stop_source();
+        // Safety: the bridge no longer links to the destination channel.
+        unsafe { self.source_channel.set_major_loop_link(None) };
+        self.source_channel.clear_complete();
+        self.source_channel.clear_error();
+        self.destination.disable_destination();
+        while self.destination_channel.is_active() {}
+        // Drop `transfer` to finish cancellation...
+    }
+}
@@ -0,0 +1,262 @@
+//! Data cache maintenance for DMA buffers.
+//!
+//! The DMA engine doesn't observe the Cortex-M7 data cache. Before the DMA engine
+//! reads a buffer, the CPU must [`clean`] the buffer, writing any cached data into
+//! memory. After the DMA engine writes a buffer, the CPU must [`invalidate`] the
+//! buffer, discarding any stale cached data.
+//!
+//! Tightly-coupled memories are never cached. Buffers in OCRAM, FlexSPI memory, and
+//! external SDRAM on the SEMC usually are. Since DMA into SDRAM is common for camera
+//! and display pipelines, you'll usually need cache maintenance for those buffers.
+//!
+//! # Alignment
+//!
+//! The cache operates on 32 byte lines. Invalidating a buffer discards the entire
+//! line, including any data that neighbors the buffer. To prevent that data loss,
+//! a buffer that the DMA engine writes must start on a cache line, and its size must
+//! be a multiple of the cache line. Use [`Aligned`] to align a buffer.
+//!
+//! # The `cache` feature
+//!
+//! Enable the `cache` feature to have the transfers in [`memcpy`](crate::memcpy::memcpy)
+//! and the [`peripheral`](crate::peripheral) module maintain the cache for you:
+//!
+//! - transfers clean source buffers before they start.
+//! - transfers clean and invalidate destination buffers before they start, then
+//!   invalidate the destination buffers once the DMA channel completes.
+//! - transfers panic if a destination buffer isn't aligned to the cache line.
+//!
+//! Other transfers leave cache maintenance to you.
+//!
+//! ```no_run
+//! use imxrt_dma::{cache::{self, Aligned}, channel::Channel, peripheral};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct X;
+//! # unsafe impl peripheral::Source<u32> for X {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u32 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//!
+//! // A frame buffer, placed in SDRAM by your linker script.
+//! // #[link_section = ".sdram"]
+//! static mut FRAME: Aligned<[u32; 1024]> = Aligned([0; 1024]);
+//!
+//! # async fn f() -> imxrt_dma::Result<()> {
+//! let mut camera = // A camera peripheral
+//!     # X;
+//! let mut channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! // Safety: FRAME is only referenced here.
+//! let frame: &mut [u32] = unsafe { &mut *core::ptr::addr_of_mut!(FRAME.0) };
+//! assert!(cache::is_aligned(frame));
+//! peripheral::read(&mut channel_7, &mut camera, frame).await?;
+//! // Without the `cache` feature, invalidate the frame before reading it.
+//! cache::invalidate(frame);
+//! # Ok(()) }
+//! ```
+
+use crate::{ConfigError, Element};
+
+use core::ops::{Deref, DerefMut};
+
+/// The size of a data cache line, in bytes
+pub const LINE_SIZE: usize = 32;
+
+/// Aligns `T` to a cache line
+///
+/// If the size of `T` isn't a multiple of the cache line, the wrapper pads
+/// `T` to fill its last cache line.
+///
+/// ```
+/// use imxrt_dma::cache::{self, Aligned};
+///
+/// let buffer = Aligned([0u8; 64]);
+/// assert!(cache::is_aligned(&*buffer));
+/// ```
+#[repr(C, align(32))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Aligned<T>(pub T);
+
+const _STATIC_ASSERT_ALIGNED_LINE_SIZE: [u32; 1] =
+    [0; (LINE_SIZE == core::mem::align_of::<Aligned<u8>>()) as usize];
+
+impl<T> Deref for Aligned<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Aligned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsRef<U>, U: ?Sized> AsRef<U> for Aligned<T> {
+    fn as_ref(&self) -> &U {
+        self.0.as_ref()
+    }
+}
+
+impl<T: AsMut<U>, U: ?Sized> AsMut<U> for Aligned<T> {
+    fn as_mut(&mut self) -> &mut U {
+        self.0.as_mut()
+    }
+}
+
+/// Returns `true` if `buffer` starts on a cache line, and if its size is a
+/// multiple of the cache line
+pub fn is_aligned<E>(buffer: &[E]) -> bool {
+    (buffer.as_ptr() as usize).is_multiple_of(LINE_SIZE)
+        && core::mem::size_of_val(buffer).is_multiple_of(LINE_SIZE)
+}
+
+/// Write any cached data for `buffer` into memory
+///
+/// Call `clean` before the DMA engine reads `buffer`.
+pub fn clean<E: Element>(buffer: &[E]) {
+    // Safety: cleaning doesn't change the contents of memory observed
+    // by the CPU.
+    unsafe {
+        for_each_line(buffer, |cbp, line| cbp.dccmvac.write(line));
+    }
+}
+
+/// Discard any cached data for `buffer`
+///
+/// Call `invalidate` after the DMA engine writes `buffer`, and before the CPU
+/// reads `buffer`.
+///
+/// # Panics
+///
+/// Panics if `buffer` isn't [aligned to the cache line](is_aligned).
+pub fn invalidate<E: Element>(buffer: &mut [E]) {
+    try_invalidate(buffer).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Discard any cached data for `buffer`, or return an error if `buffer` isn't
+/// aligned to the cache line
+///
+/// See [`invalidate`] for more information.
+pub fn try_invalidate<E: Element>(buffer: &mut [E]) -> Result<(), ConfigError> {
+    if !is_aligned(buffer) {
+        return Err(ConfigError::UnalignedCacheBuffer);
+    }
+    // Safety: the buffer occupies entire cache lines, so we don't discard
+    // any other data. Any bit pattern is a valid element.
+    unsafe {
+        for_each_line(buffer, |cbp, line| cbp.dcimvac.write(line));
+    }
+    Ok(())
+}
+
+/// Write any cached data for `buffer` into memory, then discard the cached data
+///
+/// Call `clean_invalidate` before the DMA engine writes `buffer`, so that the
+/// cache doesn't later evict stale data into the buffer.
+pub fn clean_invalidate<E: Element>(buffer: &mut [E]) {
+    // Safety: the CPU observes the same memory contents after cleaning.
+    unsafe {
+        for_each_line(buffer, |cbp, line| cbp.dccimvac.write(line));
+    }
+}
+
+/// Perform a cache maintenance operation on each cache line that holds `buffer`
+///
+/// # Safety
+///
+/// `op` must be a valid maintenance operation for every line of the buffer.
+unsafe fn for_each_line<E>(
+    buffer: &[E],
+    mut op: impl FnMut(&cortex_m::peripheral::cbp::RegisterBlock, u32),
+) {
+    let size = core::mem::size_of_val(buffer);
+    if size == 0 {
+        return;
+    }
+    let start = buffer.as_ptr() as usize;
+    let end = start + size;
+    let cbp = &*cortex_m::peripheral::CBP::PTR;
+
+    cortex_m::asm::dsb();
+    let mut line = start & !(LINE_SIZE - 1);
+    while line < end {
+        op(cbp, line as u32);
+        line += LINE_SIZE;
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Prepare a source buffer for a transfer
+///
+/// Only performs cache maintenance with the `cache` feature.
+#[cfg(any(feature = "async", feature = "ring-buffers"))]
+#[inline(always)]
+pub(crate) fn prepare_source<E: Element>(_source: &[E]) {
+    #[cfg(feature = "cache")]
+    clean(_source);
+}
+
+/// Cache maintenance for a transfer's destination buffer
+///
+/// Only performs cache maintenance with the `cache` feature.
+#[cfg(feature = "async")]
+pub(crate) struct Destination {
+    #[cfg(feature = "cache")]
+    range: Option<(usize, usize)>,
+}
+
+#[cfg(feature = "async")]
+impl Destination {
+    /// Prepare a destination buffer for a transfer
+    ///
+    /// # Panics
+    ///
+    /// With the `cache` feature, panics if the buffer isn't aligned to the cache line.
+    #[inline(always)]
+    pub(crate) fn prepare<E: Element>(_destination: &mut [E]) -> Self {
+        #[cfg(feature = "cache")]
+        {
+            if !is_aligned(_destination) {
+                panic!("{}", ConfigError::UnalignedCacheBuffer);
+            }
+            clean_invalidate(_destination);
+            Destination {
+                range: Some((
+                    _destination.as_mut_ptr() as usize,
+                    core::mem::size_of_val(_destination),
+                )),
+            }
+        }
+        #[cfg(not(feature = "cache"))]
+        Destination {}
+    }
+
+    /// No cache maintenance for the destination
+    #[inline(always)]
+    pub(crate) const fn none() -> Self {
+        Destination {
+            #[cfg(feature = "cache")]
+            range: None,
+        }
+    }
+
+    /// Discard cached data after the DMA channel completes the transfer
+    #[inline(always)]
+    pub(crate) fn complete(&self) {
+        #[cfg(feature = "cache")]
+        {
+            if let Some((address, size)) = self.range {
+                // Safety: the transfer borrows the destination buffer for at least
+                // as long as this object.
+                let buffer = unsafe { core::slice::from_raw_parts_mut(address as *mut u8, size) };
+                invalidate(buffer);
+            }
+        }
+    }
+}
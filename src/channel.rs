@@ -6,14 +6,31 @@
 //! `Channel` methods that specify memory involved in transfers are marked `unsafe`. You must
 //! be very careful when calling these methods, particuarly when a channel is already
 //! enabled.
+//!
+//! With the `validate` feature, the buffer helpers in this module panic if a buffer isn't
+//! DMA-accessible. See the `memory` module, available with a chip feature, for more
+//! information.
 
 use crate::{
     element::Element,
+    error::{ConfigError, ErrorStatus},
     ral::{self, dma, dmamux, tcd::BandwidthControl, Static},
-    Error,
+    retry::RetryPolicy,
 };
 
+/// The number of channels that support periodic triggering
+const PERIODIC_CHANNELS: usize = 4;
+/// The maximum number of channels supported by a DMA controller
+pub(crate) const MAX_CHANNELS: usize = 32;
+
 impl<const CHANNELS: usize> super::Dma<CHANNELS> {
+    /// Returns the number of DMA channels supported by the driver
+    ///
+    /// This is the `CHANNELS` value used to create the driver.
+    pub const fn channel_count(&self) -> usize {
+        CHANNELS
+    }
+
     /// Creates the DMA channel described by `index`.
     ///
     /// # Safety
@@ -26,13 +43,151 @@ impl<const CHANNELS: usize> super::Dma<CHANNELS> {
     ///
     /// Panics if `index` is greater than or equal to the maximum number of channels.
     pub unsafe fn channel(&'static self, index: usize) -> Channel {
-        assert!(index < CHANNELS);
-        Channel {
+        self.try_channel(index)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates the DMA channel described by `index`, or returns an error if
+    /// the index is invalid.
+    ///
+    /// # Safety
+    ///
+    /// See [`channel`](Self::channel) for the safety requirements.
+    pub unsafe fn try_channel(&'static self, index: usize) -> Result<Channel, ConfigError> {
+        if index >= CHANNELS {
+            return Err(ConfigError::InvalidChannel);
+        }
+        Ok(Channel {
             index,
+            channels: CHANNELS,
             registers: self.controller,
             multiplexer: self.multiplexer,
+            #[cfg(feature = "async")]
             waker: &self.wakers[index],
+            retry: None,
+            instance: self.instance,
+            interrupt_on_error: false,
+            seal: None,
+        })
+    }
+
+    /// Creates all DMA channels
+    ///
+    /// `split` returns every channel supported by the DMA controller, indexed by the
+    /// channel number. Use `split` to take ownership of all channels in one place,
+    /// then distribute the channels to your drivers.
+    ///
+    /// `split` only returns the channels once. Successive calls return `None`.
+    /// `split` also returns `None` if any channel is [claimed](crate::claim). After
+    /// calling `split`, do not use [`channel`](Self::channel) to create channels,
+    /// since those channels would alias the split channels.
+    ///
+    /// ```
+    /// use imxrt_dma::Dma;
+    /// # const DMA_PTR: *const () = core::ptr::null() as _;
+    /// # const DMAMUX_PTR: *const () = core::ptr::null() as  _;
+    ///
+    /// static DMA: Dma<32> = unsafe { Dma::new(DMA_PTR, DMAMUX_PTR) };
+    ///
+    /// let channels = DMA.split().unwrap();
+    /// assert_eq!(channels[7].channel(), 7);
+    /// assert!(DMA.split().is_none());
+    /// ```
+    pub fn split(&'static self) -> Option<[Channel; CHANNELS]> {
+        if !self.claim_mask(u32::MAX >> (32 - CHANNELS)) {
+            return None;
         }
+        // Safety: we only create one channel for each index, and only once.
+        Some(core::array::from_fn(|index| unsafe { self.channel(index) }))
+    }
+
+    /// Returns a bitmask of the channels that are receiving a service signal
+    /// from hardware
+    ///
+    /// Bit `n` is set if channel `n` is receiving a signal. See
+    /// [`Channel::is_hardware_signaling`] for more information.
+    pub fn hardware_signaling(&self) -> u32 {
+        self.controller.HRS.read()
+    }
+
+    /// Clear the interrupt flags of the channels in `mask`
+    ///
+    /// Bit `n` clears channel `n`'s flag. If `mask` covers every channel, this takes
+    /// a single register write. Use this, and [`clear_errors`](Self::clear_errors)
+    /// and [`clear_done`](Self::clear_done), to return every channel to a clean
+    /// slate, like in a fault handler.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// DMA.clear_interrupts(u32::MAX);
+    /// DMA.clear_errors(u32::MAX);
+    /// DMA.clear_done(u32::MAX);
+    /// ```
+    pub fn clear_interrupts(&self, mask: u32) {
+        clear_flags::<CHANNELS>(mask, |value| self.controller.CINT.write(value));
+        crate::errata::complete_write(|| self.controller.INT.read());
+    }
+
+    /// Clear the error flags of the channels in `mask`
+    ///
+    /// Bit `n` clears channel `n`'s flag. See [`clear_interrupts`](Self::clear_interrupts)
+    /// for more information.
+    pub fn clear_errors(&self, mask: u32) {
+        clear_flags::<CHANNELS>(mask, |value| self.controller.CERR.write(value));
+        crate::errata::complete_write(|| self.controller.ERR.read());
+    }
+
+    /// Clear the completion flags of the channels in `mask`
+    ///
+    /// Bit `n` clears channel `n`'s flag. See [`clear_interrupts`](Self::clear_interrupts)
+    /// for more information.
+    pub fn clear_done(&self, mask: u32) {
+        clear_flags::<CHANNELS>(mask, |value| self.controller.CDNE.write(value));
+        // DONE lives in each TCD. Any read of the controller waits for the write.
+        crate::errata::complete_write(|| self.controller.INT.read());
+    }
+}
+
+/// Set in CINT, CERR, and CDNE to clear the flag of every channel.
+const CLEAR_ALL: u8 = 1 << 6;
+
+/// Clear the flags in `mask` using one of the clear registers
+///
+/// `write` writes the clear register. Bits for channels beyond `CHANNELS` are
+/// ignored.
+fn clear_flags<const CHANNELS: usize>(mask: u32, write: impl Fn(u8)) {
+    let all = u32::MAX >> (MAX_CHANNELS - CHANNELS);
+    let mask = mask & all;
+    if mask == all {
+        write(CLEAR_ALL);
+        return;
+    }
+    (0..CHANNELS)
+        .filter(|index| mask & (1 << index) != 0)
+        .for_each(|index| write(index as u8));
+}
+
+/// Samples a channel's progress without borrowing the channel
+#[cfg(feature = "async")]
+#[derive(Clone, Copy)]
+pub(crate) struct Probe {
+    index: usize,
+    registers: Static<dma::RegisterBlock>,
+}
+
+#[cfg(feature = "async")]
+impl Probe {
+    /// Returns the channel's current iteration count and destination address
+    pub(crate) fn sample(&self) -> (u16, u32) {
+        // Safety: the index is less than CHANNELS, which is at most 32.
+        let tcd = unsafe { self.registers.TCD.get_unchecked(self.index) };
+        (
+            ral::read_reg!(crate::ral::tcd, tcd, CITER),
+            ral::read_reg!(crate::ral::tcd, tcd, DADDR),
+        )
     }
 }
 
@@ -46,12 +201,23 @@ impl<const CHANNELS: usize> super::Dma<CHANNELS> {
 pub struct Channel {
     /// Our channel number, expected to be between [0, 32)
     index: usize,
+    /// The number of channels on this channel's controller
+    channels: usize,
     /// Reference to the DMA registers
     registers: Static<dma::RegisterBlock>,
     /// Reference to the DMA multiplexer
     multiplexer: Static<dmamux::RegisterBlock>,
     /// This channel's waker.
+    #[cfg(feature = "async")]
     pub(crate) waker: &'static super::SharedWaker,
+    /// How transfers retry after bus errors.
+    retry: Option<RetryPolicy>,
+    /// The controller that owns this channel.
+    instance: crate::Instance,
+    /// Re-enable the error interrupt when the error is cleared.
+    interrupt_on_error: bool,
+    /// The checksum of the sealed TCD.
+    seal: Option<u32>,
 }
 
 impl Channel {
@@ -68,9 +234,89 @@ impl Channel {
     ///   else
     /// - if the transfer uses a circular buffer, you must ensure that the circular
     ///   buffer is correctly sized and aligned.
+    ///
+    /// `enable()` waits for all prior memory writes to complete before it enables the
+    /// channel. See [`start`](Self::start) for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TCD is [sealed](Self::seal_tcd), and it changed since it was
+    /// sealed. See [`try_enable`](Self::try_enable) for a non-panicking version.
     pub unsafe fn enable(&self) {
+        self.try_enable().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Enable the DMA channel for transfers, or return an error if the sealed TCD
+    /// changed
+    ///
+    /// Returns [`ConfigError::CorruptTcd`] without enabling the channel if the TCD
+    /// is [sealed](Self::seal_tcd), and it doesn't match its seal.
+    ///
+    /// # Safety
+    ///
+    /// See [`enable`](Self::enable) for the safety requirements.
+    pub unsafe fn try_enable(&self) -> Result<(), ConfigError> {
+        self.verify_seal()?;
+        barrier();
         // Immutable write OK. No other methods directly modify ERQ.
         self.registers.SERQ.write(self.index as u8);
+        Ok(())
+    }
+
+    /// Seal the programmed TCD with a checksum
+    ///
+    /// While the TCD is sealed, [`enable`](Self::enable) checks the TCD against its
+    /// checksum before it sets the channel's hardware request enable. A mismatch
+    /// means that something changed the TCD since you sealed it, like RAM corruption
+    /// or a racing writer, so the channel isn't enabled. The checksum skips the
+    /// START, ACTIVE, and DONE status bits.
+    ///
+    /// Seal the TCD after you program the transfer, and seal it again after you
+    /// reprogram the channel. When a transfer rewinds its addresses, the TCD matches
+    /// its seal again once the major loop completes, so you can also
+    /// [verify](Self::verify_seal) the TCD after completion.
+    ///
+    /// The driver's futures and streams enable their channels with
+    /// [`try_enable`](Self::try_enable), so a mismatch surfaces as the future's
+    /// [`ConfigError::CorruptTcd`] error, not as a panic. Those APIs program their
+    /// own transfers; unseal the TCD before you give them the channel.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::Channel, tcd::Tcd};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// let tcd = // Describe a transfer...
+    ///     # Tcd::new();
+    /// unsafe { tcd.apply(&mut channel) };
+    /// channel.seal_tcd();
+    ///
+    /// // Later...
+    /// if unsafe { channel.try_enable() }.is_err() {
+    ///     // The TCD changed. Reprogram the transfer...
+    /// }
+    /// ```
+    pub fn seal_tcd(&mut self) {
+        self.seal = Some(checksum(&self.snapshot()));
+    }
+
+    /// Remove the TCD's seal
+    ///
+    /// `enable` no longer checks the TCD.
+    pub fn unseal_tcd(&mut self) {
+        self.seal = None;
+    }
+
+    /// Check the TCD against its seal
+    ///
+    /// Returns [`ConfigError::CorruptTcd`] if the TCD is sealed, and it doesn't match
+    /// its seal. Returns `Ok` if the TCD isn't sealed.
+    pub fn verify_seal(&self) -> Result<(), ConfigError> {
+        match self.seal {
+            Some(seal) if seal != checksum(&self.snapshot()) => Err(ConfigError::CorruptTcd),
+            _ => Ok(()),
+        }
     }
 
     /// Returns the DMA channel number
@@ -80,6 +326,26 @@ impl Channel {
         self.index
     }
 
+    /// Returns the description of the controller that owns this channel
+    pub fn instance(&self) -> crate::Instance {
+        self.instance
+    }
+
+    /// Returns the interrupt number that this channel signals
+    ///
+    /// Unmask this interrupt in the NVIC to receive the channel's interrupts.
+    pub fn interrupt(&self) -> u16 {
+        self.instance.interrupt(self.index)
+    }
+
+    /// Returns `true` if the channel supports periodic triggering
+    ///
+    /// Only the first four DMA channels support periodic triggering from PIT timers.
+    /// See [`Configuration`] for the triggered configurations.
+    pub fn supports_periodic_trigger(&self) -> bool {
+        self.index < PERIODIC_CHANNELS
+    }
+
     /// Set the channel's bandwidth control
     ///
     /// - `None` disables bandwidth control (default setting)
@@ -100,7 +366,15 @@ impl Channel {
 
     /// Returns a handle to this channel's transfer control descriptor
     fn tcd(&self) -> &crate::ral::tcd::RegisterBlock {
-        &self.registers.TCD[self.index]
+        // Safety: the index is less than CHANNELS, which is at most 32. Skipping
+        // the bounds check keeps the register accesses panic-free.
+        unsafe { self.registers.TCD.get_unchecked(self.index) }
+    }
+
+    /// Returns a handle to this channel's DMAMUX configuration register
+    fn chcfg(&self) -> &ral_registers::RWRegister<u32> {
+        // Safety: see tcd().
+        unsafe { self.multiplexer.chcfg.get_unchecked(self.index) }
     }
 
     /// Set the source address for a DMA transfer
@@ -186,6 +460,11 @@ impl Channel {
 
     /// Set the source last address adjustment *in bytes*
     ///
+    /// The DMA engine adds `adjustment` to the source address when the major
+    /// loop completes. The adjustment may be any value. Use a negative adjustment
+    /// to rewind the source back to the start of a buffer, or a positive adjustment
+    /// to advance the source beyond the data that was just transferred.
+    ///
     /// # Safety
     ///
     /// This could allow the DMA engine to reference an invalid source buffer.
@@ -197,7 +476,46 @@ impl Channel {
         ral::write_reg!(crate::ral::tcd, tcd, SLAST, adjustment);
     }
 
-    /// Set the destination last addrss adjustment *in bytes*
+    /// Returns the source last address adjustment *in bytes*
+    ///
+    /// This reflects the last call to `set_source_last_address_adjustment`, or
+    /// the adjustment computed by one of the source helper functions.
+    pub fn source_last_address_adjustment(&self) -> i32 {
+        let tcd = self.tcd();
+        ral::read_reg!(crate::ral::tcd, tcd, SLAST)
+    }
+
+    /// Set the destination last address adjustment *in bytes*
+    ///
+    /// The DMA engine adds `adjustment` to the destination address when the
+    /// major loop completes. The adjustment may be any value. For example, a
+    /// transfer that writes one row of a tiled image per major loop may advance
+    /// the destination by the remainder of the row pitch, so that the next major
+    /// loop writes the next row.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::channel::{self, Channel};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// const WIDTH: usize = 16;
+    /// const PITCH: usize = 64;
+    ///
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// let mut framebuffer = [0u16; PITCH * 8];
+    ///
+    /// // Safety: framebuffer outlives the transfers, and the channel
+    /// // never advances beyond the end of the framebuffer.
+    /// unsafe {
+    ///     channel::set_destination_linear_buffer(&mut channel, &mut framebuffer[..WIDTH]);
+    ///     // Instead of rewinding to the start of the row, skip to the start
+    ///     // of the next row.
+    ///     let skip = (PITCH - WIDTH) * core::mem::size_of::<u16>();
+    ///     channel.set_destination_last_address_adjustment(skip as i32);
+    /// }
+    /// ```
+    ///
+    /// The adjustment shares hardware with an [epilogue](Self::set_epilogue).
+    /// Setting the adjustment removes any epilogue.
     ///
     /// # Safety
     ///
@@ -207,9 +525,85 @@ impl Channel {
     /// current transfer completes.
     pub unsafe fn set_destination_last_address_adjustment(&self, adjustment: i32) {
         let tcd = self.tcd();
+        // The adjustment and the scatter / gather address share a register.
+        // Disable scatter / gather, so the adjustment isn't treated as a descriptor.
+        ral::modify_reg!(crate::ral::tcd, tcd, CSR, ESG: 0);
         ral::write_reg!(crate::ral::tcd, tcd, DLAST_SGA, adjustment);
     }
 
+    /// Run an epilogue after the transfer's major loop completes
+    ///
+    /// When the major loop completes, the DMA engine loads the epilogue, and immediately
+    /// performs the epilogue's write. The epilogue inherits the transfer's interrupt on
+    /// completion and disable on completion settings, so call this method after
+    /// configuring those settings. The transfer is complete once the epilogue completes.
+    ///
+    /// The epilogue replaces the destination last address adjustment. Call this
+    /// method after the destination is configured. Setting the destination last address
+    /// adjustment removes the epilogue.
+    ///
+    /// # Safety
+    ///
+    /// The DMA engine reads the epilogue when the transfer completes. You must ensure that
+    /// the epilogue is valid, and does not move, until the transfer completes or is canceled.
+    /// The epilogue's destination must be valid for the write.
+    ///
+    /// # Example
+    ///
+    /// Write a sentinel into memory once a transfer completes.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::{self, Channel}, epilogue::Epilogue};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # async fn f() -> imxrt_dma::Result<()> {
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    ///
+    /// let source = [1u32, 2, 3, 4];
+    /// let mut destination = [0u32; 4];
+    /// let mut done = 0u32;
+    /// let mut epilogue = Epilogue::new(&mut done, 0xC0FFEE);
+    ///
+    /// // Safety: all memory outlives the transfer.
+    /// unsafe {
+    ///     channel::set_source_linear_buffer(&mut channel, &source);
+    ///     channel::set_destination_linear_buffer(&mut channel, &mut destination);
+    ///     channel.set_minor_loop_bytes(core::mem::size_of_val(&source) as u32);
+    ///     channel.set_transfer_iterations(1);
+    ///     channel.set_disable_on_completion(true);
+    ///     channel.set_epilogue(&mut epilogue);
+    ///     channel.set_channel_configuration(channel::Configuration::Off);
+    /// }
+    ///
+    /// let transfer = unsafe { imxrt_dma::Transfer::new(&channel) };
+    /// channel.start();
+    /// transfer.await?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "scatter-gather")]
+    pub unsafe fn set_epilogue<E: Element>(&mut self, epilogue: &mut crate::epilogue::Epilogue<E>) {
+        use crate::ral::tcd::CSR;
+        let tcd = self.tcd();
+        let csr = ral::read_reg!(crate::ral::tcd, tcd, CSR);
+        let inherit = csr & (CSR::INTMAJOR::mask | CSR::DREQ::mask | CSR::BWC::mask);
+        let descriptor = epilogue.prepare(inherit);
+
+        // DONE must be clear before enabling scatter / gather.
+        self.clear_complete();
+        ral::write_reg!(crate::ral::tcd, tcd, DLAST_SGA, descriptor as i32);
+        ral::modify_reg!(crate::ral::tcd, tcd, CSR, ESG: 1);
+        crate::errata::complete_write(|| tcd.CSR.read());
+    }
+
+    /// Returns the destination last address adjustment *in bytes*
+    ///
+    /// This reflects the last call to `set_destination_last_address_adjustment`,
+    /// or the adjustment computed by one of the destination helper functions.
+    pub fn destination_last_address_adjustment(&self) -> i32 {
+        let tcd = self.tcd();
+        ral::read_reg!(crate::ral::tcd, tcd, DLAST_SGA)
+    }
+
     /// Set the transfer attributes for the destination
     ///
     /// # Safety
@@ -244,6 +638,90 @@ impl Channel {
         ral::write_reg!(crate::ral::tcd, tcd, NBYTES, nbytes);
     }
 
+    /// Set the number of *bytes* to transfer per minor loop, and an address offset
+    /// applied after each minor loop
+    ///
+    /// After the DMA engine transfers `nbytes` in a minor loop, it adds the
+    /// offset to the source address, the destination address, or both. This
+    /// lets a single major loop describe a strided transfer, like a rectangle
+    /// within a framebuffer.
+    ///
+    /// Minor loop offsets require minor loop mapping, which is a controller setting
+    /// shared by all channels. This method enables minor loop mapping, and it remains
+    /// enabled. With minor loop mapping enabled, channels that do not use an offset
+    /// may still transfer up to 1GiB per minor loop.
+    ///
+    /// # Safety
+    ///
+    /// This might allow the DMA engine to read beyond the source, or write beyond
+    /// the destination. Caller must ensure that the minor loop size and offset are
+    /// valid for the given transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbytes` is greater than 1023, or if the offset cannot be represented
+    /// as a 20 bit signed integer.
+    pub unsafe fn set_minor_loop_bytes_with_offset(&self, nbytes: u32, offset: MinorLoopOffset) {
+        self.try_set_minor_loop_bytes_with_offset(nbytes, offset)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of *bytes* to transfer per minor loop, and an address offset
+    /// applied after each minor loop, or returns an error if the hardware can't
+    /// represent the minor loop
+    ///
+    /// See [`set_minor_loop_bytes_with_offset`](Self::set_minor_loop_bytes_with_offset)
+    /// for more information.
+    ///
+    /// # Safety
+    ///
+    /// See [`set_minor_loop_bytes_with_offset`](Self::set_minor_loop_bytes_with_offset)
+    /// for the safety requirements.
+    pub unsafe fn try_set_minor_loop_bytes_with_offset(
+        &self,
+        nbytes: u32,
+        offset: MinorLoopOffset,
+    ) -> Result<(), ConfigError> {
+        const MLOFF_MAX: i32 = (1 << 19) - 1;
+        const MLOFF_MIN: i32 = -(1 << 19);
+
+        let (mloff, smloe, dmloe) = match offset {
+            MinorLoopOffset::Source(mloff) => (mloff, 1, 0),
+            MinorLoopOffset::Destination(mloff) => (mloff, 0, 1),
+            MinorLoopOffset::Both(mloff) => (mloff, 1, 1),
+        };
+        if nbytes > 0x3FF {
+            return Err(ConfigError::MinorLoopTooLarge);
+        }
+        if !(MLOFF_MIN..=MLOFF_MAX).contains(&mloff) {
+            return Err(ConfigError::MinorLoopOffsetOutOfRange);
+        }
+
+        self.enable_minor_loop_mapping();
+
+        // Immutable write OK. 32-bit store on NBYTES.
+        let tcd = self.tcd();
+        ral::write_reg!(
+            crate::ral::tcd,
+            tcd,
+            NBYTES,
+            SMLOE: smloe,
+            DMLOE: dmloe,
+            MLOFF: mloff as u32,
+            NBYTES: nbytes
+        );
+        Ok(())
+    }
+
+    /// Enable minor loop mapping for the whole DMA controller
+    pub(crate) fn enable_minor_loop_mapping(&self) {
+        // CR is shared by all channels. Prevent a racing modify from
+        // another execution context.
+        crate::sync::free(|| {
+            ral::modify_reg!(crate::ral::dma, self.registers, CR, EMLM: 1);
+        });
+    }
+
     /// Tells the DMA channel how many transfer iterations to perform
     ///
     /// A 'transfer iteration' is a read from a source, and a write to a destination, with
@@ -251,18 +729,41 @@ impl Channel {
     /// service request, either from hardware or from software. The maximum number of iterations
     /// is 2^15.
     ///
+    /// This disables any minor loop link. To link this channel to another channel
+    /// after each minor loop, call [`set_minor_loop_link`](Self::set_minor_loop_link)
+    /// after setting the transfer iterations.
+    ///
     /// # Safety
     ///
     /// This may allow the DMA engine to read beyond the source, or write beyond
     /// the destination. Caller must ensure that the number of iterations is valid
     /// for the transfer.
     pub unsafe fn set_transfer_iterations(&mut self, iterations: u16) {
+        // Without a link, all iteration counts are representable.
+        let _ = self.write_iterations(None, iterations);
+    }
+
+    /// Write the iteration counts, and the minor loop link, into CITER and BITER
+    fn write_iterations(&self, link: Option<usize>, iterations: u16) -> Result<(), ConfigError> {
+        use crate::ral::tcd::CITER;
         let tcd = self.tcd();
-        // Note that this is clearing the ELINK bit. We don't have support
-        // for channel-to-channel linking right now. Clearing ELINK is intentional
-        // to use the whole 15 bits for iterations.
-        ral::modify_reg!(crate::ral::tcd, tcd, CITER, CITER: iterations);
-        ral::modify_reg!(crate::ral::tcd, tcd, BITER, BITER: iterations);
+        match link {
+            // Clearing ELINK to use the whole 15 bits for iterations.
+            None => {
+                ral::write_reg!(crate::ral::tcd, tcd, CITER, CITER: iterations);
+                ral::write_reg!(crate::ral::tcd, tcd, BITER, BITER: iterations);
+            }
+            Some(link) => {
+                if iterations > CITER::ITER_ELINKYES::mask {
+                    return Err(ConfigError::TooManyIterations);
+                }
+                let value =
+                    CITER::ELINK::mask | ((link as u16) << CITER::LINKCH::offset) | iterations;
+                ral::write_reg!(crate::ral::tcd, tcd, CITER, value);
+                ral::write_reg!(crate::ral::tcd, tcd, BITER, value);
+            }
+        }
+        Ok(())
     }
 
     /// Returns the beginning transfer iterations setting for the channel.
@@ -270,7 +771,140 @@ impl Channel {
     /// This reflects the last call to `set_transfer_iterations`.
     pub fn beginning_transfer_iterations(&self) -> u16 {
         let tcd = self.tcd();
-        ral::read_reg!(crate::ral::tcd, tcd, BITER, BITER)
+        let biter = ral::read_reg!(crate::ral::tcd, tcd, BITER);
+        iterations(biter)
+    }
+
+    /// Returns the current transfer iterations for the channel.
+    ///
+    /// The DMA engine decrements this count after each minor loop. When the major
+    /// loop completes, the count reloads with the beginning transfer iterations.
+    pub fn current_transfer_iterations(&self) -> u16 {
+        let tcd = self.tcd();
+        let citer = ral::read_reg!(crate::ral::tcd, tcd, CITER);
+        iterations(citer)
+    }
+
+    /// Link this channel to another channel after each minor loop
+    ///
+    /// When this channel completes a minor loop, it requests service from the
+    /// `link` channel. The link does not happen after the final minor loop; use
+    /// [`set_major_loop_link`](Self::set_major_loop_link) to link after the major
+    /// loop. A channel may link to itself. `None` disables the minor loop link.
+    ///
+    /// A minor loop link reduces the maximum number of transfer iterations to 511.
+    /// Call this method *after* [`set_transfer_iterations`](Self::set_transfer_iterations).
+    ///
+    /// # Safety
+    ///
+    /// The linked channel will run its transfer. You must ensure that the linked channel
+    /// describes a valid transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `link` is not a channel of this controller, or if there are more than
+    /// 511 transfer iterations.
+    pub unsafe fn set_minor_loop_link(&mut self, link: Option<usize>) {
+        self.try_set_minor_loop_link(link)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Link this channel to another channel after each minor loop, or returns an
+    /// error if the link is invalid
+    ///
+    /// See [`set_minor_loop_link`](Self::set_minor_loop_link) for more information.
+    /// The link must name one of the controller's `CHANNELS`.
+    ///
+    /// # Safety
+    ///
+    /// See [`set_minor_loop_link`](Self::set_minor_loop_link) for the safety requirements.
+    pub unsafe fn try_set_minor_loop_link(
+        &mut self,
+        link: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        if link.is_some_and(|link| link >= self.channels) {
+            return Err(ConfigError::InvalidChannel);
+        }
+        self.write_iterations(link, self.beginning_transfer_iterations())
+    }
+
+    /// Returns the channel that this channel links to after each minor loop
+    pub fn minor_loop_link(&self) -> Option<usize> {
+        use crate::ral::tcd::BITER;
+        let tcd = self.tcd();
+        let biter = ral::read_reg!(crate::ral::tcd, tcd, BITER);
+        (biter & BITER::ELINK::mask != 0)
+            .then_some(((biter & BITER::LINKCH::mask) >> BITER::LINKCH::offset) as usize)
+    }
+
+    /// Link this channel to another channel after the major loop
+    ///
+    /// When this channel completes its major loop, it requests service from the
+    /// `link` channel. A channel may link to itself. `None` disables the major
+    /// loop link. The link remains until it's disabled, or until the channel is reset.
+    ///
+    /// Setting a link clears the channel's completion flag, since the eDMA ignores
+    /// the link while the flag is set.
+    ///
+    /// # Safety
+    ///
+    /// The linked channel will run its transfer. You must ensure that the linked channel
+    /// describes a valid transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `link` is not a channel of this controller.
+    pub unsafe fn set_major_loop_link(&mut self, link: Option<usize>) {
+        self.try_set_major_loop_link(link)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Link this channel to another channel after the major loop, or returns an
+    /// error if the link is invalid
+    ///
+    /// See [`set_major_loop_link`](Self::set_major_loop_link) for more information.
+    /// The link must name one of the controller's `CHANNELS`.
+    ///
+    /// ```
+    /// use imxrt_dma::{ConfigError, Dma};
+    /// # const DMA_PTR: *const () = core::ptr::null() as _;
+    /// # const DMAMUX_PTR: *const () = core::ptr::null() as  _;
+    ///
+    /// static DMA: Dma<16> = unsafe { Dma::new(DMA_PTR, DMAMUX_PTR) };
+    ///
+    /// let mut channel = unsafe { DMA.channel(7) };
+    /// let result = unsafe { channel.try_set_major_loop_link(Some(20)) };
+    /// assert_eq!(result, Err(ConfigError::InvalidChannel));
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`set_major_loop_link`](Self::set_major_loop_link) for the safety requirements.
+    pub unsafe fn try_set_major_loop_link(
+        &mut self,
+        link: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        if link.is_some_and(|link| link >= self.channels) {
+            return Err(ConfigError::InvalidChannel);
+        }
+        let tcd = self.tcd();
+        match link {
+            Some(link) => {
+                // The eDMA ignores MAJORELINK writes while DONE is set.
+                self.clear_complete();
+                ral::modify_reg!(crate::ral::tcd, tcd, CSR, MAJORELINK: 1, MAJORLINKCH: link as u16);
+                crate::errata::complete_write(|| tcd.CSR.read());
+            }
+            None => ral::modify_reg!(crate::ral::tcd, tcd, CSR, MAJORELINK: 0, MAJORLINKCH: 0),
+        }
+        Ok(())
+    }
+
+    /// Returns the channel that this channel links to after the major loop
+    pub fn major_loop_link(&self) -> Option<usize> {
+        let tcd = self.tcd();
+        let (elink, link) = ral::read_reg!(crate::ral::tcd, tcd, CSR, MAJORELINK, MAJORLINKCH);
+        (elink != 0).then_some(link as usize)
     }
 
     /// Set the DMAMUX channel configuration
@@ -281,20 +915,38 @@ impl Channel {
     /// # Panics
     ///
     /// Only the first four DMA channels support periodic triggering from PIT timers. This method
-    /// panics if `triggering` is set for the [`Enable`](crate::channel::Configuration)
-    /// variant, but the channel does not support triggering.
+    /// panics if `periodic` is set for the [`Enable`](crate::channel::Configuration)
+    /// variant, or if the configuration is [`AlwaysOnPeriodic`](Configuration::AlwaysOnPeriodic),
+    /// but the channel does not support triggering.
     pub fn set_channel_configuration(&mut self, configuration: Configuration) {
+        self.try_set_channel_configuration(configuration)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the DMAMUX channel configuration, or returns an error if the channel
+    /// doesn't support the configuration
+    ///
+    /// See [`set_channel_configuration`](Self::set_channel_configuration) for more
+    /// information.
+    pub fn try_set_channel_configuration(
+        &mut self,
+        configuration: Configuration,
+    ) -> Result<(), ConfigError> {
+        let periodic = matches!(
+            configuration,
+            Configuration::Enable { periodic: true, .. } | Configuration::AlwaysOnPeriodic
+        );
+        if periodic && !self.supports_periodic_trigger() {
+            return Err(ConfigError::PeriodicUnsupported);
+        }
+
         // Immutable write OK. 32-bit store on configuration register.
-        let chcfg = &self.multiplexer.chcfg[self.index];
+        let chcfg = self.chcfg();
         match configuration {
             Configuration::Off => chcfg.write(0),
             Configuration::Enable { source, periodic } => {
                 let mut v = source | dmamux::RegisterBlock::ENBL;
                 if periodic {
-                    assert!(
-                        self.channel() < 4,
-                        "Requested DMA periodic triggering on an unsupported channel."
-                    );
                     v |= dmamux::RegisterBlock::TRIG;
                 }
                 chcfg.write(v);
@@ -303,10 +955,93 @@ impl Channel {
                 // See note in reference manual: when A_ON is high, SOURCE is ignored.
                 chcfg.write(dmamux::RegisterBlock::ENBL | dmamux::RegisterBlock::A_ON)
             }
+            Configuration::AlwaysOnPeriodic => chcfg.write(
+                dmamux::RegisterBlock::ENBL
+                    | dmamux::RegisterBlock::A_ON
+                    | dmamux::RegisterBlock::TRIG,
+            ),
+        }
+        Ok(())
+    }
+
+    /// Returns the DMAMUX channel configuration
+    pub fn channel_configuration(&self) -> Configuration {
+        let chcfg = self.chcfg().read();
+        let periodic = chcfg & dmamux::RegisterBlock::TRIG != 0;
+        if chcfg & dmamux::RegisterBlock::ENBL == 0 {
+            Configuration::Off
+        } else if chcfg & dmamux::RegisterBlock::A_ON != 0 {
+            if periodic {
+                Configuration::AlwaysOnPeriodic
+            } else {
+                Configuration::AlwaysOn
+            }
+        } else {
+            Configuration::Enable {
+                source: chcfg & dmamux::RegisterBlock::SOURCE,
+                periodic,
+            }
+        }
+    }
+
+    /// Set the policy for retrying transfers after bus errors
+    ///
+    /// The transfer futures in this crate, including [`Transfer`](crate::Transfer),
+    /// follow the policy. Use `None` to report all errors immediately, which is the
+    /// default. See the [`retry`](crate::retry) module for more information.
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy;
+    }
+
+    /// Returns the policy for retrying transfers after bus errors
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry
+    }
+
+    /// Copy the channel's transfer into memory
+    pub(crate) fn snapshot(&self) -> crate::ral::tcd::Descriptor {
+        self.tcd().snapshot()
+    }
+
+    /// Restore a transfer from a [`snapshot`](Self::snapshot)
+    ///
+    /// # Safety
+    ///
+    /// The snapshot must describe a valid transfer.
+    pub(crate) unsafe fn restore(&self, descriptor: &crate::ral::tcd::Descriptor) {
+        self.tcd().restore(descriptor);
+    }
+
+    /// Returns a probe that samples this channel's progress
+    #[cfg(feature = "async")]
+    pub(crate) fn probe(&self) -> Probe {
+        Probe {
+            index: self.index,
+            registers: self.registers,
         }
     }
 
+    /// Returns the DMA controller's registers
+    pub(crate) fn controller(&self) -> &dma::RegisterBlock {
+        &self.registers
+    }
+
+    /// Returns `true` if the DMA multiplexer routes requests to this channel
+    ///
+    /// If this is `false`, software must [`start`](Self::start) the transfer.
+    #[cfg(feature = "async")]
+    pub(crate) fn is_multiplexed(&self) -> bool {
+        self.chcfg().read() & dmamux::RegisterBlock::ENBL != 0
+    }
+
     /// Returns `true` if the DMA channel is receiving a service signal from hardware
+    ///
+    /// This reflects the channel's bit in the hardware request status register. The
+    /// status follows the peripheral's DMA request, even if the channel is disabled.
+    /// Use this when a transfer isn't making progress: if the peripheral isn't
+    /// signaling, check the peripheral's DMA enable and watermark. If the peripheral
+    /// is signaling but the channel isn't [enabled](Self::is_enabled), the request
+    /// is waiting on the channel.
     pub fn is_hardware_signaling(&self) -> bool {
         self.registers.HRS.read() & (1 << self.index) != 0
     }
@@ -326,6 +1061,7 @@ impl Channel {
     pub fn clear_interrupt(&self) {
         // Immutable write OK. No other methods modify INT.
         self.registers.CINT.write(self.index as u8);
+        crate::errata::complete_write(|| self.registers.INT.read());
     }
 
     /// Enable or disable 'disable on completion'
@@ -345,6 +1081,15 @@ impl Channel {
         ral::modify_reg!(crate::ral::tcd, tcd, CSR, INTMAJOR: intr as u16);
     }
 
+    /// Enable or disable interrupt generation when the transfer is half complete
+    ///
+    /// The interrupt fires when the transfer has completed half of its transfer
+    /// iterations. You're responsible for registering your interrupt handler.
+    pub fn set_interrupt_on_half_completion(&mut self, intr: bool) {
+        let tcd = self.tcd();
+        ral::modify_reg!(crate::ral::tcd, tcd, CSR, INTHALF: intr as u16);
+    }
+
     /// Indicates if the DMA transfer has completed
     pub fn is_complete(&self) -> bool {
         let tcd = self.tcd();
@@ -357,6 +1102,7 @@ impl Channel {
         // TCD require &mut reference. Existence of &mut reference blocks
         // clear_complete calls.
         self.registers.CDNE.write(self.index as u8);
+        crate::errata::complete_write(|| self.tcd().CSR.read());
     }
 
     /// Indicates if the DMA channel is in an error state
@@ -365,10 +1111,38 @@ impl Channel {
     }
 
     /// Clears the error flag
+    ///
+    /// If the channel [interrupts on error](Self::set_interrupt_on_error), this
+    /// also unmasks the error interrupt.
     pub fn clear_error(&self) {
         // Immutable write OK. CERR affects a bit in ERR, which is
         // not written to elsewhere.
         self.registers.CERR.write(self.index as u8);
+        if self.interrupt_on_error {
+            self.registers.SEEI.write(self.index as u8);
+        }
+        crate::errata::complete_write(|| self.registers.ERR.read());
+    }
+
+    /// Enable or disable an interrupt when this channel reports an error
+    ///
+    /// Each channel opts into error interrupts on its own, independent of its
+    /// completion interrupts. Error interrupts signal the controller's error
+    /// interrupt, not the channel's interrupt. Route that interrupt handler to
+    /// [`Dma::on_error`](crate::Dma::on_error) to wake the channel's transfer,
+    /// which then reports the error.
+    pub fn set_interrupt_on_error(&mut self, intr: bool) {
+        self.interrupt_on_error = intr;
+        if intr {
+            self.registers.SEEI.write(self.index as u8);
+        } else {
+            self.registers.CEEI.write(self.index as u8);
+        }
+    }
+
+    /// Returns `true` if this channel generates an interrupt when it reports an error
+    pub fn interrupt_on_error(&self) -> bool {
+        self.interrupt_on_error
     }
 
     /// Indicates if this DMA channel is actively transferring data
@@ -386,8 +1160,37 @@ impl Channel {
     ///
     /// It may reflect the last channel that produced an error, and that
     /// may not be related to this channel.
-    pub fn error_status(&self) -> Error {
-        Error::new(self.registers.ES.read())
+    pub fn error_status(&self) -> ErrorStatus {
+        ErrorStatus::new(self.registers.ES.read())
+    }
+
+    /// Check the channel's transfer control descriptor for signs of corruption
+    ///
+    /// The DMA controller rejects an inconsistent descriptor with a configuration
+    /// error, like NCE or SGE, that doesn't say which field is wrong. `verify_tcd`
+    /// checks the same fields, and more, and explains what it finds. Call it after
+    /// an error, or before enabling a hand-built transfer.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::channel::Channel;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// if let Err(diagnosis) = channel.verify_tcd() {
+    ///     if diagnosis.is_iteration_mismatch() {
+    ///         // CITER and BITER disagree...
+    ///     }
+    /// }
+    /// ```
+    pub fn verify_tcd(&self) -> Result<(), Diagnosis> {
+        let mapped = ral::read_reg!(crate::ral::dma, self.registers, CR, EMLM == 1);
+        let diagnosis = Diagnosis::of(&self.snapshot(), mapped);
+        if diagnosis.faults == 0 {
+            Ok(())
+        } else {
+            Err(diagnosis)
+        }
     }
 
     /// Start a DMA transfer
@@ -398,12 +1201,62 @@ impl Channel {
     /// to request DMA service.
     ///
     /// Flag is automatically cleared by hardware after it's asserted.
+    ///
+    /// Like [`enable`](Self::enable), `start()` waits for all prior memory writes to
+    /// complete before it requests service. The Cortex-M7 write buffer may hold writes
+    /// to buffers and in-memory descriptors after the CPU executes later writes to DMA
+    /// registers. Without the barrier, the DMA engine could read stale data.
     pub fn start(&self) {
+        barrier();
         // Immutable write OK. SSRT affects a bit in TCD. But, other writes to
         // TCD require &mut reference. Existence of &mut reference blocks
         // start calls.
         self.registers.SSRT.write(self.index as u8);
     }
+
+    /// Request service for exactly one minor loop from software
+    ///
+    /// `trigger_once` sets the TCD's START bit, so the DMA engine services one minor
+    /// loop, like a single hardware request. Use it to pace a transfer from software,
+    /// to drive a peripheral from a test bench, or to step through a transfer while
+    /// debugging; check [`current_transfer_iterations`](Self::current_transfer_iterations)
+    /// after each step.
+    ///
+    /// Returns `false` if an earlier software request is still pending. The DMA
+    /// engine clears START once it begins the minor loop, and requests don't
+    /// accumulate, so the new request merges with the pending request.
+    ///
+    /// Software requests don't depend on the channel being [enabled](Self::enable).
+    /// If the channel is enabled, hardware requests also start minor loops. To step
+    /// a transfer only from software, [disable](Self::disable) the channel's hardware
+    /// requests first.
+    pub fn trigger_once(&self) -> bool {
+        if self.is_trigger_pending() {
+            return false;
+        }
+        self.start();
+        true
+    }
+
+    /// Returns `true` if a software request is waiting for the DMA engine
+    ///
+    /// See [`trigger_once`](Self::trigger_once) for more information.
+    pub fn is_trigger_pending(&self) -> bool {
+        let tcd = self.tcd();
+        ral::read_reg!(crate::ral::tcd, tcd, CSR, START == 1)
+    }
+}
+
+/// Wait for all prior memory writes to complete
+///
+/// Call this before a write that lets the DMA engine access memory. The data
+/// synchronization barrier ensures that the DMA engine observes all CPU writes
+/// to transfer control descriptors, in-memory descriptors, and buffers. The
+/// compiler fences keep the compiler from moving writes across the barrier.
+#[inline(always)]
+pub(crate) fn barrier() {
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    cortex_m::asm::dsb();
 }
 
 // It's OK to send a channel across an execution context.
@@ -411,8 +1264,25 @@ impl Channel {
 // them being (mutably) shared.
 unsafe impl Send for Channel {}
 
+/// An address offset applied after each minor loop
+///
+/// See [`set_minor_loop_bytes_with_offset`](Channel::set_minor_loop_bytes_with_offset)
+/// for more information. Offsets are expressed *in bytes*, and they may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MinorLoopOffset {
+    /// Apply the offset to the source address
+    Source(i32),
+    /// Apply the offset to the destination address
+    Destination(i32),
+    /// Apply the same offset to both the source and destination addresses
+    Both(i32),
+}
+
 /// DMAMUX channel configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum Configuration {
     /// The DMAMUX channel is disabled
@@ -439,6 +1309,15 @@ pub enum Configuration {
     /// - memory-to-memory transfers
     /// - memory to external bus transfers
     AlwaysOn,
+    /// The DMAMUX is always on, and the channel's PIT timer gates the request
+    ///
+    /// The channel receives one service request for every PIT timer period. Use
+    /// `AlwaysOnPeriodic` to pace transfers that don't involve a DMA-capable
+    /// peripheral, like writes to GPIO set / clear registers.
+    ///
+    /// `AlwaysOnPeriodic` only works for the first four DMA channels, since
+    /// it corresponds to the PIT timers.
+    AlwaysOnPeriodic,
 }
 
 impl Configuration {
@@ -453,6 +1332,172 @@ impl Configuration {
             periodic: false,
         }
     }
+
+    /// Enable the channel for a chip's request signal, without triggering
+    ///
+    /// Shorthand for `Configuration::enable(signal.raw())`.
+    #[cfg(feature = "imxrt1060")]
+    pub const fn enable_signal(signal: crate::signal::Signal) -> Self {
+        Self::enable(signal.raw())
+    }
+}
+
+/// Signs of corruption in a transfer control descriptor
+///
+/// [`Channel::verify_tcd`] returns a `Diagnosis` when it finds at least one
+/// fault. Each method indicates one fault. Format the diagnosis to see a
+/// summary of its faults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Diagnosis {
+    faults: u8,
+}
+
+impl Diagnosis {
+    const ITERATION_MISMATCH: u8 = 1 << 0;
+    const ZERO_ITERATIONS: u8 = 1 << 1;
+    const ZERO_MINOR_LOOP: u8 = 1 << 2;
+    const MINOR_LOOP_SIZE: u8 = 1 << 3;
+    const TRANSFER_SIZE: u8 = 1 << 4;
+    const SOURCE_ALIGNMENT: u8 = 1 << 5;
+    const DESTINATION_ALIGNMENT: u8 = 1 << 6;
+    const DESCRIPTOR_ALIGNMENT: u8 = 1 << 7;
+
+    /// Check `descriptor`
+    ///
+    /// `mapped` is set if the controller enables minor loop mapping.
+    fn of(descriptor: &crate::ral::tcd::Descriptor, mapped: bool) -> Self {
+        use crate::ral::tcd::{CITER, CSR, NBYTES, SATTR};
+
+        let mut faults = 0;
+        let mut flag = |fault, set| {
+            if set {
+                faults |= fault;
+            }
+        };
+
+        let (citer, biter) = (descriptor.CITER, descriptor.BITER);
+        let linked = CITER::ELINK::mask | CITER::LINKCH::mask;
+        flag(
+            Self::ITERATION_MISMATCH,
+            (citer & CITER::ELINK::mask != 0 && citer & linked != biter & linked)
+                || citer & CITER::ELINK::mask != biter & CITER::ELINK::mask
+                || iterations(citer) > iterations(biter),
+        );
+        flag(
+            Self::ZERO_ITERATIONS,
+            iterations(citer) == 0 || iterations(biter) == 0,
+        );
+
+        let offsets = NBYTES::SMLOE::mask | NBYTES::DMLOE::mask;
+        let nbytes = match (mapped, descriptor.NBYTES & offsets != 0) {
+            (false, _) => descriptor.NBYTES,
+            (true, false) => descriptor.NBYTES & !offsets,
+            (true, true) => descriptor.NBYTES & NBYTES::NBYTES::mask,
+        };
+        flag(Self::ZERO_MINOR_LOOP, nbytes == 0);
+
+        // The number of bytes for a size encoding. 16 bytes, and encodings
+        // above 32 bytes, are reserved.
+        let size = |attr: u8| match (attr & SATTR::SIZE::mask) >> SATTR::SIZE::offset {
+            encoding @ 0..=3 => Some(1u32 << encoding),
+            5 => Some(32),
+            _ => None,
+        };
+        let (ssize, dsize) = (size(descriptor.SATTR), size(descriptor.DATTR));
+        flag(Self::TRANSFER_SIZE, ssize.is_none() || dsize.is_none());
+        let (ssize, dsize) = (ssize.unwrap_or(1), dsize.unwrap_or(1));
+
+        flag(
+            Self::MINOR_LOOP_SIZE,
+            !nbytes.is_multiple_of(ssize) || !nbytes.is_multiple_of(dsize),
+        );
+        // Bursts of 32 bytes need only 32-bit aligned addresses and offsets.
+        let aligned = |address: u32, offset: i16, size: u32| {
+            let size = size.min(4);
+            address.is_multiple_of(size) && (offset as i32) % size as i32 == 0
+        };
+        flag(
+            Self::SOURCE_ALIGNMENT,
+            !aligned(descriptor.SADDR, descriptor.SOFF, ssize),
+        );
+        flag(
+            Self::DESTINATION_ALIGNMENT,
+            !aligned(descriptor.DADDR, descriptor.DOFF, dsize),
+        );
+        flag(
+            Self::DESCRIPTOR_ALIGNMENT,
+            descriptor.CSR & CSR::ESG::mask != 0 && descriptor.DLAST_SGA % 32 != 0,
+        );
+
+        Diagnosis { faults }
+    }
+
+    const fn is_fault(self, fault: u8) -> bool {
+        self.faults & fault != 0
+    }
+    /// Indicates that CITER and BITER disagree on the minor loop link, or that
+    /// the current iteration count exceeds the beginning iteration count
+    pub const fn is_iteration_mismatch(self) -> bool {
+        self.is_fault(Self::ITERATION_MISMATCH)
+    }
+    /// Indicates that CITER or BITER has no iterations
+    pub const fn is_zero_iterations(self) -> bool {
+        self.is_fault(Self::ZERO_ITERATIONS)
+    }
+    /// Indicates that the minor loop transfers zero bytes
+    pub const fn is_zero_minor_loop(self) -> bool {
+        self.is_fault(Self::ZERO_MINOR_LOOP)
+    }
+    /// Indicates that the minor loop isn't a multiple of the source and
+    /// destination transfer sizes
+    pub const fn is_minor_loop_size(self) -> bool {
+        self.is_fault(Self::MINOR_LOOP_SIZE)
+    }
+    /// Indicates a reserved source or destination transfer size
+    pub const fn is_transfer_size(self) -> bool {
+        self.is_fault(Self::TRANSFER_SIZE)
+    }
+    /// Indicates that the source address or offset isn't aligned to the
+    /// source transfer size
+    pub const fn is_source_misaligned(self) -> bool {
+        self.is_fault(Self::SOURCE_ALIGNMENT)
+    }
+    /// Indicates that the destination address or offset isn't aligned to the
+    /// destination transfer size
+    pub const fn is_destination_misaligned(self) -> bool {
+        self.is_fault(Self::DESTINATION_ALIGNMENT)
+    }
+    /// Indicates that scatter / gather is enabled, and DLAST_SGA isn't a
+    /// 32-byte aligned descriptor address
+    pub const fn is_descriptor_misaligned(self) -> bool {
+        self.is_fault(Self::DESCRIPTOR_ALIGNMENT)
+    }
+}
+
+impl core::fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let faults = [
+            (self.is_iteration_mismatch(), "CITER / BITER mismatch"),
+            (self.is_zero_iterations(), "zero iterations"),
+            (self.is_zero_minor_loop(), "zero NBYTES"),
+            (
+                self.is_minor_loop_size(),
+                "NBYTES not a multiple of the transfer sizes",
+            ),
+            (self.is_transfer_size(), "reserved transfer size"),
+            (self.is_source_misaligned(), "misaligned source"),
+            (self.is_destination_misaligned(), "misaligned destination"),
+            (self.is_descriptor_misaligned(), "misaligned DLAST_SGA"),
+        ];
+        f.write_str("DMA TCD:")?;
+        let mut separator = " ";
+        for (_, fault) in faults.iter().filter(|(set, _)| *set) {
+            write!(f, "{separator}{fault}")?;
+            separator = ", ";
+        }
+        Ok(())
+    }
 }
 
 /// Set a hardware peripheral as the source for a DMA transfer
@@ -502,12 +1547,11 @@ pub unsafe fn set_destination_hardware<E: Element>(
 /// Caller must ensure that the source is valid for the lifetime of the transfer,
 /// and valid for all subsequent transfers performed by this DMA channel with this buffer.
 pub unsafe fn set_source_linear_buffer<E: Element>(chan: &mut Channel, source: &[E]) {
+    validate_source(source);
     chan.set_source_address(source.as_ptr());
     chan.set_source_offset(core::mem::size_of::<E>() as i16);
     chan.set_source_attributes::<E>(0);
-    chan.set_source_last_address_adjustment(
-        ((source.len() * core::mem::size_of::<E>()) as i32).wrapping_neg(),
-    );
+    chan.set_source_last_address_adjustment((core::mem::size_of_val(source) as i32).wrapping_neg());
 }
 
 /// Set a linear buffer as the destination for a DMA transfer
@@ -520,14 +1564,126 @@ pub unsafe fn set_source_linear_buffer<E: Element>(chan: &mut Channel, source: &
 /// Caller must ensure that the destination is valid for the lifetime of the transfer,
 /// and valid for all subsequent transfers performed by this DMA channel with this buffer.
 pub unsafe fn set_destination_linear_buffer<E: Element>(chan: &mut Channel, destination: &mut [E]) {
+    validate_destination(destination);
     chan.set_destination_address(destination.as_ptr());
     chan.set_destination_offset(core::mem::size_of::<E>() as i16);
     chan.set_destination_attributes::<E>(0);
     chan.set_destination_last_address_adjustment(
-        ((destination.len() * core::mem::size_of::<E>()) as i32).wrapping_neg(),
+        (core::mem::size_of_val(destination) as i32).wrapping_neg(),
     );
 }
 
+/// Set a linear buffer as the source for a DMA transfer, reading
+/// from the last element to the first element
+///
+/// When the transfer completes, the DMA channel will point at the
+/// end of the buffer.
+///
+/// # Safety
+///
+/// Caller must ensure that the source is valid for the lifetime of the transfer,
+/// and valid for all subsequent transfers performed by this DMA channel with this buffer.
+pub unsafe fn set_source_reverse_buffer<E: Element>(chan: &mut Channel, source: &[E]) {
+    validate_source(source);
+    chan.set_source_address(last_element(source));
+    chan.set_source_offset((core::mem::size_of::<E>() as i16).wrapping_neg());
+    chan.set_source_attributes::<E>(0);
+    chan.set_source_last_address_adjustment(core::mem::size_of_val(source) as i32);
+}
+
+/// Set a linear buffer as the destination for a DMA transfer, writing
+/// from the last element to the first element
+///
+/// When the transfer completes, the DMA channel will point at the
+/// end of the buffer.
+///
+/// # Safety
+///
+/// Caller must ensure that the destination is valid for the lifetime of the transfer,
+/// and valid for all subsequent transfers performed by this DMA channel with this buffer.
+pub unsafe fn set_destination_reverse_buffer<E: Element>(
+    chan: &mut Channel,
+    destination: &mut [E],
+) {
+    validate_destination(destination);
+    chan.set_destination_address(last_element(destination));
+    chan.set_destination_offset((core::mem::size_of::<E>() as i16).wrapping_neg());
+    chan.set_destination_attributes::<E>(0);
+    chan.set_destination_last_address_adjustment(core::mem::size_of_val(destination) as i32);
+}
+
+/// Check the source buffer against the memory map
+///
+/// Only performs checks when the `validate` feature is enabled.
+#[inline(always)]
+pub(crate) fn validate_source<E>(_source: &[E]) {
+    #[cfg(all(feature = "validate", feature = "imxrt1060"))]
+    match crate::memory::check_source(_source) {
+        Ok(region) => warn_cacheable(_source, region),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Check the destination buffer against the memory map
+///
+/// Only performs checks when the `validate` feature is enabled.
+#[inline(always)]
+pub(crate) fn validate_destination<E>(_destination: &[E]) {
+    #[cfg(all(feature = "validate", feature = "imxrt1060"))]
+    match crate::memory::check_destination(_destination) {
+        Ok(region) => warn_cacheable(_destination, region),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Warn about a buffer in cacheable memory when the driver doesn't maintain the cache
+///
+/// The warning needs the `defmt` feature, and an embedded target.
+#[cfg(all(feature = "validate", feature = "imxrt1060"))]
+#[inline(always)]
+fn warn_cacheable<E>(_buffer: &[E], _region: crate::memory::Region) {
+    #[cfg(all(feature = "defmt", not(feature = "cache"), target_os = "none"))]
+    if _region.is_cacheable() {
+        defmt::warn!(
+            "DMA buffer at {=usize:#x} is in cacheable {}; clean or invalidate the data cache around the transfer",
+            _buffer.as_ptr() as usize,
+            _region
+        );
+    }
+}
+
+/// Returns a checksum of the descriptor, without its status bits
+///
+/// The checksum is FNV-1a over the descriptor's 32-bit words.
+fn checksum(descriptor: &crate::ral::tcd::Descriptor) -> u32 {
+    use crate::ral::tcd::CSR;
+    let mut descriptor = *descriptor;
+    descriptor.CSR &= !(CSR::START::mask | CSR::ACTIVE::mask | CSR::DONE::mask);
+    // Safety: the descriptor is 32 bytes of plain integers, aligned to 32 bytes.
+    let words: &[u32; 8] = unsafe { &*core::ptr::addr_of!(descriptor).cast() };
+    words.iter().fold(0x811C_9DC5, |hash, &word| {
+        (hash ^ word).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Returns the iteration count from a CITER or BITER value
+fn iterations(iter: u16) -> u16 {
+    use crate::ral::tcd::CITER;
+    if iter & CITER::ELINK::mask != 0 {
+        iter & CITER::ITER_ELINKYES::mask
+    } else {
+        iter & CITER::CITER::mask
+    }
+}
+
+/// Returns a pointer to the last element in the buffer
+///
+/// The pointer is never dereferenced here, so it's OK for an empty
+/// buffer to produce a pointer before the start of the buffer.
+fn last_element<E>(buffer: &[E]) -> *const E {
+    buffer.as_ptr().wrapping_add(buffer.len()).wrapping_sub(1)
+}
+
 /// Assert properties about the circular buffer
 fn circular_buffer_asserts<E>(buffer: &[E]) {
     let len = buffer.len();
@@ -536,16 +1692,16 @@ fn circular_buffer_asserts<E>(buffer: &[E]) {
         "DMA circular buffer size is not power of two"
     );
     let start = buffer.as_ptr();
-    let size = len * core::mem::size_of::<E>();
+    let size = core::mem::size_of_val(buffer);
     assert!(
-        (start as usize) % size == 0,
+        (start as usize).is_multiple_of(size),
         "DMA circular buffer is not properly aligned"
     );
 }
 
 /// Compute the circular buffer modulo value
 fn circular_buffer_modulo<E>(buffer: &[E]) -> u32 {
-    31 - (buffer.len() * core::mem::size_of::<E>()).leading_zeros()
+    31 - core::mem::size_of_val(buffer).leading_zeros()
 }
 
 /// Set a circular buffer as the source for a DMA transfer
@@ -565,6 +1721,7 @@ fn circular_buffer_modulo<E>(buffer: &[E]) -> u32 {
 /// - the capacity is not a power of two
 /// - the alignment is not a multiple of the buffer's size in bytes
 pub unsafe fn set_source_circular_buffer<E: Element>(chan: &mut Channel, source: &[E]) {
+    validate_source(source);
     circular_buffer_asserts(source);
     let modulo = circular_buffer_modulo(source);
 
@@ -594,6 +1751,7 @@ pub unsafe fn set_destination_circular_buffer<E: Element>(
     chan: &mut Channel,
     destination: &mut [E],
 ) {
+    validate_destination(destination);
     circular_buffer_asserts(destination);
     let modulo = circular_buffer_modulo(destination);
 
@@ -602,3 +1760,64 @@ pub unsafe fn set_destination_circular_buffer<E: Element>(
     chan.set_destination_attributes::<E>(modulo as u8);
     chan.set_destination_last_address_adjustment(0);
 }
+
+/// Assert, at build time, that `N` elements describe a circular buffer
+const fn circular_array_asserts<E, const N: usize>() {
+    assert!(
+        N.is_power_of_two(),
+        "DMA circular buffer size is not power of two"
+    );
+    assert!(
+        N <= (1 << 31) / core::mem::size_of::<E>(),
+        "DMA circular buffer is too large"
+    );
+}
+
+/// Set a circular array as the source for a DMA transfer
+///
+/// Like [`set_source_circular_buffer`], but the build fails if the array's length
+/// is not a power of two. Only the alignment is checked at runtime.
+///
+/// # Safety
+///
+/// See [`set_source_circular_buffer`].
+///
+/// # Panics
+///
+/// Panics if the alignment is not a multiple of the buffer's size in bytes.
+pub unsafe fn set_source_circular_array<E: Element, const N: usize>(
+    chan: &mut Channel,
+    source: &[E; N],
+) {
+    const { circular_array_asserts::<E, N>() };
+    set_source_circular_buffer(chan, source);
+}
+
+/// Set a circular array as the destination for a DMA transfer
+///
+/// Like [`set_destination_circular_buffer`], but the build fails if the array's
+/// length is not a power of two. Only the alignment is checked at runtime.
+///
+/// ```compile_fail
+/// use imxrt_dma::channel;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # let mut channel = unsafe { DMA.channel(7) };
+/// let mut samples = [0u16; 12];
+/// // Error: 12 isn't a power of two.
+/// unsafe { channel::set_destination_circular_array(&mut channel, &mut samples) };
+/// ```
+///
+/// # Safety
+///
+/// See [`set_destination_circular_buffer`].
+///
+/// # Panics
+///
+/// Panics if the alignment is not a multiple of the buffer's size in bytes.
+pub unsafe fn set_destination_circular_array<E: Element, const N: usize>(
+    chan: &mut Channel,
+    destination: &mut [E; N],
+) {
+    const { circular_array_asserts::<E, N>() };
+    set_destination_circular_buffer(chan, destination);
+}
@@ -0,0 +1,192 @@
+//! Statically assigned DMA channels.
+//!
+//! A [`Claim`] owns one DMA channel, and its type names the channel number. A driver
+//! that takes a `Claim<7>` can only run on channel 7. Claim a channel with
+//! [`Dma::claim`](crate::Dma::claim); each channel can be claimed once.
+//!
+//! When a design assigns all channels up front, list the assignments in one
+//! [`dma_channels!`](crate::dma_channels) registry. The build fails if two
+//! entries in the registry name the same channel.
+//!
+//! ```
+//! use imxrt_dma::{claim::Claim, dma_channels, Dma};
+//!
+//! static DMA: Dma<32> = // Handle to DMA driver.
+//! # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! dma_channels! {
+//!     /// Channels for every driver on the board.
+//!     pub struct Channels {
+//!         pub lpuart2_tx: 7,
+//!         pub lpspi4_rx: 8,
+//!     }
+//! }
+//!
+//! fn lpuart2_driver(channel: Claim<7>) {
+//!     // ...
+//!     # let _ = channel;
+//! }
+//!
+//! let channels = Channels::take(&DMA).unwrap();
+//! lpuart2_driver(channels.lpuart2_tx);
+//! assert_eq!(channels.lpspi4_rx.channel(), 8);
+//!
+//! assert!(Channels::take(&DMA).is_none());
+//! assert!(DMA.claim::<7>().is_none());
+//! assert!(DMA.split().is_none());
+//! ```
+//!
+//! ```compile_fail
+//! use imxrt_dma::dma_channels;
+//!
+//! dma_channels! {
+//!     struct Channels {
+//!         lpuart2_tx: 7,
+//!         lpspi4_rx: 7, // Build fails: channel 7 is already assigned.
+//!     }
+//! }
+//! ```
+
+use core::sync::atomic::Ordering;
+
+use crate::{channel::Channel, Dma};
+
+/// A DMA channel claimed from the driver
+///
+/// `N` is the channel number. A `Claim` dereferences to its [`Channel`]. Use
+/// [`into_channel`](Self::into_channel) to give the channel to an API that
+/// takes a `Channel` by value.
+pub struct Claim<const N: usize> {
+    channel: Channel,
+}
+
+impl<const N: usize> Claim<N> {
+    /// Create the claim for channel `N`
+    ///
+    /// The build fails if `N` isn't a channel of `dma`.
+    ///
+    /// # Safety
+    ///
+    /// Caller must claim the channel from `dma` before creating the claim, and
+    /// create it only once.
+    #[doc(hidden)]
+    pub unsafe fn new<const CHANNELS: usize>(dma: &'static Dma<CHANNELS>) -> Self {
+        const { assert!(N < CHANNELS, "Invalid DMA channel") };
+        Claim {
+            channel: dma.channel(N),
+        }
+    }
+
+    /// Release the claimed channel
+    ///
+    /// The channel remains claimed, so it can't be claimed again.
+    pub fn into_channel(self) -> Channel {
+        self.channel
+    }
+}
+
+impl<const N: usize> core::ops::Deref for Claim<N> {
+    type Target = Channel;
+    fn deref(&self) -> &Channel {
+        &self.channel
+    }
+}
+
+impl<const N: usize> core::ops::DerefMut for Claim<N> {
+    fn deref_mut(&mut self) -> &mut Channel {
+        &mut self.channel
+    }
+}
+
+impl<const CHANNELS: usize> Dma<CHANNELS> {
+    /// Claim channel `N`
+    ///
+    /// Returns `None` if the channel is already claimed, or if the channels are
+    /// [split](Self::split). The build fails if `N` isn't a channel of this
+    /// driver.
+    ///
+    /// Claims don't prevent [`channel`](Self::channel) from creating another
+    /// handle to the same channel.
+    pub fn claim<const N: usize>(&'static self) -> Option<Claim<N>> {
+        const { assert!(N < CHANNELS, "Invalid DMA channel") };
+        // Safety: the claim is made once for channel N.
+        self.claim_mask(1 << N).then(|| unsafe { Claim::new(self) })
+    }
+
+    /// Claim every channel in `mask`, or none of them
+    ///
+    /// Returns `false` if any channel in `mask` is already claimed.
+    #[doc(hidden)]
+    pub fn claim_mask(&self, mask: u32) -> bool {
+        self.claimed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |claimed| {
+                (claimed & mask == 0).then_some(claimed | mask)
+            })
+            .is_ok()
+    }
+}
+
+/// Fails the build if a channel number appears more than once in `channels`
+#[doc(hidden)]
+pub const fn assert_unique(channels: &[usize]) {
+    let mut i = 0;
+    while i < channels.len() {
+        let mut j = i + 1;
+        while j < channels.len() {
+            assert!(
+                channels[i] != channels[j],
+                "DMA channel assigned more than once"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Declare a registry of statically assigned DMA channels
+///
+/// The macro defines a struct with one [`Claim`](crate::claim::Claim) field for
+/// each entry. The struct's `take` method claims every channel at once, and
+/// returns `None` if any channel is already claimed. The build fails if two
+/// entries name the same channel. See the [`claim`](crate::claim) module for an
+/// example.
+#[macro_export]
+macro_rules! dma_channels {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field_vis:vis $field:ident: $channel:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $crate::claim::Claim<$channel>,
+            )*
+        }
+
+        const _: () = $crate::claim::assert_unique(&[$($channel),*]);
+
+        impl $name {
+            /// Claim every channel in the registry
+            ///
+            /// Returns `None` if any channel is already claimed.
+            #[allow(dead_code)]
+            $vis fn take<const CHANNELS: usize>(
+                dma: &'static $crate::Dma<CHANNELS>,
+            ) -> ::core::option::Option<Self> {
+                if !dma.claim_mask(0 $(| 1 << $channel)*) {
+                    return ::core::option::Option::None;
+                }
+                // Safety: each channel is claimed, and each appears once.
+                ::core::option::Option::Some($name {
+                    $($field: unsafe { $crate::claim::Claim::new(dma) },)*
+                })
+            }
+        }
+    };
+}
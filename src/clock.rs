@@ -0,0 +1,108 @@
+//! DMA clock gates.
+//!
+//! The DMA controller and DMA multiplexer registers are only accessible when their
+//! clocks are enabled. An access with a gated clock usually ends in a hard fault.
+//! Implement [`ClockGate`] for your clock controller, then use
+//! [`Dma::check_clocks`](crate::Dma::check_clocks) or
+//! [`Dma::enable_clocks`](crate::Dma::enable_clocks) before you use any channels.
+//!
+//! ```
+//! use imxrt_dma::{clock::ClockGate, ConfigError, Dma};
+//!
+//! /// A clock controller with a single DMA clock gate.
+//! struct Ccm { dma_enabled: bool }
+//!
+//! impl ClockGate for Ccm {
+//!     fn is_enabled(&self) -> bool { self.dma_enabled }
+//!     fn enable(&mut self) { self.dma_enabled = true; }
+//! }
+//!
+//! static DMA: Dma<32> = // Handle to DMA driver.
+//!     # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! let mut ccm = Ccm { dma_enabled: false };
+//! assert_eq!(DMA.check_clocks(&ccm), Err(ConfigError::ClockGated));
+//! DMA.enable_clocks(&mut ccm);
+//! assert_eq!(DMA.check_clocks(&ccm), Ok(()));
+//! ```
+
+use crate::ConfigError;
+
+/// A clock controller that gates the DMA controller and DMA multiplexer clocks
+///
+/// HALs should implement this trait on their clock control handles.
+pub trait ClockGate {
+    /// Returns `true` if both the DMA controller and DMA multiplexer clocks
+    /// are enabled
+    fn is_enabled(&self) -> bool;
+    /// Enable the DMA controller and DMA multiplexer clocks
+    fn enable(&mut self);
+}
+
+/// The i.MX RT 1060 clock gate for the DMA controller and multiplexer
+///
+/// The gate is the CG3 field of the CCM's CCGR5 register. This implementation
+/// only touches those bits.
+#[cfg(feature = "imxrt1060")]
+pub struct Ccm {
+    ccgr5: *mut u32,
+}
+
+#[cfg(feature = "imxrt1060")]
+impl Ccm {
+    /// Offset of CCGR5 from the start of the CCM
+    const CCGR5: usize = 0x7C;
+    /// Mask of CG3 in CCGR5
+    const CG3: u32 = 0b11 << 6;
+
+    /// Create a clock gate handle from the CCM address
+    ///
+    /// # Safety
+    ///
+    /// `ccm` must point to the start of the CCM register block. Since the handle
+    /// modifies a CCM register, you must prevent concurrent writes to that register.
+    pub const unsafe fn new(ccm: *const ()) -> Self {
+        Ccm {
+            ccgr5: (ccm as *mut u8).wrapping_add(Self::CCGR5).cast(),
+        }
+    }
+}
+
+#[cfg(feature = "imxrt1060")]
+impl ClockGate for Ccm {
+    fn is_enabled(&self) -> bool {
+        // Safety: pointer is valid per constructor. Read has no side effects.
+        let ccgr5 = unsafe { self.ccgr5.read_volatile() };
+        ccgr5 & Self::CG3 == Self::CG3
+    }
+    fn enable(&mut self) {
+        // Safety: pointer is valid, and access is exclusive, per constructor.
+        unsafe {
+            let ccgr5 = self.ccgr5.read_volatile();
+            self.ccgr5.write_volatile(ccgr5 | Self::CG3);
+        }
+    }
+}
+
+impl<const CHANNELS: usize> crate::Dma<CHANNELS> {
+    /// Check that the DMA clocks are enabled
+    ///
+    /// Returns an error if `gate` reports that the clocks are disabled. Call this
+    /// before you use any channels.
+    pub fn check_clocks<G: ClockGate>(&self, gate: &G) -> Result<(), ConfigError> {
+        if gate.is_enabled() {
+            Ok(())
+        } else {
+            Err(ConfigError::ClockGated)
+        }
+    }
+
+    /// Enable the DMA clocks, if they're not already enabled
+    ///
+    /// Call this before you use any channels.
+    pub fn enable_clocks<G: ClockGate>(&self, gate: &mut G) {
+        if !gate.is_enabled() {
+            gate.enable();
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Coalesce the completions of many small transfers.
+//!
+//! A transfer future wakes its task for every interrupt. When channels complete
+//! thousands of tiny transfers each second, those wakes cost more than the
+//! transfers. A [`Coalescer`] trades latency for throughput. Its interrupt handler
+//! only counts each channel's completions, and wakes one task, once per batch. That
+//! task [drains](Coalescer::next_batch) every completion recorded since the last
+//! batch.
+//!
+//! Route the interrupt handlers of the coalesced channels to
+//! [`Coalescer::on_interrupt`], not to [`Dma::on_interrupt`](crate::Dma::on_interrupt).
+//! Configure each channel to interrupt on completion, then start transfers
+//! without awaiting them.
+//!
+//! ```no_run
+//! use imxrt_dma::{coalesce::Coalescer, Dma};
+//! static DMA: Dma<32> = // Handle to DMA driver.
+//! # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # async fn f() {
+//!
+//! static COALESCER: Coalescer = Coalescer::new();
+//!
+//! // #[cortex_m_rt::interrupt]
+//! fn DMA7_DMA23() {
+//!     // Safety: channels 7 and 23 are only used for
+//!     // coalesced transfers.
+//!     unsafe {
+//!         COALESCER.on_interrupt(&DMA, 7);
+//!         COALESCER.on_interrupt(&DMA, 23);
+//!     }
+//! }
+//!
+//! loop {
+//!     let batch = COALESCER.next_batch().await;
+//!     for (channel, completions) in batch.iter() {
+//!         // Start the next `completions` transfers on `channel`...
+//!         # let _ = (channel, completions);
+//!     }
+//! }
+//! # }
+//! ```
+
+use core::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{channel::MAX_CHANNELS, sync::Mutex, ConfigError, Dma};
+
+/// The completions recorded since the last batch, and the draining task
+struct State {
+    batch: Cell<Batch>,
+    waker: Cell<Option<Waker>>,
+}
+
+/// Counts channel completions in interrupt handlers, and reports them in batches
+///
+/// See the [module documentation](crate::coalesce) for more information.
+pub struct Coalescer {
+    state: Mutex<State>,
+}
+
+impl Coalescer {
+    /// Create a coalescer without any recorded completions
+    pub const fn new() -> Self {
+        Coalescer {
+            state: Mutex::new(State {
+                batch: Cell::new(Batch::new()),
+                waker: Cell::new(None),
+            }),
+        }
+    }
+
+    /// Record the interrupt of the channel identified by `channel`
+    ///
+    /// Clears the channel's interrupt, and counts one completion. If the channel
+    /// has an error, the batch flags the channel. Only the first interrupt of each
+    /// batch wakes the draining task.
+    ///
+    /// # Safety
+    ///
+    /// The channel must only be used for coalesced transfers. Caller must ensure
+    /// that `on_interrupt` is called in the correct interrupt handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    /// See [`try_on_interrupt`](Self::try_on_interrupt) for a non-panicking version.
+    #[inline(always)]
+    pub unsafe fn on_interrupt<const CHANNELS: usize>(
+        &self,
+        dma: &'static Dma<CHANNELS>,
+        channel: usize,
+    ) {
+        self.try_on_interrupt(dma, channel)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Record the interrupt of the channel identified by `channel`, or return an
+    /// error if the channel is invalid
+    ///
+    /// `try_on_interrupt` never panics. See [`on_interrupt`](Self::on_interrupt) for
+    /// more information.
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt).
+    #[inline(always)]
+    pub unsafe fn try_on_interrupt<const CHANNELS: usize>(
+        &self,
+        dma: &'static Dma<CHANNELS>,
+        channel: usize,
+    ) -> Result<(), ConfigError> {
+        let channel = dma.try_channel(channel)?;
+        let is_interrupt = channel.is_interrupt();
+        if is_interrupt {
+            channel.clear_interrupt();
+        }
+        let is_error = channel.is_error();
+        if !(is_interrupt | is_error) {
+            return Ok(());
+        }
+
+        let index = channel.channel();
+        let waker = self.state.lock(|state| {
+            let mut batch = state.batch.replace(Batch::new());
+            if is_interrupt {
+                if let Some(count) = batch.counts.get_mut(index) {
+                    *count = count.saturating_add(1);
+                }
+            }
+            if is_error {
+                batch.errors |= 1u32.checked_shl(index as u32).unwrap_or(0);
+            }
+            state.batch.set(batch);
+            state.waker.take()
+        });
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Take every completion recorded since the last batch
+    ///
+    /// The batch may be empty.
+    pub fn drain(&self) -> Batch {
+        self.state.lock(|state| state.batch.replace(Batch::new()))
+    }
+
+    /// Poll for the next batch of completions
+    ///
+    /// Returns `Poll::Pending` while there is nothing to report. Only one task
+    /// should drain the coalescer; each poll replaces the waker of the last poll.
+    ///
+    /// ```no_run
+    /// use core::task::{Context, Poll, Waker};
+    /// use imxrt_dma::coalesce::Coalescer;
+    ///
+    /// static COALESCER: Coalescer = Coalescer::new();
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert!(COALESCER.poll_batch(&mut cx).is_pending());
+    /// assert!(COALESCER.drain().is_empty());
+    /// ```
+    pub fn poll_batch(&self, cx: &mut Context<'_>) -> Poll<Batch> {
+        self.state.lock(|state| {
+            let batch = state.batch.replace(Batch::new());
+            if batch.is_empty() {
+                state.waker.set(Some(cx.waker().clone()));
+                Poll::Pending
+            } else {
+                state.waker.set(None);
+                Poll::Ready(batch)
+            }
+        })
+    }
+
+    /// Wait for the next batch of completions
+    ///
+    /// See [`poll_batch`](Self::poll_batch) for more information.
+    pub fn next_batch(&self) -> NextBatch<'_> {
+        NextBatch { coalescer: self }
+    }
+}
+
+impl Default for Coalescer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The completions that a [`Coalescer`] recorded for each channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    /// Indexed by channel number.
+    counts: [u32; MAX_CHANNELS],
+    errors: u32,
+}
+
+impl Batch {
+    const fn new() -> Self {
+        Batch {
+            counts: [0; MAX_CHANNELS],
+            errors: 0,
+        }
+    }
+
+    /// Returns the number of completions of the channel identified by `channel`
+    ///
+    /// The count saturates at `u32::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    pub fn completions(&self, channel: usize) -> u32 {
+        self.counts[channel]
+    }
+
+    /// Returns a bitmask of the channels that completed; bit `n` is set for channel `n`
+    pub fn channels(&self) -> u32 {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count != 0)
+            .fold(0, |mask, (index, _)| mask | 1 << index)
+    }
+
+    /// Returns a bitmask of the channels that reported an error
+    ///
+    /// Inspect and clear each channel's error before its next transfer.
+    pub fn errors(&self) -> u32 {
+        self.errors
+    }
+
+    /// Returns `true` if the batch records no completions and no errors
+    pub fn is_empty(&self) -> bool {
+        self.errors == 0 && self.counts.iter().all(|&count| count == 0)
+    }
+
+    /// Iterate the channels that completed, with their completion counts
+    pub fn iter(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.counts
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, count)| count != 0)
+    }
+}
+
+/// A future that resolves with the next batch of completions
+///
+/// Use [`Coalescer::next_batch`] to create this future.
+pub struct NextBatch<'a> {
+    coalescer: &'a Coalescer,
+}
+
+impl Future for NextBatch<'_> {
+    type Output = Batch;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Batch> {
+        self.coalescer.poll_batch(cx)
+    }
+}
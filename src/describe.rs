@@ -0,0 +1,235 @@
+//! Summarize a channel for crash dumps and support bundles.
+//!
+//! A DMA failure in the field rarely leaves a debugger attached. Before you reset,
+//! [`describe`](crate::channel::Channel::describe) each channel of interest, and
+//! store the [`Description`] with the rest of the crash dump. A description is
+//! plain data: it records the channel's logical configuration, its progress, and
+//! its status flags, decoded from the registers. With the `serde` feature, it
+//! serializes into any format; with the `defmt` feature, it logs.
+//!
+//! A description also formats as a compact, one-line summary:
+//!
+//! ```no_run
+//! use imxrt_dma::channel::Channel;
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # fn persist(_: &dyn core::fmt::Display) {}
+//!
+//! let channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! // In the HardFault handler:
+//! let description = channel.describe();
+//! persist(&description);
+//! // "DMA0 channel 7: off, 0x20000000 (+4, 4 B) -> 0x20200000 (+4, 4 B), ..."
+//! ```
+//!
+//! Describing a channel only reads its registers. It doesn't change the channel's
+//! state, so it's safe to call from a fault handler.
+
+use core::fmt::{self, Display};
+
+use crate::{
+    channel::{Channel, Configuration},
+    ral::{self, tcd::Descriptor},
+    BandwidthControl, ErrorStatus,
+};
+
+/// A channel's configuration and status, decoded from its registers
+///
+/// Use [`Channel::describe`] to describe a channel. Addresses, offsets, and
+/// iteration counts reflect the transfer's progress when the description was
+/// taken. See the [module documentation](crate::describe) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Description {
+    /// The channel number
+    pub channel: u8,
+    /// The controller number
+    pub controller: u8,
+    /// How the DMAMUX triggers the channel
+    pub configuration: Configuration,
+    /// The next source address
+    pub source_address: u32,
+    /// The source offset, in bytes
+    pub source_offset: i16,
+    /// The bytes in each read, or zero if the size is reserved
+    pub source_size: u8,
+    /// The source address modulo
+    pub source_modulo: u8,
+    /// The source last address adjustment, in bytes
+    pub source_last_adjustment: i32,
+    /// The next destination address
+    pub destination_address: u32,
+    /// The destination offset, in bytes
+    pub destination_offset: i16,
+    /// The bytes in each write, or zero if the size is reserved
+    pub destination_size: u8,
+    /// The destination address modulo
+    pub destination_modulo: u8,
+    /// The destination last address adjustment in bytes, or the address of the
+    /// next descriptor if [`scatter_gather`](Self::scatter_gather) is set
+    pub destination_last_adjustment: i32,
+    /// The bytes in each minor loop
+    pub minor_loop_bytes: u32,
+    /// The address offset applied after each minor loop, in bytes
+    ///
+    /// Zero if neither address has a minor loop offset.
+    pub minor_loop_offset: i32,
+    /// The transfer's iterations
+    pub iterations: u16,
+    /// The iterations that haven't run
+    pub remaining: u16,
+    /// The channel that this channel starts after each minor loop
+    pub minor_loop_link: Option<u8>,
+    /// The channel that this channel starts after its major loop
+    pub major_loop_link: Option<u8>,
+    /// The bandwidth control
+    pub bandwidth: Option<BandwidthControl>,
+    /// The channel loads the next descriptor after its major loop
+    pub scatter_gather: bool,
+    /// The channel disables itself after its major loop
+    pub disable_on_completion: bool,
+    /// The channel interrupts after its major loop
+    pub interrupt_on_completion: bool,
+    /// The channel interrupts halfway through its major loop
+    pub interrupt_on_half: bool,
+    /// The channel is enabled for hardware requests
+    pub enabled: bool,
+    /// The channel is receiving a service signal from hardware
+    pub signaling: bool,
+    /// The channel has a pending software request
+    pub pending: bool,
+    /// The channel is executing a minor loop
+    pub active: bool,
+    /// The channel completed its major loop
+    pub complete: bool,
+    /// The channel's interrupt flag is set
+    pub interrupt: bool,
+    /// The controller's error status, if the channel reports an error
+    ///
+    /// The error status describes the controller's last error. Check its channel
+    /// number before you blame this channel.
+    pub error: Option<ErrorStatus>,
+}
+
+impl Channel {
+    /// Describe the channel's configuration and status
+    ///
+    /// See the [`describe`](crate::describe) module for more information.
+    pub fn describe(&self) -> Description {
+        use ral::tcd::{CSR, NBYTES, SATTR};
+
+        let descriptor: Descriptor = self.snapshot();
+        let mapped = ral::read_reg!(crate::ral::dma, self.controller(), CR, EMLM == 1);
+        let offsets = NBYTES::SMLOE::mask | NBYTES::DMLOE::mask;
+        let (minor_loop_bytes, minor_loop_offset) = match (mapped, descriptor.NBYTES & offsets != 0)
+        {
+            (false, _) => (descriptor.NBYTES, 0),
+            (true, false) => (descriptor.NBYTES & !offsets, 0),
+            (true, true) => {
+                // Sign-extend the 20-bit offset.
+                let mloff = ((descriptor.NBYTES & NBYTES::MLOFF::mask) << 2) as i32
+                    >> (NBYTES::MLOFF::offset + 2);
+                (descriptor.NBYTES & NBYTES::NBYTES::mask, mloff)
+            }
+        };
+        let size = |attr: u8| match (attr & SATTR::SIZE::mask) >> SATTR::SIZE::offset {
+            encoding @ 0..=3 => 1 << encoding,
+            5 => 32,
+            _ => 0,
+        };
+        let modulo = |attr: u8| (attr & SATTR::MOD::mask) >> SATTR::MOD::offset;
+        let csr = descriptor.CSR;
+        let bandwidth = match (csr & CSR::BWC::mask) >> CSR::BWC::offset {
+            CSR::BWC::RW::BWC_2 => Some(BandwidthControl::Stall4Cycles),
+            CSR::BWC::RW::BWC_3 => Some(BandwidthControl::Stall8Cycles),
+            _ => None,
+        };
+        let error = self.is_error().then(|| self.error_status());
+
+        Description {
+            channel: self.channel() as u8,
+            controller: self.instance().number(),
+            configuration: self.channel_configuration(),
+            source_address: descriptor.SADDR,
+            source_offset: descriptor.SOFF,
+            source_size: size(descriptor.SATTR),
+            source_modulo: modulo(descriptor.SATTR),
+            source_last_adjustment: descriptor.SLAST,
+            destination_address: descriptor.DADDR,
+            destination_offset: descriptor.DOFF,
+            destination_size: size(descriptor.DATTR),
+            destination_modulo: modulo(descriptor.DATTR),
+            destination_last_adjustment: descriptor.DLAST_SGA,
+            minor_loop_bytes,
+            minor_loop_offset,
+            iterations: self.beginning_transfer_iterations(),
+            remaining: self.current_transfer_iterations(),
+            minor_loop_link: self.minor_loop_link().map(|link| link as u8),
+            major_loop_link: self.major_loop_link().map(|link| link as u8),
+            bandwidth,
+            scatter_gather: csr & CSR::ESG::mask != 0,
+            disable_on_completion: csr & CSR::DREQ::mask != 0,
+            interrupt_on_completion: csr & CSR::INTMAJOR::mask != 0,
+            interrupt_on_half: csr & CSR::INTHALF::mask != 0,
+            enabled: self.is_enabled(),
+            signaling: self.is_hardware_signaling(),
+            pending: csr & CSR::START::mask != 0,
+            active: csr & CSR::ACTIVE::mask != 0,
+            complete: csr & CSR::DONE::mask != 0,
+            interrupt: self.is_interrupt(),
+            error,
+        }
+    }
+}
+
+impl Display for Description {
+    /// Format a one-line summary
+    ///
+    /// The summary names the channel, its DMAMUX configuration, both addresses
+    /// with their offsets and sizes, the minor loop, the progress, and the set
+    /// status flags. The error status follows, if there is one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DMA{} channel {}: ", self.controller, self.channel)?;
+        match self.configuration {
+            Configuration::Off => f.write_str("off")?,
+            Configuration::Enable { source, periodic } => {
+                write!(f, "source {source}")?;
+                if periodic {
+                    f.write_str(" periodic")?;
+                }
+            }
+            Configuration::AlwaysOn => f.write_str("always on")?,
+            Configuration::AlwaysOnPeriodic => f.write_str("always on periodic")?,
+        }
+        write!(
+            f,
+            ", {:#010X} ({:+}, {} B) -> {:#010X} ({:+}, {} B), minor {} B, {}/{} left",
+            self.source_address,
+            self.source_offset,
+            self.source_size,
+            self.destination_address,
+            self.destination_offset,
+            self.destination_size,
+            self.minor_loop_bytes,
+            self.remaining,
+            self.iterations,
+        )?;
+        let flags = [
+            (self.enabled, "ERQ"),
+            (self.signaling, "HRS"),
+            (self.pending, "START"),
+            (self.active, "ACTIVE"),
+            (self.complete, "DONE"),
+            (self.interrupt, "INT"),
+        ];
+        for (_, name) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, " {name}")?;
+        }
+        if let Some(error) = self.error {
+            write!(f, ", {error}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1523 @@
+//! Double-buffered streaming for audio peripherals.
+//!
+//! [`Playback`] continuously sends a buffer to a [`Destination`], like a SAI
+//! transmitter. [`Capture`] continuously receives data from a [`Source`], like
+//! a SAI receiver, into a buffer. In both cases, the buffer is split into two
+//! halves. While the DMA channel works on one half, your code works on the other
+//! half. The DMA channel wraps around the buffer forever, without any need for
+//! software to re-arm the transfer.
+//!
+//! Use [`Playback::next_half`] and [`Capture::next_half`] to wait for the next half that's
+//! ready for your code. The streams enable interrupts when the DMA channel finishes
+//! each half. To wake the executor, route the DMA channel's interrupt handler to
+//! [`on_interrupt()`](crate::Dma::on_interrupt). Otherwise, you can poll the
+//! futures in a loop.
+//!
+//! If your code takes too long to work on a half, the DMA channel wraps around into
+//! a half that's not ready. The streams detect this as an underrun (playback) or
+//! an overrun (capture). Detection is best effort: the streams detect a late
+//! half when it's within one buffer period. After reporting the error, the stream
+//! resynchronizes with the DMA channel, and continues to run.
+//!
+//! [`StreamWriter`] runs a `Playback`, and refills each half from a closure, so you
+//! can stream generated data without holding the whole payload in memory.
+//!
+//! [`LinkedPlayback`] and [`LinkedCapture`] alternate between two separate buffers.
+//! Each buffer has its own descriptor, and the descriptors link to each other, so
+//! the DMA engine switches buffers without any CPU work.
+//!
+//! [`StampedCapture`] pairs a `Capture` with a second DMA channel, which reads a
+//! free-running timer after each element. Each half arrives with a parallel half
+//! of hardware timestamps.
+//!
+//! [`RingPlayback`] and [`RingCapture`] rotate through `N` buffers, like a linked
+//! stream with more than two buffers. When your code is sometimes slow, and
+//! sometimes fast, the extra buffers absorb the bursts.
+
+use crate::{
+    channel::{self, Channel, Configuration},
+    interrupt,
+    peripheral::{Destination, ReadableRegister, Source},
+    tcd::Tcd,
+    ConfigError, Element, Error,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Tracks the position of the DMA channel, and the position of the user,
+/// within the buffer halves
+///
+/// Positions are absolute half counts. The physical half is the position
+/// modulo two.
+struct Halves {
+    /// The next half handed to the user
+    user: usize,
+    /// The number of times the DMA channel wrapped around the buffer
+    laps: usize,
+}
+
+impl Halves {
+    const fn new() -> Self {
+        Halves { user: 0, laps: 0 }
+    }
+
+    /// Returns the physical half that's ready for the user
+    ///
+    /// Returns `Err(())` if the DMA channel has advanced beyond the half
+    /// that's next for the user. In this case, the user's position moves
+    /// to the latest half that's ready.
+    fn poll(&mut self, channel: &Channel, len: usize) -> Poll<Result<usize, ()>> {
+        if channel.is_complete() {
+            channel.clear_complete();
+            self.laps = self.laps.wrapping_add(1);
+        }
+        let transferred = len.saturating_sub(channel.current_transfer_iterations() as usize);
+        let active = (transferred >= len / 2) as usize;
+        let dma = self.laps.wrapping_mul(2).wrapping_add(active);
+
+        // Interpret as signed, since a wrap that occurs between the DONE and CITER
+        // reads may make the DMA position briefly appear behind the user.
+        let ahead = dma.wrapping_sub(self.user) as isize;
+        if ahead == 1 {
+            let half = self.user % 2;
+            self.user = self.user.wrapping_add(1);
+            Poll::Ready(Ok(half))
+        } else if ahead > 1 {
+            self.user = dma.wrapping_sub(1);
+            Poll::Ready(Err(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Returns the physical half of the buffer
+fn half_mut<E>(buffer: &mut [E], half: usize) -> &mut [E] {
+    let half_len = buffer.len() / 2;
+    &mut buffer[half * half_len..][..half_len]
+}
+
+/// Register the waker, then check for a channel error
+fn poll_error(channel: &Channel, cx: &mut Context<'_>) -> Result<(), Error> {
+    interrupt::register_waker(channel, cx.waker());
+    if channel.is_error() {
+        let es = channel.error_status();
+        channel.clear_error();
+        Err(es.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Common channel configuration for a continuous, two-half transfer
+fn prepare_stream<E: Element>(channel: &mut Channel, len: usize) {
+    assert!(
+        len > 0 && len.is_multiple_of(2),
+        "DMA stream buffer size must be a non-zero multiple of two"
+    );
+    assert!(len <= 0x7FFF, "DMA stream buffer is too large");
+
+    channel.disable();
+    // The channel runs until the stream is dropped.
+    channel.set_disable_on_completion(false);
+    channel.set_interrupt_on_completion(true);
+    channel.set_interrupt_on_half_completion(true);
+    channel.clear_complete();
+    channel.clear_error();
+
+    // Safety: one element per minor loop, and as many iterations as
+    // there are elements in the buffer.
+    unsafe {
+        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+        channel.set_transfer_iterations(len as u16);
+    }
+}
+
+/// Stop a continuous transfer, and release the channel waker
+fn finish_stream(channel: &Channel) {
+    channel.disable();
+    while channel.is_hardware_signaling() {}
+    channel.clear_complete();
+    channel.clear_error();
+    interrupt::clear_waker(channel);
+}
+
+/// Continuously send a buffer to a peripheral
+///
+/// Use [`Playback::new`] to start the stream. The stream runs until it's dropped.
+///
+/// # Example
+///
+/// Play a stereo, 16-bit audio stream through a SAI transmitter. Refill each half
+/// when it's ready.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::Playback, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u16> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+/// # fn synthesize(_: &mut [u16]) {}
+///
+/// // #[cortex_m_rt::interrupt]
+/// fn DMA7() {
+///     // Safety: DMA channel 7 valid and used by a stream.
+///     unsafe { DMA.on_interrupt(7) };
+/// }
+///
+/// # async fn f() {
+/// let mut sai_tx = // A SAI transmitter
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// // TODO unmask interrupts in NVIC!
+///
+/// let mut buffer = [0u16; 2 * 256];
+/// synthesize(&mut buffer);
+///
+/// let mut playback = Playback::new(&mut channel_7, &mut sai_tx, &mut buffer);
+/// loop {
+///     match playback.next_half().await {
+///         Ok(half) => synthesize(half),
+///         Err(err) => { /* Handle the underrun... */ }
+///     }
+/// }
+/// # }
+/// ```
+pub struct Playback<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    destination: &'a mut D,
+    buffer: &'a mut [E],
+    halves: Halves,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, D, E> Playback<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Start sending `buffer` to `destination`
+    ///
+    /// Fill `buffer` with the initial data before starting the stream. The
+    /// DMA channel immediately starts sending the first half.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty, if the buffer has an odd number of elements, or
+    /// if the buffer has more than 32767 elements.
+    pub fn new(channel: &'a mut Channel, destination: &'a mut D, buffer: &'a mut [E]) -> Self {
+        prepare_stream::<E>(channel, buffer.len());
+        channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+        // Safety: the stream borrows the buffer, and it's valid while the
+        // stream runs. The source wraps around to the start of the buffer.
+        let enable = unsafe {
+            channel::set_source_linear_buffer(channel, buffer);
+            channel::set_destination_hardware(channel, destination.destination_address());
+            channel.try_enable()
+        };
+        if enable.is_ok() {
+            destination.enable_destination();
+        }
+
+        Playback {
+            channel,
+            destination,
+            buffer,
+            halves: Halves::new(),
+            enable,
+        }
+    }
+
+    /// Poll for the next half that's ready for new data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still sending the next half.
+    /// The returned half is yours until you poll again.
+    pub fn poll_next_half(&mut self, cx: &mut Context<'_>) -> Poll<Result<&mut [E], Error>> {
+        let half = core::task::ready!(self.poll_half(cx))?;
+        Poll::Ready(Ok(half_mut(self.buffer, half)))
+    }
+
+    /// Poll for the index of the next half
+    fn poll_half(&mut self, cx: &mut Context<'_>) -> Poll<Result<usize, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        self.halves
+            .poll(self.channel, self.buffer.len())
+            .map_err(|()| Error::Underrun)
+    }
+
+    /// Wait for the next half that's ready for new data
+    ///
+    /// See [`poll_next_half`](Self::poll_next_half) for more information.
+    pub fn next_half(&mut self) -> Next<'_, Self> {
+        Next { stream: Some(self) }
+    }
+}
+
+impl<D, E> Drop for Playback<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.destination.disable_destination();
+        finish_stream(self.channel);
+    }
+}
+
+/// Continuously receive data from a peripheral into a buffer
+///
+/// Use [`Capture::new`] to start the stream. The stream runs until it's dropped.
+///
+/// # Example
+///
+/// Capture a stereo, 16-bit audio stream from a SAI receiver. Process each half
+/// when it's ready.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::Capture, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u16> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # fn process(_: &[u16]) {}
+///
+/// # async fn f() {
+/// let mut sai_rx = // A SAI receiver
+///     # X;
+/// let mut channel_8: Channel = // DMA channel 8
+///     # unsafe { DMA.channel(8) };
+///
+/// let mut buffer = [0u16; 2 * 256];
+/// let mut capture = Capture::new(&mut channel_8, &mut sai_rx, &mut buffer);
+/// loop {
+///     match capture.next_half().await {
+///         Ok(half) => process(half),
+///         Err(err) => { /* Handle the overrun... */ }
+///     }
+/// }
+/// # }
+/// ```
+pub struct Capture<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    halves: Halves,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, S, E> Capture<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Start receiving data from `source` into `buffer`
+    ///
+    /// The DMA channel immediately starts receiving into the first half.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty, if the buffer has an odd number of elements, or
+    /// if the buffer has more than 32767 elements.
+    pub fn new(channel: &'a mut Channel, source: &'a mut S, buffer: &'a mut [E]) -> Self {
+        prepare_stream::<E>(channel, buffer.len());
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+        // Safety: the stream borrows the buffer, and it's valid while the
+        // stream runs. The destination wraps around to the start of the buffer.
+        let enable = unsafe {
+            channel::set_source_hardware(channel, source.source_address());
+            channel::set_destination_linear_buffer(channel, buffer);
+            channel.try_enable()
+        };
+        if enable.is_ok() {
+            source.enable_source();
+        }
+
+        Capture {
+            channel,
+            source,
+            buffer,
+            halves: Halves::new(),
+            enable,
+        }
+    }
+
+    /// Poll for the next half that's full of received data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still receiving into the next
+    /// half. The returned half is yours until you poll again.
+    pub fn poll_next_half(&mut self, cx: &mut Context<'_>) -> Poll<Result<&mut [E], Error>> {
+        let half = core::task::ready!(self.poll_half(cx))?;
+        Poll::Ready(Ok(half_mut(self.buffer, half)))
+    }
+
+    /// Poll for the index of the next half
+    fn poll_half(&mut self, cx: &mut Context<'_>) -> Poll<Result<usize, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        self.halves
+            .poll(self.channel, self.buffer.len())
+            .map_err(|()| Error::Overrun)
+    }
+
+    /// Wait for the next half that's full of received data
+    ///
+    /// See [`poll_next_half`](Self::poll_next_half) for more information.
+    pub fn next_half(&mut self) -> Next<'_, Self> {
+        Next { stream: Some(self) }
+    }
+}
+
+impl<S, E> Drop for Capture<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        finish_stream(self.channel);
+    }
+}
+
+/// A future that resolves with the next half of a stream
+///
+/// Use [`Playback::next_half`] or [`Capture::next_half`] to create this future.
+pub struct Next<'s, T> {
+    stream: Option<&'s mut T>,
+}
+
+impl<'s, D, E> Future for Next<'s, Playback<'_, D, E>>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    type Output = Result<&'s mut [E], Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        match stream.poll_half(cx) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(result) => Poll::Ready(result.map(|half| half_mut(stream.buffer, half))),
+        }
+    }
+}
+
+impl<'s, S, E> Future for Next<'s, Capture<'_, S, E>>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<&'s mut [E], Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        match stream.poll_half(cx) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(result) => Poll::Ready(result.map(|half| half_mut(stream.buffer, half))),
+        }
+    }
+}
+
+/// Continuously receive data from a peripheral, and timestamp every element
+///
+/// A `StampedCapture` is a [`Capture`] with a second DMA channel. After the
+/// capture channel receives each element, it links to the timer channel. The timer
+/// channel reads a free-running timer's counter into a parallel buffer of
+/// timestamps. Element `n` of each half pairs with timestamp `n` of the same half.
+/// The timestamps come from the hardware, so they don't depend on interrupt latency.
+///
+/// The timer channel reads the counter once the capture channel finishes the
+/// element. The timestamp lags the element by the DMA engine's arbitration latency,
+/// which is a few bus cycles when the timer channel isn't preempted.
+///
+/// The timer channel signals the stream's interrupts, since it finishes each half
+/// last. Route the timer channel's interrupt handler to
+/// [`on_interrupt()`](crate::Dma::on_interrupt).
+///
+/// # Example
+///
+/// Capture an accelerometer's samples from a LPSPI receiver, and timestamp each
+/// sample with a GPT counter.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::StampedCapture, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u16> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # struct Gpt { CNT: ral_registers::RORegister<u32> }
+/// # fn process(_: &[u16], _: &[u32]) {}
+///
+/// # async fn f() {
+/// let mut lpspi4_rx = // A LPSPI receiver
+///     # X;
+/// let gpt1: &Gpt = // GPT1 register block
+///     # unsafe { &*(0x401E_C000 as *const Gpt) };
+/// let mut channel_8: Channel = // DMA channel 8
+///     # unsafe { DMA.channel(8) };
+/// let mut channel_9: Channel = // DMA channel 9
+///     # unsafe { DMA.channel(9) };
+///
+/// let mut samples = [0u16; 2 * 64];
+/// let mut stamps = [0u32; 2 * 64];
+///
+/// // Safety: GPT1's counter is valid for DMA reads.
+/// let mut capture = unsafe {
+///     StampedCapture::new(
+///         &mut channel_8,
+///         &mut channel_9,
+///         &mut lpspi4_rx,
+///         &gpt1.CNT,
+///         &mut samples,
+///         &mut stamps,
+///     )
+/// };
+/// loop {
+///     match capture.next_half().await {
+///         Ok((samples, stamps)) => process(samples, stamps),
+///         Err(err) => { /* Handle the overrun... */ }
+///     }
+/// }
+/// # }
+/// ```
+pub struct StampedCapture<'a, S, E, T>
+where
+    S: Source<E>,
+    E: Element,
+    T: Element,
+{
+    channel: &'a mut Channel,
+    timer_channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    stamps: &'a mut [T],
+    halves: Halves,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+/// A half of a [`StampedCapture`], and its timestamps
+pub type StampedHalf<'s, E, T> = (&'s mut [E], &'s mut [T]);
+
+impl<'a, S, E, T> StampedCapture<'a, S, E, T>
+where
+    S: Source<E>,
+    E: Element,
+    T: Element,
+{
+    /// Start receiving data from `source` into `buffer`, and reading `timer`
+    /// into `stamps` after each element
+    ///
+    /// `channel` receives the data, and `timer_channel` reads the timer. The DMA
+    /// channels immediately start on the first half. Transfers of `u8` and `u16`
+    /// read the low bytes of the timer. The build fails if `T` is wider than the
+    /// timer register.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffers have different lengths, if the buffer is empty, if the
+    /// buffer has an odd number of elements, or if the buffer has more than 511
+    /// elements. Panics if both channels are the same channel.
+    ///
+    /// # Safety
+    ///
+    /// `timer` must be valid for DMA reads of `T`, for as long as the stream runs.
+    pub unsafe fn new(
+        channel: &'a mut Channel,
+        timer_channel: &'a mut Channel,
+        source: &'a mut S,
+        timer: &impl ReadableRegister,
+        buffer: &'a mut [E],
+        stamps: &'a mut [T],
+    ) -> Self {
+        assert_eq!(
+            buffer.len(),
+            stamps.len(),
+            "DMA timestamp buffer must match the data buffer"
+        );
+        assert!(
+            buffer.len() <= 511,
+            "DMA stream buffer is too large to link"
+        );
+        assert_ne!(
+            channel.channel(),
+            timer_channel.channel(),
+            "DMA timestamps need their own channel"
+        );
+
+        prepare_stream::<T>(timer_channel, stamps.len());
+        // The capture channel starts the timer channel, so there's no DMAMUX
+        // source, and no hardware request.
+        timer_channel.set_channel_configuration(Configuration::Off);
+        channel::set_source_hardware(
+            timer_channel,
+            crate::peripheral::register_address::<_, T>(timer),
+        );
+        channel::set_destination_linear_buffer(timer_channel, stamps);
+
+        prepare_stream::<E>(channel, buffer.len());
+        // Only the timer channel signals the halves.
+        channel.set_interrupt_on_completion(false);
+        channel.set_interrupt_on_half_completion(false);
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+        // Safety: the stream borrows both buffers, and they're valid while the stream
+        // runs. Both destinations wrap around to the start of their buffers. The
+        // timer channel describes a valid transfer. The minor loop link doesn't
+        // run after the final element, so the major loop link covers it.
+        channel::set_source_hardware(channel, source.source_address());
+        channel::set_destination_linear_buffer(channel, buffer);
+        channel.set_minor_loop_link(Some(timer_channel.channel()));
+        channel.set_major_loop_link(Some(timer_channel.channel()));
+        let enable = channel.try_enable();
+        if enable.is_ok() {
+            source.enable_source();
+        }
+
+        StampedCapture {
+            channel,
+            timer_channel,
+            source,
+            buffer,
+            stamps,
+            halves: Halves::new(),
+            enable,
+        }
+    }
+
+    /// Poll for the next half that's full of received data, and its timestamps
+    ///
+    /// Returns `Poll::Pending` if the DMA channels are still receiving into the next
+    /// half. The returned halves are yours until you poll again.
+    pub fn poll_next_half(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<StampedHalf<'_, E, T>, Error>> {
+        let half = core::task::ready!(self.poll_half(cx))?;
+        Poll::Ready(Ok((
+            half_mut(self.buffer, half),
+            half_mut(self.stamps, half),
+        )))
+    }
+
+    /// Poll for the index of the next half
+    fn poll_half(&mut self, cx: &mut Context<'_>) -> Poll<Result<usize, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        poll_error(self.timer_channel, cx)?;
+        self.halves
+            .poll(self.timer_channel, self.stamps.len())
+            .map_err(|()| Error::Overrun)
+    }
+
+    /// Wait for the next half that's full of received data, and its timestamps
+    ///
+    /// See [`poll_next_half`](Self::poll_next_half) for more information.
+    pub fn next_half(&mut self) -> Next<'_, Self> {
+        Next { stream: Some(self) }
+    }
+}
+
+impl<S, E, T> Drop for StampedCapture<'_, S, E, T>
+where
+    S: Source<E>,
+    E: Element,
+    T: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        finish_stream(self.channel);
+        // Safety: removes the links, so the capture channel can't start the timer
+        // channel after the stream.
+        unsafe {
+            self.channel.set_minor_loop_link(None);
+            self.channel.set_major_loop_link(None);
+        }
+        while self.timer_channel.is_active() {}
+        finish_stream(self.timer_channel);
+    }
+}
+
+impl<'s, S, E, T> Future for Next<'s, StampedCapture<'_, S, E, T>>
+where
+    S: Source<E>,
+    E: Element,
+    T: Element,
+{
+    type Output = Result<StampedHalf<'s, E, T>, Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        match stream.poll_half(cx) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(result) => Poll::Ready(
+                result.map(|half| (half_mut(stream.buffer, half), half_mut(stream.stamps, half))),
+            ),
+        }
+    }
+}
+
+/// Continuously send generated data to a peripheral
+///
+/// A `StreamWriter` runs a [`Playback`] stream, and refills each half by calling
+/// your `fill` closure. Use it to send data that's generated as it's sent, like
+/// test patterns, computed waveforms, or decompressed data, without holding the
+/// whole payload in memory.
+///
+/// `fill` receives the next half of the buffer, and returns the number of elements
+/// that it wrote into the start of the half. Return fewer elements than the half's
+/// length to end the stream. The writer fills the rest of the half, and every half
+/// after it, with the `idle` element. The writer resolves once the DMA channel has
+/// sent the final half that has data. The DMA channel may send a few idle elements
+/// before the writer stops the stream.
+///
+/// # Example
+///
+/// Send a sawtooth through a SAI transmitter, 256 samples at a time.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::StreamWriter, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u16> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut sai_tx = // A SAI transmitter
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut samples = (0..48_000u32).map(|n| (n % 1024) as u16);
+/// let mut buffer = [0u16; 2 * 256];
+///
+/// StreamWriter::new(&mut channel_7, &mut sai_tx, &mut buffer, 0, |half| {
+///     half.iter_mut().zip(&mut samples).map(|(slot, sample)| *slot = sample).count()
+/// })
+/// .await?;
+/// # Ok(()) }
+/// ```
+pub struct StreamWriter<'a, D, E, F>
+where
+    D: Destination<E>,
+    E: Element,
+    F: FnMut(&mut [E]) -> usize,
+{
+    /// `None` once the stream is finished.
+    playback: Option<Playback<'a, D, E>>,
+    refill: Refill<E, F>,
+    /// The number of halves sent.
+    sent: usize,
+}
+
+/// Refills the halves of a [`StreamWriter`]
+struct Refill<E, F> {
+    fill: F,
+    idle: E,
+    /// The number of halves filled.
+    filled: usize,
+    /// The index of the final half with data, once `fill` ends the stream.
+    last: Option<usize>,
+}
+
+impl<E: Element, F: FnMut(&mut [E]) -> usize> Refill<E, F> {
+    /// Fill the next half, and return the number of elements from `fill`
+    fn half(&mut self, half: &mut [E]) -> usize {
+        let written = if self.last.is_some() {
+            0
+        } else {
+            let written = (self.fill)(half).min(half.len());
+            if written < half.len() {
+                self.last = Some(self.filled);
+            }
+            written
+        };
+        half[written..].fill(self.idle);
+        self.filled += 1;
+        written
+    }
+}
+
+impl<'a, D, E, F> StreamWriter<'a, D, E, F>
+where
+    D: Destination<E>,
+    E: Element,
+    F: FnMut(&mut [E]) -> usize,
+{
+    /// Fill both halves of `buffer`, then start sending it to `destination`
+    ///
+    /// If `fill` doesn't write any elements into the first half, the writer never
+    /// starts the stream, and it resolves on its first poll.
+    ///
+    /// ```
+    /// use core::task::{Context, Poll, Waker};
+    /// use imxrt_dma::double_buffer::StreamWriter;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # struct X;
+    /// # unsafe impl imxrt_dma::peripheral::Destination<u8> for X {
+    /// #   fn destination_signal(&self) -> u32 { 0 }
+    /// #   fn destination_address(&self) -> *const u8 { panic!() }
+    /// #   fn enable_destination(&mut self) { panic!() }
+    /// #   fn disable_destination(&mut self) { panic!() }
+    /// # }
+    /// # let mut channel = unsafe { DMA.channel(7) };
+    /// # let mut lpuart = X;
+    ///
+    /// let mut buffer = [0u8; 8];
+    /// let mut writer = StreamWriter::new(&mut channel, &mut lpuart, &mut buffer, 0, |_| 0);
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(writer.poll_write(&mut cx), Poll::Ready(Ok(())));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty, if the buffer has an odd number of elements, or
+    /// if the buffer has more than 32767 elements.
+    pub fn new(
+        channel: &'a mut Channel,
+        destination: &'a mut D,
+        buffer: &'a mut [E],
+        idle: E,
+        fill: F,
+    ) -> Self {
+        assert!(
+            !buffer.is_empty() && buffer.len().is_multiple_of(2),
+            "DMA stream buffer size must be a non-zero multiple of two"
+        );
+        let mut refill = Refill {
+            fill,
+            idle,
+            filled: 0,
+            last: None,
+        };
+        let mut playback = None;
+        if refill.half(half_mut(buffer, 0)) > 0 {
+            refill.half(half_mut(buffer, 1));
+            playback = Some(Playback::new(channel, destination, buffer));
+        }
+        StreamWriter {
+            playback,
+            refill,
+            sent: 0,
+        }
+    }
+
+    /// Poll the stream, refilling each half when it's ready
+    ///
+    /// Returns `Poll::Ready(Ok(()))` once the DMA channel has sent the final half
+    /// with data. If the DMA channel underruns, the writer stops the stream, and
+    /// returns the error.
+    pub fn poll_write(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        loop {
+            let Some(playback) = self.playback.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            let half = match playback.poll_next_half(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(err)) => {
+                    self.playback = None;
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Ready(Ok(half)) => half,
+            };
+            self.sent += 1;
+            if self.refill.last.is_some_and(|last| self.sent > last) {
+                self.playback = None;
+                return Poll::Ready(Ok(()));
+            }
+            self.refill.half(half);
+        }
+    }
+}
+
+impl<D, E, F> Future for StreamWriter<'_, D, E, F>
+where
+    D: Destination<E>,
+    E: Element,
+    F: FnMut(&mut [E]) -> usize,
+{
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: nothing in the writer is pinned.
+        unsafe { self.get_unchecked_mut() }.poll_write(cx)
+    }
+}
+
+/// Descriptor storage for [`RingPlayback`] and [`RingCapture`]
+///
+/// The ring holds one descriptor for each of `N` buffers. The DMA engine reloads
+/// these descriptors after each buffer, so they must not move while a stream runs.
+/// The stream borrows the descriptors to guarantee this.
+pub struct Ring<const N: usize> {
+    tcds: [Tcd; N],
+}
+
+impl<const N: usize> Ring<N> {
+    /// Create storage for `N` linked descriptors
+    ///
+    /// The build fails if `N` is less than two.
+    pub const fn new() -> Self {
+        const { assert!(N >= 2, "DMA buffer ring needs at least two buffers") };
+        Ring {
+            tcds: [Tcd::new(); N],
+        }
+    }
+}
+
+impl<const N: usize> Default for Ring<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Descriptor storage for [`LinkedPlayback`] and [`LinkedCapture`]
+///
+/// A linked stream is a [`Ring`] of two buffers.
+pub type Links = Ring<2>;
+
+/// Link each descriptor to the next, and the last to the first, then load the
+/// first descriptor
+///
+/// `configure` describes the transfer for each buffer on the channel.
+fn load_ring(
+    tcds: &mut [Tcd],
+    channel: &mut Channel,
+    mut configure: impl FnMut(&mut Channel, usize),
+) {
+    for (buffer, tcd) in tcds.iter_mut().enumerate() {
+        configure(channel, buffer);
+        *tcd = Tcd::capture(channel);
+    }
+    for index in 0..tcds.len() {
+        let next = core::ptr::addr_of!(tcds[(index + 1) % tcds.len()]);
+        crate::tcd::link_to(&mut tcds[index], next);
+    }
+    // Safety: the transfers were configured on the channel.
+    unsafe { tcds[0].apply(channel) };
+}
+
+/// Returns the index of the descriptor that the DMA channel is running
+fn active_ring(tcds: &[Tcd], channel: &Channel) -> usize {
+    // The active descriptor links to the descriptor after it.
+    let next = channel.snapshot().DLAST_SGA as *const Tcd;
+    let next = tcds
+        .iter()
+        .position(|tcd| core::ptr::eq(tcd, next))
+        .unwrap_or(1);
+    (next + tcds.len() - 1) % tcds.len()
+}
+
+/// Check the buffers of a linked stream, and prepare the channel
+fn prepare_linked<E>(channel: &mut Channel, buffers: &[&mut [E]]) {
+    for buffer in buffers {
+        assert!(
+            !buffer.is_empty() && buffer.len() <= 0x7FFF,
+            "DMA stream buffer size must be between 1 and 32767 elements"
+        );
+    }
+    channel.disable();
+    // The channel runs until the stream is dropped.
+    channel.set_disable_on_completion(false);
+    channel.set_interrupt_on_completion(true);
+    channel.set_interrupt_on_half_completion(false);
+    channel.clear_complete();
+    channel.clear_error();
+}
+
+/// Continuously send two buffers to a peripheral, alternating between them
+///
+/// Unlike [`Playback`], the buffers don't need to be contiguous. Each buffer has its
+/// own descriptor, and each descriptor links to the other. The DMA engine switches
+/// buffers by loading the next descriptor, so there's no CPU work, and no re-arm
+/// jitter, between buffers. Interrupts only notify your code that a buffer is ready.
+///
+/// A linked stream is a [`RingPlayback`] of two buffers. Instead of lending a
+/// [`Slot`], it lends each buffer until your next poll.
+///
+/// Use [`LinkedPlayback::new`] to start the stream. The stream runs until it's dropped.
+///
+/// A linked stream can't detect an underrun. If your code is more than one buffer
+/// late, the DMA channel resends the stale buffer, and the stream hands you buffers
+/// one lap late.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::{LinkedPlayback, Links}, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u16> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+/// # fn synthesize(_: &mut [u16]) {}
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut sai_tx = // A SAI transmitter
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut ping = [0u16; 256];
+/// let mut pong = [0u16; 256];
+/// let mut links = Links::new();
+///
+/// let mut playback = LinkedPlayback::new(&mut channel_7, &mut sai_tx, [&mut ping, &mut pong], &mut links);
+/// loop {
+///     synthesize(playback.next_buffer().await?);
+/// }
+/// # }
+/// ```
+pub struct LinkedPlayback<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    ring: RingPlayback<'a, D, E, 2>,
+}
+
+impl<'a, D, E> LinkedPlayback<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Start sending `buffers` to `destination`
+    ///
+    /// Fill both buffers with the initial data before starting the stream. The
+    /// DMA channel immediately starts sending the first buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either buffer is empty, or has more than 32767 elements.
+    pub fn new(
+        channel: &'a mut Channel,
+        destination: &'a mut D,
+        buffers: [&'a mut [E]; 2],
+        links: &'a mut Links,
+    ) -> Self {
+        LinkedPlayback {
+            ring: RingPlayback::new(channel, destination, buffers, links),
+        }
+    }
+
+    /// Poll for the next buffer that's ready for new data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still sending the next buffer.
+    /// The returned buffer is yours until you poll again.
+    pub fn poll_next_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<&mut [E], Error>> {
+        let buffer = core::task::ready!(self.ring.poll_lend(cx))?;
+        Poll::Ready(Ok(&mut *self.ring.buffers[buffer]))
+    }
+
+    /// Wait for the next buffer that's ready for new data
+    ///
+    /// See [`poll_next_buffer`](Self::poll_next_buffer) for more information.
+    pub fn next_buffer(&mut self) -> NextBuffer<'_, Self> {
+        NextBuffer { stream: Some(self) }
+    }
+}
+
+/// Continuously receive data from a peripheral into two buffers, alternating
+/// between them
+///
+/// See [`LinkedPlayback`] for how a linked stream differs from [`Capture`]. A linked
+/// capture can't detect an overrun.
+///
+/// Use [`LinkedCapture::new`] to start the stream. The stream runs until it's dropped.
+pub struct LinkedCapture<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    ring: RingCapture<'a, S, E, 2>,
+}
+
+impl<'a, S, E> LinkedCapture<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Start receiving data from `source` into `buffers`
+    ///
+    /// The DMA channel immediately starts receiving into the first buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either buffer is empty, or has more than 32767 elements.
+    pub fn new(
+        channel: &'a mut Channel,
+        source: &'a mut S,
+        buffers: [&'a mut [E]; 2],
+        links: &'a mut Links,
+    ) -> Self {
+        LinkedCapture {
+            ring: RingCapture::new(channel, source, buffers, links),
+        }
+    }
+
+    /// Poll for the next buffer that's full of received data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still receiving into the next
+    /// buffer. The returned buffer is yours until you poll again.
+    pub fn poll_next_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<&mut [E], Error>> {
+        let buffer = core::task::ready!(self.ring.poll_lend(cx))?;
+        Poll::Ready(Ok(&mut *self.ring.buffers[buffer]))
+    }
+
+    /// Wait for the next buffer that's full of received data
+    ///
+    /// See [`poll_next_buffer`](Self::poll_next_buffer) for more information.
+    pub fn next_buffer(&mut self) -> NextBuffer<'_, Self> {
+        NextBuffer { stream: Some(self) }
+    }
+}
+
+/// A future that resolves with the next buffer of a linked stream
+///
+/// Use [`LinkedPlayback::next_buffer`] or [`LinkedCapture::next_buffer`] to create
+/// this future.
+pub struct NextBuffer<'s, T> {
+    stream: Option<&'s mut T>,
+}
+
+impl<'s, D, E> Future for NextBuffer<'s, LinkedPlayback<'_, D, E>>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    type Output = Result<&'s mut [E], Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        match stream.ring.poll_lend(cx) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(result) => {
+                Poll::Ready(result.map(|buffer| &mut *stream.ring.buffers[buffer]))
+            }
+        }
+    }
+}
+
+impl<'s, S, E> Future for NextBuffer<'s, LinkedCapture<'_, S, E>>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<&'s mut [E], Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        match stream.ring.poll_lend(cx) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(result) => {
+                Poll::Ready(result.map(|buffer| &mut *stream.ring.buffers[buffer]))
+            }
+        }
+    }
+}
+
+/// Returns `Poll::Ready` once the DMA channel leaves the user's next buffer
+fn poll_ring(tcds: &[Tcd], channel: &Channel, user: usize) -> Poll<()> {
+    if active_ring(tcds, channel) != user {
+        Poll::Ready(())
+    } else {
+        Poll::Pending
+    }
+}
+
+/// Returns the number of buffers, starting at `user`, that the DMA channel left
+fn ready_ring(tcds: &[Tcd], channel: &Channel, user: usize) -> usize {
+    (active_ring(tcds, channel) + tcds.len() - user) % tcds.len()
+}
+
+/// Continuously send `N` buffers to a peripheral, rotating through them
+///
+/// Each buffer has its own descriptor in a [`Ring`], and each descriptor links to
+/// the next. The DMA engine moves to the next buffer without any CPU work. Your
+/// code receives each buffer once the DMA channel has sent it, oldest first, as a
+/// [`Slot`]. Refill the slot, then drop it to return the buffer to the ring.
+///
+/// While you hold a slot, the DMA channel keeps sending the other buffers. Your
+/// code may fall up to `N - 1` buffers behind before the channel resends a stale
+/// buffer. Like [`LinkedPlayback`], a ring can't detect the underrun.
+///
+/// Use [`RingPlayback::new`] to start the stream. The stream runs until it's dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, double_buffer::{Ring, RingPlayback}, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u16> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+/// # fn synthesize(_: &mut [u16]) {}
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut sai_tx = // A SAI transmitter
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let [mut a, mut b, mut c] = [[0u16; 128]; 3];
+/// let mut ring = Ring::new();
+///
+/// let mut playback = RingPlayback::new(&mut channel_7, &mut sai_tx, [&mut a, &mut b, &mut c], &mut ring);
+/// loop {
+///     let mut slot = playback.next_buffer().await?;
+///     synthesize(&mut slot);
+///     // Dropping the slot returns the buffer to the ring.
+/// }
+/// # }
+/// ```
+pub struct RingPlayback<'a, D, E, const N: usize>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    destination: &'a mut D,
+    buffers: [&'a mut [E]; N],
+    ring: &'a mut Ring<N>,
+    user: usize,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, D, E, const N: usize> RingPlayback<'a, D, E, N>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Start sending `buffers` to `destination`
+    ///
+    /// Fill every buffer with the initial data before starting the stream. The
+    /// DMA channel immediately starts sending the first buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any buffer is empty, or has more than 32767 elements.
+    pub fn new(
+        channel: &'a mut Channel,
+        destination: &'a mut D,
+        buffers: [&'a mut [E]; N],
+        ring: &'a mut Ring<N>,
+    ) -> Self {
+        prepare_linked(channel, &buffers);
+        channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+        // Safety: the stream borrows the buffers and descriptors, and they're valid
+        // while the stream runs. Each descriptor sends one buffer.
+        load_ring(&mut ring.tcds, channel, |channel, buffer| unsafe {
+            let buffer = &*buffers[buffer];
+            channel::set_source_linear_buffer(channel, buffer);
+            channel::set_destination_hardware(channel, destination.destination_address());
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(buffer.len() as u16);
+        });
+        // Safety: the channel's transfer is loaded.
+        let enable = unsafe { channel.try_enable() };
+        if enable.is_ok() {
+            destination.enable_destination();
+        }
+
+        RingPlayback {
+            channel,
+            destination,
+            buffers,
+            ring,
+            user: 0,
+            enable,
+        }
+    }
+
+    /// Returns the number of sent buffers that are ready for new data
+    pub fn ready(&self) -> usize {
+        ready_ring(&self.ring.tcds, self.channel, self.user)
+    }
+
+    /// Poll for the oldest buffer that the DMA channel left, and move past it
+    ///
+    /// Returns the buffer's index. Unlike a [`Slot`], the buffer returns to the
+    /// ring immediately, so it's only the user's until the next poll.
+    fn poll_lend(&mut self, cx: &mut Context<'_>) -> Poll<Result<usize, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        core::task::ready!(poll_ring(&self.ring.tcds, self.channel, self.user));
+        let buffer = self.user;
+        self.user = (buffer + 1) % N;
+        Poll::Ready(Ok(buffer))
+    }
+
+    /// Poll for the oldest buffer that's ready for new data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still sending the buffer.
+    pub fn poll_next_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<Slot<'_, E>, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        core::task::ready!(poll_ring(&self.ring.tcds, self.channel, self.user));
+        Poll::Ready(Ok(Slot::new(&mut self.buffers, &mut self.user)))
+    }
+
+    /// Wait for the oldest buffer that's ready for new data
+    ///
+    /// See [`poll_next_buffer`](Self::poll_next_buffer) for more information.
+    pub fn next_buffer(&mut self) -> NextBuffer<'_, Self> {
+        NextBuffer { stream: Some(self) }
+    }
+}
+
+impl<D, E, const N: usize> Drop for RingPlayback<'_, D, E, N>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.destination.disable_destination();
+        finish_stream(self.channel);
+    }
+}
+
+/// Continuously receive data from a peripheral into `N` buffers, rotating through
+/// them
+///
+/// See [`RingPlayback`] for how a ring works. Your code receives each buffer once
+/// the DMA channel has filled it, oldest first. Drop the [`Slot`] to return the
+/// buffer to the ring. A ring capture can't detect an overrun.
+///
+/// Use [`RingCapture::new`] to start the stream. The stream runs until it's dropped.
+pub struct RingCapture<'a, S, E, const N: usize>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffers: [&'a mut [E]; N],
+    ring: &'a mut Ring<N>,
+    user: usize,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, S, E, const N: usize> RingCapture<'a, S, E, N>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Start receiving data from `source` into `buffers`
+    ///
+    /// The DMA channel immediately starts receiving into the first buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any buffer is empty, or has more than 32767 elements.
+    pub fn new(
+        channel: &'a mut Channel,
+        source: &'a mut S,
+        mut buffers: [&'a mut [E]; N],
+        ring: &'a mut Ring<N>,
+    ) -> Self {
+        prepare_linked(channel, &buffers);
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+        // Safety: the stream borrows the buffers and descriptors, and they're valid
+        // while the stream runs. Each descriptor receives into one buffer.
+        load_ring(&mut ring.tcds, channel, |channel, buffer| unsafe {
+            let buffer = &mut *buffers[buffer];
+            channel::set_source_hardware(channel, source.source_address());
+            channel::set_destination_linear_buffer(channel, buffer);
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(buffer.len() as u16);
+        });
+        // Safety: the channel's transfer is loaded.
+        let enable = unsafe { channel.try_enable() };
+        if enable.is_ok() {
+            source.enable_source();
+        }
+
+        RingCapture {
+            channel,
+            source,
+            buffers,
+            ring,
+            user: 0,
+            enable,
+        }
+    }
+
+    /// Returns the number of buffers that are full of received data
+    pub fn ready(&self) -> usize {
+        ready_ring(&self.ring.tcds, self.channel, self.user)
+    }
+
+    /// Poll for the oldest buffer that the DMA channel left, and move past it
+    ///
+    /// Returns the buffer's index. Unlike a [`Slot`], the buffer returns to the
+    /// ring immediately, so it's only the user's until the next poll.
+    fn poll_lend(&mut self, cx: &mut Context<'_>) -> Poll<Result<usize, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        core::task::ready!(poll_ring(&self.ring.tcds, self.channel, self.user));
+        let buffer = self.user;
+        self.user = (buffer + 1) % N;
+        Poll::Ready(Ok(buffer))
+    }
+
+    /// Poll for the oldest buffer that's full of received data
+    ///
+    /// Returns `Poll::Pending` if the DMA channel is still receiving into the buffer.
+    pub fn poll_next_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<Slot<'_, E>, Error>> {
+        self.enable?;
+        poll_error(self.channel, cx)?;
+        core::task::ready!(poll_ring(&self.ring.tcds, self.channel, self.user));
+        Poll::Ready(Ok(Slot::new(&mut self.buffers, &mut self.user)))
+    }
+
+    /// Wait for the oldest buffer that's full of received data
+    ///
+    /// See [`poll_next_buffer`](Self::poll_next_buffer) for more information.
+    pub fn next_buffer(&mut self) -> NextBuffer<'_, Self> {
+        NextBuffer { stream: Some(self) }
+    }
+}
+
+impl<S, E, const N: usize> Drop for RingCapture<'_, S, E, N>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        finish_stream(self.channel);
+    }
+}
+
+/// A buffer of a ring stream, lent to your code
+///
+/// The slot dereferences to the buffer. Dropping the slot returns the buffer to the
+/// ring, and the stream's next buffer is the buffer after it.
+pub struct Slot<'s, E> {
+    buffer: &'s mut [E],
+    user: &'s mut usize,
+    len: usize,
+}
+
+impl<'s, E> Slot<'s, E> {
+    /// Lend the user's next buffer
+    fn new<'a, const N: usize>(buffers: &'s mut [&'a mut [E]; N], user: &'s mut usize) -> Self {
+        Slot {
+            buffer: &mut *buffers[*user],
+            user,
+            len: N,
+        }
+    }
+
+    /// Returns the position of the buffer in the ring
+    pub fn index(&self) -> usize {
+        *self.user
+    }
+}
+
+impl<E> core::ops::Deref for Slot<'_, E> {
+    type Target = [E];
+    fn deref(&self) -> &[E] {
+        self.buffer
+    }
+}
+
+impl<E> core::ops::DerefMut for Slot<'_, E> {
+    fn deref_mut(&mut self) -> &mut [E] {
+        self.buffer
+    }
+}
+
+impl<E> Drop for Slot<'_, E> {
+    fn drop(&mut self) {
+        *self.user = (*self.user + 1) % self.len;
+    }
+}
+
+impl<'s, D, E, const N: usize> Future for NextBuffer<'s, RingPlayback<'_, D, E, N>>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    type Output = Result<Slot<'s, E>, Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        stream.enable?;
+        poll_error(stream.channel, cx)?;
+        match poll_ring(&stream.ring.tcds, stream.channel, stream.user) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(()) => Poll::Ready(Ok(Slot::new(&mut stream.buffers, &mut stream.user))),
+        }
+    }
+}
+
+impl<'s, S, E, const N: usize> Future for NextBuffer<'s, RingCapture<'_, S, E, N>>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<Slot<'s, E>, Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("polled after completion");
+        stream.enable?;
+        poll_error(stream.channel, cx)?;
+        match poll_ring(&stream.ring.tcds, stream.channel, stream.user) {
+            Poll::Pending => {
+                self.stream = Some(stream);
+                Poll::Pending
+            }
+            Poll::Ready(()) => Poll::Ready(Ok(Slot::new(&mut stream.buffers, &mut stream.user))),
+        }
+    }
+}
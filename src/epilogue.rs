@@ -0,0 +1,61 @@
+//! Fixed writes performed by hardware after a transfer.
+//!
+//! An [`Epilogue`] describes a single write that the DMA engine performs once a
+//! transfer's major loop completes. Use an epilogue to clear a peripheral's enable
+//! bit, or to write a completion sentinel into memory, without any CPU work.
+//!
+//! The DMA engine loads the epilogue using scatter / gather. Attach an epilogue to a
+//! channel with [`set_epilogue`](crate::channel::Channel::set_epilogue).
+
+use crate::{ral::tcd::Descriptor, Element};
+
+/// Describes a write of `value` into a destination
+///
+/// The DMA engine reads the epilogue from memory. The epilogue must remain valid,
+/// and it must not move, while a channel uses the epilogue.
+#[repr(C, align(32))]
+pub struct Epilogue<E: Element> {
+    descriptor: Descriptor,
+    value: E,
+}
+
+impl<E: Element> Epilogue<E> {
+    /// Create an epilogue that writes `value` into `destination`
+    ///
+    /// `destination` may be a peripheral register, or memory.
+    pub fn new(destination: *const E, value: E) -> Self {
+        Epilogue {
+            descriptor: Descriptor {
+                SADDR: 0,
+                SOFF: 0,
+                DATTR: E::DATA_TRANSFER_ID,
+                SATTR: E::DATA_TRANSFER_ID,
+                NBYTES: core::mem::size_of::<E>() as u32,
+                SLAST: 0,
+                DADDR: destination as u32,
+                DOFF: 0,
+                CITER: 1,
+                DLAST_SGA: 0,
+                CSR: 0,
+                BITER: 1,
+            },
+            value,
+        }
+    }
+
+    /// Returns the value written by the epilogue
+    pub fn value(&self) -> E {
+        self.value
+    }
+
+    /// Prepare the descriptor for loading, and return its address
+    ///
+    /// `csr` are the control and status settings inherited from the transfer.
+    pub(crate) fn prepare(&mut self, csr: u16) -> *const Descriptor {
+        use crate::ral::tcd::CSR;
+        self.descriptor.SADDR = core::ptr::addr_of!(self.value) as u32;
+        // Start the epilogue as soon as it's loaded.
+        self.descriptor.CSR = csr | CSR::START::mask;
+        &self.descriptor
+    }
+}
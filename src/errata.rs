@@ -0,0 +1,34 @@
+//! Workarounds for eDMA and Cortex-M7 interactions on i.MX RT 10xx chips.
+//!
+//! Enable the `errata` feature to apply these workarounds. Without the feature,
+//! the functions in this module compile to nothing.
+//!
+//! # Flag clears that land after the ISR returns
+//!
+//! A Cortex-M7 runs much faster than the peripheral bus, and it buffers stores. The
+//! write that clears an interrupt flag may still be in flight when the ISR returns,
+//! so the interrupt fires again. The DMA driver clears flags through write-only
+//! registers (CINT, CDNE, CERR). The workaround reads the matching status register
+//! back, which can't complete until the write reaches the peripheral, then issues a
+//! data synchronization barrier.
+//!
+//! # Writes to TCD CSR ignored while DONE is set
+//!
+//! The eDMA ignores writes that set the TCD CSR's ESG and MAJORELINK bits while the
+//! CSR's DONE bit is set. See the TCD CSR field descriptions in the reference manual.
+//! The driver always clears DONE before it sets either bit, with or without this
+//! feature. With the feature, it also reads the CSR back so the write completes
+//! before the channel is enabled.
+
+/// Make sure that a write to a DMA register completes
+///
+/// `read` reads a register in the same peripheral. The read can't complete
+/// until the prior write completes, and the barrier waits for the read.
+#[inline(always)]
+pub(crate) fn complete_write<T>(_read: impl FnOnce() -> T) {
+    #[cfg(feature = "errata")]
+    {
+        let _ = _read();
+        cortex_m::asm::dsb();
+    }
+}
@@ -1,23 +1,114 @@
-//! DMA error status
+//! DMA error status, and configuration errors
 
 use core::fmt::{self, Debug, Display};
 
+/// A DMA error
+///
+/// `Error` describes all the ways that a DMA transfer can fail. The error
+/// implements `Display`. Enable the `defmt` feature to format the error with
+/// `defmt`. Enable the `std` feature to use the error as a `std::error::Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The DMA controller reported an error
+    Transfer(ErrorStatus),
+    /// The requested configuration isn't supported
+    Config(ConfigError),
+    /// Software canceled the transfer before it completed
+    Cancelled,
+    /// The transfer didn't complete in time
+    Timeout,
+    /// The DMA channel sent data that was not refilled in time
+    Underrun,
+    /// The DMA channel received into memory that was not read in time
+    Overrun,
+    /// The transfer made no progress in time
+    Stalled,
+    /// The data read back from the destination didn't match the source
+    Mismatch,
+    /// A peripheral-specific error
+    ///
+    /// HALs may use this to pass through peripheral errors that occur
+    /// during a DMA transfer. The meaning of the code depends on the HAL.
+    Peripheral(u32),
+}
+
+impl Error {
+    /// Returns the DMA controller's error status, if the controller
+    /// reported the error
+    pub const fn status(self) -> Option<ErrorStatus> {
+        match self {
+            Error::Transfer(es) => Some(es),
+            _ => None,
+        }
+    }
+}
+
+impl From<ErrorStatus> for Error {
+    fn from(es: ErrorStatus) -> Self {
+        Error::Transfer(es)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(err: ConfigError) -> Self {
+        Error::Config(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transfer(es) => Display::fmt(es, f),
+            Error::Config(err) => Display::fmt(err, f),
+            Error::Cancelled => f.write_str("DMA transfer canceled"),
+            Error::Timeout => f.write_str("DMA transfer timed out"),
+            Error::Underrun => f.write_str("DMA underrun"),
+            Error::Overrun => f.write_str("DMA overrun"),
+            Error::Stalled => f.write_str("DMA transfer stalled"),
+            Error::Mismatch => f.write_str("DMA copy failed verification"),
+            Error::Peripheral(code) => write!(f, "DMA peripheral error {code:#010X}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Error {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self {
+            Error::Transfer(es) => defmt::write!(f, "Transfer({})", es),
+            Error::Config(err) => defmt::write!(f, "Config({})", err),
+            Error::Cancelled => defmt::write!(f, "Cancelled"),
+            Error::Timeout => defmt::write!(f, "Timeout"),
+            Error::Underrun => defmt::write!(f, "Underrun"),
+            Error::Overrun => defmt::write!(f, "Overrun"),
+            Error::Stalled => defmt::write!(f, "Stalled"),
+            Error::Mismatch => defmt::write!(f, "Mismatch"),
+            Error::Peripheral(code) => defmt::write!(f, "Peripheral({=u32:#010X})", code),
+        }
+    }
+}
+
 /// A wrapper around a DMA error status value
 ///
 /// The wrapper contains a copy of the DMA controller's
 /// error status register at the point of an error. The
 /// wrapper implements both `Debug` and `Display`. Format
 /// the error to see a summary of the error bits.
-#[derive(Clone, Copy)]
-pub struct Error {
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorStatus {
     /// The raw error status
     es: u32,
 }
 
-impl Error {
+impl ErrorStatus {
     #[inline(always)]
     pub(crate) const fn new(es: u32) -> Self {
-        Error { es }
+        ErrorStatus { es }
     }
     /// Returns the raw error status value
     #[inline(always)]
@@ -30,7 +121,7 @@ impl Error {
     }
     /// Logical OR of all DMA channel error status bits
     ///
-    /// If you have an `Error` this should always be true.
+    /// If you have an `ErrorStatus` this should always be true.
     #[inline(always)]
     pub const fn is_valid(self) -> bool {
         self.is_bit(31)
@@ -97,13 +188,13 @@ impl Error {
     }
 }
 
-impl Debug for Error {
+impl Debug for ErrorStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "DMA_ES({:#010X})", self.es)
     }
 }
 
-impl Display for Error {
+impl Display for ErrorStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f,
              "DMA_ES: VLD {vld} ECX {ecx} GPE {gpe} CPE {cpe} ERRCHN {errchn} SAE {sae} SOE {soe} DAE {dae} DOE {doe} NCE {nce} SGE {sge} SBE {sbe} DBE {dbe}",
@@ -123,3 +214,81 @@ impl Display for Error {
          )
     }
 }
+
+/// A DMA configuration error
+///
+/// Fallible constructors and setters return a `ConfigError` when the
+/// requested configuration isn't supported. The panicking equivalents
+/// panic with the error's `Display` message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The channel index is greater than or equal to the number of channels
+    InvalidChannel,
+    /// The channel does not support periodic triggering
+    PeriodicUnsupported,
+    /// The minor loop is too large for a minor loop offset
+    MinorLoopTooLarge,
+    /// The minor loop offset cannot be represented by the hardware
+    MinorLoopOffsetOutOfRange,
+    /// There are too many transfer iterations for a minor loop link
+    TooManyIterations,
+    /// The buffer is outside of the DMA-accessible memory map
+    InaccessibleMemory,
+    /// The destination buffer is in read-only memory
+    ReadOnlyDestination,
+    /// The buffer isn't aligned to the data cache line
+    UnalignedCacheBuffer,
+    /// The DMA controller or DMA multiplexer clock is disabled
+    ClockGated,
+    /// All descriptors in the pool are leased
+    PoolExhausted,
+    /// The channel priority is greater than 15
+    InvalidPriority,
+    /// The TCD doesn't match its seal
+    CorruptTcd,
+    /// The buffer is in tightly-coupled memory that the FlexRAM doesn't allocate
+    UnallocatedTcm,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ConfigError::InvalidChannel => "Invalid DMA channel",
+            ConfigError::PeriodicUnsupported => {
+                "Requested DMA periodic triggering on an unsupported channel."
+            }
+            ConfigError::MinorLoopTooLarge => "DMA minor loop is too large for a minor loop offset",
+            ConfigError::MinorLoopOffsetOutOfRange => "DMA minor loop offset is out of range",
+            ConfigError::TooManyIterations => {
+                "Too many DMA transfer iterations for a minor loop link"
+            }
+            ConfigError::InaccessibleMemory => "DMA buffer is not in DMA-accessible memory",
+            ConfigError::ReadOnlyDestination => "DMA destination buffer is in read-only memory",
+            ConfigError::UnalignedCacheBuffer => "DMA buffer is not aligned to the data cache line",
+            ConfigError::ClockGated => "DMA clocks are disabled",
+            ConfigError::PoolExhausted => "DMA descriptor pool is exhausted",
+            ConfigError::InvalidPriority => "DMA channel priority is out of range",
+            ConfigError::CorruptTcd => "DMA TCD changed since it was sealed",
+            ConfigError::UnallocatedTcm => {
+                "DMA buffer is beyond the allocated tightly-coupled memory"
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ErrorStatus {}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ErrorStatus {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "DMA_ES({=u32:#010X})", self.es)
+    }
+}
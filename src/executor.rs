@@ -0,0 +1,125 @@
+//! A minimal executor for DMA futures.
+//!
+//! [`block_on`] runs a future to completion on the calling thread. Between polls,
+//! the CPU sleeps with `WFE`. Waking the future's waker, typically from
+//! [`on_interrupt`](crate::Dma::on_interrupt), issues `SEV` to wake the CPU.
+//!
+//! Use `block_on` when your firmware doesn't use an async runtime. Make sure that
+//! the DMA channel generates an interrupt, and that the interrupt handler calls
+//! `on_interrupt`. Otherwise, the CPU may sleep until an unrelated event wakes it.
+//! Where DMA interrupts aren't available, use [`block_on_polled`], which sweeps
+//! the channels with [`Dma::poll`](crate::Dma::poll) instead of sleeping.
+//!
+//! This module requires the `executor` feature.
+
+use core::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+/// Set when a waker from `block_on` wakes.
+static WOKEN: AtomicBool = AtomicBool::new(false);
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop_waker);
+
+fn clone(_: *const ()) -> RawWaker {
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
+
+fn wake(_: *const ()) {
+    WOKEN.store(true, Ordering::Release);
+    cortex_m::asm::sev();
+}
+
+fn drop_waker(_: *const ()) {}
+
+/// Run `future` to completion, sleeping between polls
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, executor, memcpy};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// // #[cortex_m_rt::interrupt]
+/// fn DMA7() {
+///     // Safety: DMA channel 7 valid and used by a future.
+///     unsafe { DMA.on_interrupt(7) };
+/// }
+///
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+/// // TODO unmask DMA7 interrupt!
+///
+/// let source = [4u32, 5, 6, 7, 8];
+/// let mut destination = [0; 5];
+///
+/// executor::block_on(memcpy::memcpy(&source, &mut destination, &mut channel_7)).unwrap();
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    // Safety: the vtable functions ignore the data pointer, and they're
+    // safe to call from any context.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        WOKEN.store(false, Ordering::Release);
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        while !WOKEN.load(Ordering::Acquire) {
+            cortex_m::asm::wfe();
+        }
+    }
+}
+
+/// Run `future` to completion without DMA interrupts
+///
+/// Between polls of `future`, `block_on_polled` sweeps the channels of `dma` with
+/// [`Dma::poll`](crate::Dma::poll). The CPU never sleeps, so the future makes
+/// progress as soon as its channel completes. Use this in bootloaders, and in
+/// other contexts where the DMA interrupt vectors are locked down.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, executor, memcpy};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let source = [4u32, 5, 6, 7, 8];
+/// let mut destination = [0; 5];
+///
+/// // Safety: channel 7 is used by the memcpy future, and no other
+/// // channel is in use.
+/// let copy = memcpy::memcpy(&source, &mut destination, &mut channel_7);
+/// unsafe { executor::block_on_polled(&DMA, copy) }.unwrap();
+/// ```
+///
+/// # Safety
+///
+/// See [`Dma::poll`](crate::Dma::poll).
+#[cfg(feature = "async")]
+pub unsafe fn block_on_polled<F: Future, const CHANNELS: usize>(
+    dma: &'static crate::Dma<CHANNELS>,
+    future: F,
+) -> F::Output {
+    let mut future = pin!(future);
+    // Safety: see block_on.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        WOKEN.store(false, Ordering::Release);
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        while !WOKEN.load(Ordering::Acquire) {
+            dma.poll();
+        }
+    }
+}
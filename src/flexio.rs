@@ -0,0 +1,365 @@
+//! DMA adapters for FlexIO shifter buffers.
+//!
+//! A FlexIO shifter has one buffer register, and several aliases of that buffer.
+//! Each alias rearranges the bits of the buffer as the DMA channel reads or writes
+//! the shifter. Use a [`Shifter`] to describe one shifter buffer. A `Shifter`
+//! implements [`Source`] and [`Destination`], so it works with
+//! [`read`](crate::peripheral::read), [`write`](crate::peripheral::write), and
+//! the other peripheral transfers.
+//!
+//! Parallel bus interfaces, like camera interfaces, chain multiple shifters.
+//! Each DMA request reads the buffers of all chained shifters. Describe chained shifters
+//! with a [`ShifterGroup`], then
+//!
+//! - use [`capture`] to fill a buffer with many groups of shifter data.
+//! - use a [`ScanCapture`](crate::scan::ScanCapture) with
+//!   [`Consecutive`](crate::scan::ResultRegisters::Consecutive) registers to
+//!   sample the most recent group of shifter data.
+//!
+//! You're responsible for configuring the FlexIO shifters and timers. The adapters
+//! only enable and disable the shifter's DMA request.
+
+use crate::{
+    channel::{self, Channel, Configuration, MinorLoopOffset},
+    interrupt::Transfer,
+    peripheral::{Destination, Source},
+    Error,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Offset of the shifter status DMA enable register
+const SHIFTSDEN: usize = 0x20;
+/// Number of shifters supported by the adapters
+const MAX_SHIFTERS: usize = 32;
+
+/// A shifter buffer register alias
+///
+/// Each alias accesses the same shifter buffer. The aliases differ in how
+/// they arrange the buffer's bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Alias {
+    /// The shifter buffer, `SHIFTBUF`
+    Normal,
+    /// Bits are swapped, `SHIFTBUFBIS`
+    ///
+    /// Use this alias for shifters that shift data MSB first.
+    BitSwapped,
+    /// Bytes are swapped, `SHIFTBUFBYS`
+    ByteSwapped,
+    /// Bits are swapped within each byte, `SHIFTBUFBBS`
+    BitByteSwapped,
+}
+
+impl Alias {
+    /// Offset of the first shifter buffer for this alias
+    const fn offset(self) -> usize {
+        match self {
+            Alias::Normal => 0x200,
+            Alias::BitSwapped => 0x280,
+            Alias::ByteSwapped => 0x300,
+            Alias::BitByteSwapped => 0x380,
+        }
+    }
+}
+
+/// A FlexIO shifter buffer, usable as a DMA source or destination
+///
+/// Enabling the source or destination enables the shifter's DMA request. Disabling
+/// the source or destination disables the shifter's DMA request.
+///
+/// # Example
+///
+/// Send words out of FlexIO2 shifter 0, MSB first.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, flexio, peripheral};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// const FLEXIO2: *const () = 0x401B_0000 as _;
+/// const FLEXIO2_REQUEST_0: u32 = 1;
+///
+/// // Safety: FLEXIO2 is a FlexIO instance, and only this shifter
+/// // uses shifter 0.
+/// let mut shifter = unsafe { flexio::Shifter::new(FLEXIO2, 0, FLEXIO2_REQUEST_0) }
+///     .with_alias(flexio::Alias::BitSwapped);
+///
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let words = [0xDEADBEEFu32, 0xC0FFEE];
+/// peripheral::write(&mut channel_7, &words, &mut shifter).await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Shifter {
+    flexio: *const (),
+    index: usize,
+    alias: Alias,
+    signal: u32,
+}
+
+impl Shifter {
+    /// Describe the shifter `index` of the FlexIO instance at address `flexio`
+    ///
+    /// `signal` is the DMA request signal for the shifter. See Table 4-3 of the
+    /// reference manual. The shifter uses the [`Normal`](Alias::Normal) buffer alias.
+    ///
+    /// # Safety
+    ///
+    /// `flexio` must be the address of a FlexIO instance, and `index` must be a shifter
+    /// supported by the instance. No other software may modify the shifter buffer while
+    /// the DMA channel uses the shifter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 31.
+    pub const unsafe fn new(flexio: *const (), index: usize, signal: u32) -> Self {
+        assert!(index < MAX_SHIFTERS, "Invalid FlexIO shifter index");
+        Shifter {
+            flexio,
+            index,
+            alias: Alias::Normal,
+            signal,
+        }
+    }
+
+    /// Use the shifter buffer `alias` for DMA transfers
+    pub const fn with_alias(mut self, alias: Alias) -> Self {
+        self.alias = alias;
+        self
+    }
+
+    /// Returns the shifter index
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the shifter buffer alias
+    pub const fn alias(&self) -> Alias {
+        self.alias
+    }
+
+    /// Returns the address of this shifter's buffer
+    fn buffer(&self) -> *const u32 {
+        let offset = self.alias.offset() + self.index * core::mem::size_of::<u32>();
+        self.flexio.cast::<u8>().wrapping_add(offset).cast()
+    }
+
+    /// Enable or disable the shifter's DMA request
+    fn set_dma_request(&mut self, enable: bool) {
+        let shiftsden = self.flexio.cast::<u8>().wrapping_add(SHIFTSDEN) as *mut u32;
+        let mask = 1 << self.index;
+        // Safety: new() caller ensures that this is a FlexIO register. Another
+        // shifter in the same instance could be modifying the register, so
+        // prevent preemption.
+        crate::sync::free(|| unsafe {
+            let value = shiftsden.read_volatile();
+            let value = if enable { value | mask } else { value & !mask };
+            shiftsden.write_volatile(value);
+        });
+    }
+}
+
+unsafe impl Source<u32> for Shifter {
+    fn source_signal(&self) -> u32 {
+        self.signal
+    }
+    fn source_address(&self) -> *const u32 {
+        self.buffer()
+    }
+    fn enable_source(&mut self) {
+        self.set_dma_request(true);
+    }
+    fn disable_source(&mut self) {
+        self.set_dma_request(false);
+    }
+}
+
+unsafe impl Destination<u32> for Shifter {
+    fn destination_signal(&self) -> u32 {
+        self.signal
+    }
+    fn destination_address(&self) -> *const u32 {
+        self.buffer()
+    }
+    fn enable_destination(&mut self) {
+        self.set_dma_request(true);
+    }
+    fn disable_destination(&mut self) {
+        self.set_dma_request(false);
+    }
+}
+
+/// A group of chained FlexIO shifters
+///
+/// The group starts at a [`Shifter`], and includes the next `count - 1` shifters.
+/// All shifters use the first shifter's buffer alias. The first shifter's DMA request
+/// signals that all shifters in the group are ready.
+///
+/// As a [`Source`], the group's address is the first shifter's buffer. The buffers of
+/// the remaining shifters are consecutive, so read them as
+/// [`Consecutive`](crate::scan::ResultRegisters::Consecutive) registers.
+#[derive(Debug)]
+pub struct ShifterGroup {
+    first: Shifter,
+    count: usize,
+}
+
+impl ShifterGroup {
+    /// Describe a group of `count` shifters, starting at `first`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero, or if the group has more shifters than
+    /// the FlexIO instance supports.
+    pub fn new(first: Shifter, count: usize) -> Self {
+        assert!(
+            count > 0 && first.index + count <= MAX_SHIFTERS,
+            "Invalid FlexIO shifter group"
+        );
+        ShifterGroup { first, count }
+    }
+
+    /// Returns the number of shifters in the group
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the group's first shifter
+    pub fn first(&self) -> &Shifter {
+        &self.first
+    }
+
+    /// Release the first shifter
+    pub fn release(self) -> Shifter {
+        self.first
+    }
+}
+
+unsafe impl Source<u32> for ShifterGroup {
+    fn source_signal(&self) -> u32 {
+        self.first.source_signal()
+    }
+    fn source_address(&self) -> *const u32 {
+        self.first.source_address()
+    }
+    fn enable_source(&mut self) {
+        self.first.enable_source();
+    }
+    fn disable_source(&mut self) {
+        self.first.disable_source();
+    }
+}
+
+/// A DMA transfer that receives data from a group of shifters
+///
+/// The future resolves when the DMA channel has filled the buffer.
+/// Use [`capture`] to construct this future.
+pub struct Capture<'a> {
+    channel: &'a Channel,
+    group: &'a mut ShifterGroup,
+    transfer: Transfer<'a>,
+    _buffer: core::marker::PhantomData<&'a mut [u32]>,
+}
+
+/// Fill `buffer` with data from a group of shifters
+///
+/// Each DMA request from the group reads one word from every shifter in the
+/// group. The words are stored in shifter order.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Panics
+///
+/// Panics if the buffer is empty, if the buffer length is not a multiple of the
+/// group's shifter count, or if the buffer needs more than 32767 DMA requests.
+///
+/// # Example
+///
+/// Capture 8-bit parallel data with a group of eight shifters.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, flexio};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// const FLEXIO2: *const () = 0x401B_0000 as _;
+/// const FLEXIO2_REQUEST_0: u32 = 1;
+///
+/// // Safety: FLEXIO2 is a FlexIO instance, and we own shifters 0 through 7.
+/// let first = unsafe { flexio::Shifter::new(FLEXIO2, 0, FLEXIO2_REQUEST_0) };
+/// let mut group = flexio::ShifterGroup::new(first, 8);
+///
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// static mut LINE: [u32; 8 * 40] = [0; 8 * 40];
+/// // Safety: we're the only user of LINE.
+/// let line = unsafe { &mut *core::ptr::addr_of_mut!(LINE) };
+/// flexio::capture(&mut channel_7, &mut group, line).await?;
+/// # Ok(()) }
+/// ```
+pub fn capture<'a>(
+    channel: &'a mut Channel,
+    group: &'a mut ShifterGroup,
+    buffer: &'a mut [u32],
+) -> Capture<'a> {
+    assert!(
+        !buffer.is_empty() && buffer.len().is_multiple_of(group.count),
+        "Invalid FlexIO capture buffer"
+    );
+    let iterations = buffer.len() / group.count;
+    assert!(iterations <= 0x7FFF, "FlexIO capture buffer is too large");
+    let nbytes = (group.count * core::mem::size_of::<u32>()) as i32;
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(group.source_signal()));
+    // Safety: new() caller ensures that shifter buffers are valid. Destination buffer
+    // lifetime captured by future. Each minor loop reads all shifter buffers, then
+    // returns to the first buffer. The minor loops and transfer iterations ensure
+    // that we do not exceed the end of the destination.
+    unsafe {
+        channel.set_source_address(group.source_address());
+        channel.set_source_offset(core::mem::size_of::<u32>() as i16);
+        channel.set_source_attributes::<u32>(0);
+        channel.set_source_last_address_adjustment(-nbytes);
+        channel::set_destination_linear_buffer(channel, buffer);
+        channel.set_minor_loop_bytes_with_offset(nbytes as u32, MinorLoopOffset::Source(-nbytes));
+        channel.set_transfer_iterations(iterations as u16);
+    }
+    group.enable_source();
+
+    Capture {
+        channel,
+        group,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        _buffer: core::marker::PhantomData,
+    }
+}
+
+impl Future for Capture<'_> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+    }
+}
+
+impl Drop for Capture<'_> {
+    fn drop(&mut self) {
+        self.group.disable_source();
+        while self.channel.is_hardware_signaling() {}
+        // Drop `transfer` to finish cancellation...
+    }
+}
@@ -0,0 +1,145 @@
+//! Hand a configured channel from one core to the other.
+//!
+//! In an asymmetric multiprocessing design, like an i.MX RT 1170 where the CM4
+//! owns low-power capture and the CM7 owns processing, both cores run their own
+//! firmware against the same DMA controller. A channel can move between the cores
+//! while it holds its transfer. The exporting core [quiesces](crate::channel::Channel::quiesce)
+//! the channel, which stops it between minor loops, and records its state in a
+//! [`Handoff`]. The importing core [imports](crate::Dma::import) the handoff,
+//! which restores the state, and resumes the channel where it stopped.
+//!
+//! `Handoff` is plain data. Pass it between the cores in shared memory, or in a
+//! messaging unit's registers. If the shared memory is cacheable, clean the handoff
+//! from the exporting core's cache, and invalidate it in the importing core's
+//! cache.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, Dma};
+//! # static DMA: Dma<32> = unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # fn send_to_cm7<T>(_: T) {}
+//! # fn receive_from_cm4<T>() -> T { panic!() }
+//!
+//! // On the CM4: stop servicing the channel, then export it.
+//! let channel: Channel = // DMA channel 7, running a capture
+//!     # unsafe { DMA.channel(7) };
+//! // TODO mask DMA7 interrupt in this core's NVIC!
+//! send_to_cm7(channel.quiesce());
+//!
+//! // On the CM7: import the channel, then service its interrupts.
+//! let handoff = receive_from_cm4();
+//! // Safety: the capture buffer is valid on this core, and the CM4
+//! // no longer uses the channel.
+//! let channel = unsafe { DMA.import(&handoff) }.unwrap();
+//! // TODO unmask channel.interrupt() in this core's NVIC!
+//! ```
+//!
+//! The eDMA ignores which core runs the firmware, so the cores share the channel's
+//! registers. The handoff moves ownership: once a core quiesces a channel, it
+//! must not touch the channel again, unless it imports the channel back.
+
+use crate::{channel::Channel, profile::Profile, ConfigError, Dma, Instance};
+
+/// A quiesced channel's state, ready for another core
+///
+/// Use [`Channel::quiesce`] to create a handoff, and [`Dma::import`] to resume
+/// the channel. See the [module documentation](crate::handoff) for more
+/// information.
+#[derive(Debug, Clone, Copy)]
+pub struct Handoff {
+    channel: usize,
+    instance: Instance,
+    profile: Profile,
+    enabled: bool,
+    complete: bool,
+}
+
+impl Handoff {
+    /// Returns the channel number
+    pub fn channel(&self) -> usize {
+        self.channel
+    }
+
+    /// Returns the description of the channel's controller
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the channel's configuration
+    ///
+    /// The descriptor records the transfer's progress when the channel quiesced.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns `true` if the channel was enabled for hardware requests
+    ///
+    /// Importing the handoff enables the channel again.
+    pub fn was_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns `true` if the channel had completed its transfer
+    ///
+    /// Importing the handoff clears the channel's completion flag. Check this
+    /// before you wait for an imported transfer.
+    pub fn was_complete(&self) -> bool {
+        self.complete
+    }
+}
+
+impl Channel {
+    /// Stop the channel between minor loops, and export its state for another core
+    ///
+    /// `quiesce` disables the channel's hardware requests, and waits for any active
+    /// minor loop to finish. The channel's descriptor, including the transfer's
+    /// progress, stays in the channel's registers, and in the handoff. With the
+    /// `async` feature, `quiesce` also forgets the channel's waker.
+    ///
+    /// Before you quiesce the channel, mask its interrupt on this core, so that
+    /// this core doesn't service the importing core's transfer.
+    pub fn quiesce(self) -> Handoff {
+        let enabled = self.is_enabled();
+        self.disable();
+        while self.is_active() {}
+        #[cfg(feature = "async")]
+        crate::interrupt::clear_waker(&self);
+        Handoff {
+            channel: self.channel(),
+            instance: self.instance(),
+            profile: self.capture_profile(),
+            enabled,
+            complete: self.is_complete(),
+        }
+    }
+}
+
+impl<const CHANNELS: usize> Dma<CHANNELS> {
+    /// Resume a channel that another core quiesced
+    ///
+    /// `import` creates the channel, and applies the handoff's profile, which clears
+    /// the channel's completion and error flags. If the channel was enabled when it
+    /// quiesced, `import` enables it again, and the transfer continues where it
+    /// stopped. Unmask the returned channel's
+    /// [interrupt](Channel::interrupt) on this core to service the transfer.
+    ///
+    /// Returns [`ConfigError::InvalidChannel`] if the handoff describes a channel of
+    /// a different controller, or a channel that this driver doesn't have. Returns
+    /// the errors of [`try_apply_profile`](Channel::try_apply_profile).
+    ///
+    /// # Safety
+    ///
+    /// The handoff's descriptor must describe a valid transfer on this core. The
+    /// exporting core must not use the channel after it quiesced the channel. Don't
+    /// create another handle to the channel on this core.
+    pub unsafe fn import(&'static self, handoff: &Handoff) -> Result<Channel, ConfigError> {
+        if handoff.instance.number() != self.instance.number() {
+            return Err(ConfigError::InvalidChannel);
+        }
+        let mut channel = self.try_channel(handoff.channel)?;
+        channel.try_apply_profile(&handoff.profile)?;
+        if handoff.enabled {
+            channel.try_enable()?;
+        }
+        Ok(channel)
+    }
+}
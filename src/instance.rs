@@ -0,0 +1,109 @@
+//! Describe one of many DMA controllers on a chip
+
+/// Describes an eDMA controller, and its DMA multiplexer, on a chip
+///
+/// Some chips have more than one eDMA and DMAMUX pair. Give each [`Dma`](crate::Dma)
+/// its own `Instance` with [`with_instance`](crate::Dma::with_instance), and the same
+/// code can unmask and handle interrupts for any channel of any controller.
+///
+/// Channels share interrupt lines. Channel `n` signals interrupt number
+/// `interrupt + (n % lines)`. On an i.MX RT 1060, channels 0 and 16 share the first
+/// of 16 lines:
+///
+/// ```
+/// use imxrt_dma::Instance;
+///
+/// let instance = Instance::new(0, 0, 16);
+/// assert_eq!(instance.interrupt(7), 7);
+/// assert_eq!(instance.interrupt(23), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Instance {
+    number: u8,
+    interrupt: u16,
+    lines: u8,
+}
+
+impl Instance {
+    /// Describe controller `number`, with `lines` interrupt lines starting at
+    /// interrupt number `interrupt`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lines` is zero.
+    pub const fn new(number: u8, interrupt: u16, lines: u8) -> Self {
+        assert!(lines > 0, "DMA controller needs an interrupt line");
+        Instance {
+            number,
+            interrupt,
+            lines,
+        }
+    }
+
+    /// Describe a controller with one interrupt line for each channel
+    ///
+    /// This is the instance of a driver created without
+    /// [`with_instance`](crate::Dma::with_instance).
+    pub(crate) const fn single(channels: usize) -> Self {
+        Self::new(0, 0, channels as u8)
+    }
+
+    /// Returns the controller number, starting at zero
+    pub const fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns the interrupt number signaled by `channel`
+    pub const fn interrupt(&self, channel: usize) -> u16 {
+        self.interrupt + (channel % self.lines as usize) as u16
+    }
+}
+
+impl<const CHANNELS: usize> crate::Dma<CHANNELS> {
+    /// Describe this driver's controller
+    ///
+    /// Use this to tell apart the controllers on a chip with more than one eDMA and
+    /// DMAMUX pair. Without an instance, the driver is controller 0, and each
+    /// channel has its own interrupt number, equal to the channel number.
+    ///
+    /// ```
+    /// use imxrt_dma::{Dma, Instance};
+    ///
+    /// // Safety: addresses and channel count are valid for an i.MX RT 1060.
+    /// static DMA: Dma<32> = unsafe { Dma::from_addresses(0x400E_8000, 0x400E_C000) }
+    ///     .with_instance(Instance::new(0, 0, 16));
+    ///
+    /// let channel = DMA.claim::<23>().unwrap();
+    /// assert_eq!(channel.instance().number(), 0);
+    /// assert_eq!(channel.interrupt(), 7);
+    /// ```
+    pub const fn with_instance(mut self, instance: Instance) -> Self {
+        self.instance = instance;
+        self
+    }
+
+    /// Returns the description of this driver's controller
+    pub const fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Handle the interrupt number `interrupt`
+    ///
+    /// Calls [`on_interrupt`](Self::on_interrupt) for every channel that signals
+    /// `interrupt`. Use this to handle each of the controller's interrupt lines with
+    /// the same code.
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt). Each channel that signals `interrupt`
+    /// must follow those rules.
+    #[cfg(feature = "async")]
+    pub unsafe fn on_interrupt_number(&'static self, interrupt: u16) {
+        for channel in 0..CHANNELS {
+            if self.instance.interrupt(channel) == interrupt {
+                self.on_interrupt(channel);
+            }
+        }
+    }
+}
@@ -1,23 +1,24 @@
 //! DMA interrupt support
 
-use crate::{channel::Channel, Error};
+use crate::{
+    channel::Channel, ral::tcd::Descriptor, retry::Resume, sync::Mutex, ConfigError, Error,
+    ErrorStatus,
+};
 use core::{
-    cell::RefCell,
+    cell::Cell,
     future::Future,
     marker::PhantomPinned,
     pin::Pin,
-    sync::atomic,
     task::{Context, Poll, Waker},
 };
 
-use cortex_m::interrupt::{self, Mutex};
-
 impl<const CHANNELS: usize> super::Dma<CHANNELS> {
     /// Handle a DMA interrupt
     ///
     /// Checks the interrupt status for the channel identified by `channel`.
-    /// If the channel completed its transfer, `on_interrupt` wakes the channel's
-    /// waker.
+    /// If the channel completed its transfer, or if the channel generated an
+    /// interrupt, `on_interrupt` wakes the channel's waker, and calls the channel's
+    /// [notifier](crate::notify).
     ///
     /// Consider calling `on_interrupt` in a DMA channel's interrupt handler:
     ///
@@ -46,28 +47,162 @@ impl<const CHANNELS: usize> super::Dma<CHANNELS> {
     /// # Panics
     ///
     /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    /// See [`try_on_interrupt`](Self::try_on_interrupt) for a non-panicking version.
     #[inline(always)]
     pub unsafe fn on_interrupt(&'static self, channel: usize) {
-        let channel = self.channel(channel);
-        if channel.is_interrupt() {
+        self.try_on_interrupt(channel)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Handle a DMA interrupt, or return an error if the channel is invalid
+    ///
+    /// `try_on_interrupt` never panics. Use it in hard real-time interrupt handlers
+    /// that must not reach a panic handler. See [`on_interrupt`](Self::on_interrupt)
+    /// for more information.
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt).
+    #[inline(always)]
+    pub unsafe fn try_on_interrupt(&'static self, channel: usize) -> Result<(), ConfigError> {
+        let channel = self.try_channel(channel)?;
+        let is_interrupt = channel.is_interrupt();
+        if is_interrupt {
             channel.clear_interrupt();
         }
 
-        if channel.is_complete() | channel.is_error() {
-            interrupt::free(|cs| {
-                let waker = self.wakers[channel.channel()].borrow(cs);
-                let mut waker = waker.borrow_mut();
-                if let Some(waker) = waker.take() {
-                    waker.wake();
+        // Half-complete interrupts don't set any other status.
+        if is_interrupt | channel.is_complete() | channel.is_error() {
+            if let Some(waker) = channel.waker.lock(Cell::take) {
+                waker.wake();
+            }
+            self.notify(channel.channel());
+        }
+        Ok(())
+    }
+
+    /// Handle a DMA error interrupt
+    ///
+    /// Wakes the waker, and calls the [notifier](crate::notify), of each channel
+    /// that reports an error, and that
+    /// [interrupts on error](crate::channel::Channel::set_interrupt_on_error). The
+    /// woken transfer decodes and reports its error. Returns a bitmask of those
+    /// channels; bit `n` is set for channel `n`.
+    ///
+    /// `on_error` masks each channel's error interrupt, so the interrupt doesn't
+    /// fire again before the transfer runs. Clearing the channel's error unmasks
+    /// the interrupt.
+    ///
+    /// ```
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// // #[cortex_m_rt::interrupt]
+    /// fn DMA_ERROR() {
+    ///     // Safety: the channels that interrupt on error
+    ///     // are used by DMA transfer futures.
+    ///     unsafe { DMA.on_error() };
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt). Each channel that interrupts on
+    /// error must follow those rules.
+    pub unsafe fn on_error(&'static self) -> u32 {
+        let errors = self.controller.ERR.read() & self.controller.EEI.read();
+        for index in (0..CHANNELS).filter(|index| errors & (1 << index) != 0) {
+            self.controller.CEEI.write(index as u8);
+            if let Some(waker) = self.wakers[index].lock(Cell::take) {
+                waker.wake();
+            }
+            self.notify(index);
+        }
+        crate::errata::complete_write(|| self.controller.EEI.read());
+        errors
+    }
+}
+
+impl<const CHANNELS: usize> super::Dma<CHANNELS> {
+    /// Detect completions without DMA interrupts
+    ///
+    /// Some bootloaders and safety contexts can't install DMA interrupt handlers.
+    /// In those contexts, call `poll` from your main loop, and don't unmask any
+    /// DMA interrupts. `poll` sweeps every channel. It wakes, and
+    /// [notifies](crate::notify), each channel that generated an interrupt, and
+    /// each channel that has a waiting transfer and that completed, or reported an
+    /// error. Returns a bitmask of those channels; bit `n` is set for channel `n`.
+    ///
+    /// Once woken, transfer futures detect their completion from the channel's
+    /// status, so they don't need an interrupt. To run a future to completion this
+    /// way, see `executor::block_on_polled`.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// // No DMA interrupts. Sweep the channels in the main loop.
+    /// loop {
+    ///     // Safety: every channel is exclusively used by
+    ///     // DMA transfer futures.
+    ///     unsafe { DMA.poll() };
+    ///     // Poll tasks...
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`on_interrupt`](Self::on_interrupt). Each channel must follow those
+    /// rules.
+    pub unsafe fn poll(&'static self) -> u32 {
+        let mut woken = 0;
+        for index in 0..CHANNELS {
+            let channel = self.channel(index);
+            let is_interrupt = channel.is_interrupt();
+            if is_interrupt {
+                channel.clear_interrupt();
+            }
+            let waker = self.wakers[index].lock(|shared| {
+                let waker = shared.take();
+                let finished = waker.is_some() && (channel.is_complete() || channel.is_error());
+                if is_interrupt || finished {
+                    waker
+                } else {
+                    shared.set(waker);
+                    None
                 }
             });
+            if is_interrupt || waker.is_some() {
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+                self.notify(index);
+                woken |= 1 << index;
+            }
         }
+        woken
     }
 }
 
-pub(crate) type SharedWaker = Mutex<RefCell<Option<Waker>>>;
+/// A channel's waker
+///
+/// A `Cell`, not a `RefCell`, so that interrupt handlers can't panic on a
+/// borrow.
+pub(crate) type SharedWaker = Mutex<Cell<Option<Waker>>>;
 #[allow(clippy::declare_interior_mutable_const)] // Very convenient, and usage for static init deemed OK in clippy docs
-pub(crate) const NO_WAKER: SharedWaker = Mutex::new(RefCell::new(None));
+pub(crate) const NO_WAKER: SharedWaker = Mutex::new(Cell::new(None));
+
+/// Register `waker` as the channel's waker
+pub(crate) fn register_waker(channel: &Channel, waker: &Waker) {
+    channel.waker.lock(|shared| shared.set(Some(waker.clone())));
+}
+
+/// Remove the channel's waker
+pub(crate) fn clear_waker(channel: &Channel) {
+    channel.waker.lock(|shared| shared.set(None));
+}
 
 /// The core DMA transfer future
 ///
@@ -112,12 +247,23 @@ pub(crate) const NO_WAKER: SharedWaker = Mutex::new(RefCell::new(None));
 /// ```
 pub struct Transfer<'a> {
     channel: &'a Channel,
+    /// The transfer recorded before the first enable, used to retry from the beginning
+    snapshot: Option<Descriptor>,
+    /// The number of retries so far
+    retries: u32,
+    /// When a retry may re-arm the transfer, if the transfer is waiting to retry
+    rearm: Option<u64>,
+    /// The transfer moves no data, and never touches the channel
+    empty: bool,
     _pinned: PhantomPinned,
 }
 
 impl<'a> Transfer<'a> {
     /// Create a new `Transfer` that performs the DMA transfer described by `channel`
     ///
+    /// If the channel has a [retry policy](crate::channel::Channel::set_retry_policy),
+    /// the transfer restarts after bus errors.
+    ///
     /// # Safety
     ///
     /// Assumes that the transfer is correctly defined in the DMA channel memory.
@@ -125,21 +271,143 @@ impl<'a> Transfer<'a> {
     pub unsafe fn new(channel: &'a Channel) -> Self {
         Transfer {
             channel,
+            snapshot: None,
+            retries: 0,
+            rearm: None,
+            empty: false,
             _pinned: PhantomPinned,
         }
     }
+
+    /// Create a `Transfer` that moves no data
+    ///
+    /// The transfer completes on its first poll. It never touches the channel, or
+    /// the channel's waker.
+    pub(crate) fn empty(channel: &'a Channel) -> Self {
+        Transfer {
+            channel,
+            snapshot: None,
+            retries: 0,
+            rearm: None,
+            empty: true,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Returns `true` if the transfer moves no data
+    pub(crate) fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    /// Enable the channel, starting the transfer
+    ///
+    /// `poll()` calls this if the channel isn't enabled. Call `begin()` to
+    /// start the transfer before the first `poll()`. If the channel won't enable,
+    /// the next `poll()` tries again, and reports the error.
+    pub(crate) fn begin(&mut self) -> Result<(), ConfigError> {
+        if self.empty {
+            return Ok(());
+        }
+        if self.snapshot.is_none() && self.channel.retry_policy().is_some() {
+            self.snapshot = Some(self.channel.snapshot());
+        }
+        // Safety: caller prepared the transfer, per the constructor.
+        unsafe { self.channel.try_enable() }
+    }
+
+    /// Stop the channel to retry the transfer, if the retry policy allows it
+    ///
+    /// Returns `false` if the future should report the error. Otherwise,
+    /// [`rearm`](Self::rearm) restarts the transfer on a later poll.
+    fn retry(&mut self, status: ErrorStatus) -> bool {
+        let policy = match self.channel.retry_policy() {
+            Some(policy) if policy.should_retry(self.retries, status) => policy,
+            _ => return false,
+        };
+        self.retries += 1;
+        self.channel.disable();
+        self.rearm = Some(policy.deadline(self.retries, status));
+        true
+    }
+
+    /// Restart a transfer that's waiting to retry
+    ///
+    /// Returns `Poll::Pending` until the channel finishes its minor loop, and the
+    /// backoff elapses.
+    fn rearm(&mut self, deadline: u64) -> Poll<Result<(), ConfigError>> {
+        let policy = self.channel.retry_policy();
+        if self.channel.is_active() || policy.is_some_and(|policy| !policy.is_due(deadline)) {
+            return Poll::Pending;
+        }
+        self.rearm = None;
+        let resume = policy.map_or(Resume::Beginning, |policy| policy.resume_from());
+        if let (Resume::Beginning, Some(snapshot)) = (resume, &self.snapshot) {
+            // Safety: the snapshot describes the transfer that the caller prepared.
+            unsafe { self.channel.restore(snapshot) };
+        }
+        // A software-started transfer needs another request once it's enabled.
+        let start = !self.channel.is_multiplexed();
+        // Safety: caller prepared the transfer, per the constructor.
+        unsafe { self.channel.try_enable() }?;
+        if start {
+            self.channel.start();
+        }
+        Poll::Ready(Ok(()))
+    }
 }
 
 impl Future for Transfer<'_> {
     type Output = Result<(), Error>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        interrupt::free(|cs| {
-            let waker = self.channel.waker.borrow(cs);
-            let mut waker = waker.borrow_mut();
-            *waker = Some(cx.waker().clone());
-        });
+        // Safety: the channel reference isn't moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        if !this.empty {
+            register_waker(this.channel, cx.waker());
+        }
+        let poll = this.poll_complete();
+        if poll.is_pending() && this.rearm.is_some() {
+            // No interrupt signals the end of the backoff.
+            cx.waker().wake_by_ref();
+        }
+        poll
+    }
+}
 
+impl Transfer<'_> {
+    /// Poll the transfer without a `Context`
+    ///
+    /// Use `poll_complete` to drive the transfer from a superloop, or from
+    /// your own scheduler. The first call starts the transfer. Call again until
+    /// it returns `Poll::Ready`.
+    ///
+    /// `poll_complete` doesn't register a waker. If the transfer is used as a
+    /// future, the waker from the future's last poll remains registered.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::Channel, Transfer};
+    ///
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// let my_channel: Channel = // Acquire your channel...
+    ///     # unsafe { DMA.channel(0) };
+    /// // Properly prepare your transfer...
+    /// // Safety: transfer properly prepared
+    /// let mut transfer = unsafe { Transfer::new(&my_channel) };
+    /// while transfer.poll_complete().is_pending() {
+    ///     // Do other work...
+    /// }
+    /// ```
+    pub fn poll_complete(&mut self) -> Poll<Result<(), Error>> {
+        if self.empty {
+            return Poll::Ready(Ok(()));
+        }
         loop {
+            if let Some(deadline) = self.rearm {
+                match self.rearm(deadline) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                    Poll::Ready(Ok(())) => continue,
+                }
+            }
             // This driver is only expecting to catch synchronous errors
             // (those that manifest once we enable the transfer). If there
             // is a misconfiguration that only the hardware detects, we expect
@@ -147,29 +415,133 @@ impl Future for Transfer<'_> {
             if self.channel.is_error() {
                 let es = self.channel.error_status();
                 self.channel.clear_error();
-                return Poll::Ready(Err(es));
+                if !self.retry(es) {
+                    return Poll::Ready(Err(es.into()));
+                }
             } else if self.channel.is_complete() {
                 self.channel.clear_complete();
                 return Poll::Ready(Ok(()));
             } else if self.channel.is_enabled() {
                 return Poll::Pending;
-            } else {
-                atomic::fence(atomic::Ordering::SeqCst);
-                unsafe { self.channel.enable() };
+            } else if let Err(err) = self.begin() {
+                return Poll::Ready(Err(err.into()));
             }
         }
     }
 }
 
+impl<'a> Transfer<'a> {
+    /// Detach the running transfer from this future
+    ///
+    /// The hardware transfer keeps running; `detach` starts it if it hasn't
+    /// started. A transfer that's waiting to retry stays stopped until it's
+    /// attached and polled again. The returned [`TransferTicket`] remembers the transfer, and you can
+    /// store it in a static, or move it to another task. [`attach`](TransferTicket::attach)
+    /// the ticket to the channel to await the transfer again.
+    ///
+    /// `detach` forgets the waker of the future's task. The next poll after
+    /// attaching registers a new waker.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::Channel, Transfer, TransferTicket};
+    ///
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # async fn f() -> imxrt_dma::Result<()> {
+    /// let my_channel: Channel = // Acquire your channel...
+    ///     # unsafe { DMA.channel(0) };
+    /// // Properly prepare your transfer...
+    /// // Safety: transfer properly prepared, and the memory it uses
+    /// // outlives the ticket.
+    /// let ticket: TransferTicket = unsafe { Transfer::new(&my_channel).detach() };
+    ///
+    /// // Later, maybe in another task...
+    /// ticket.attach(&my_channel).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The memory used by the transfer must remain valid until the transfer
+    /// completes, or until the attached transfer is dropped. Dropping the ticket
+    /// doesn't cancel the transfer; use the channel to cancel it.
+    pub unsafe fn detach(self) -> TransferTicket {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        let channel = this.channel;
+        if !this.empty
+            && this.rearm.is_none()
+            && !channel.is_enabled()
+            && !channel.is_complete()
+            && !channel.is_error()
+        {
+            // If the channel won't enable, the attached transfer reports why.
+            let _ = this.begin();
+        }
+        if !this.empty {
+            clear_waker(channel);
+        }
+        TransferTicket {
+            index: channel.channel(),
+            instance: channel.instance(),
+            snapshot: this.snapshot.take(),
+            retries: this.retries,
+            rearm: this.rearm,
+            empty: this.empty,
+        }
+    }
+}
+
+/// A transfer detached from its future
+///
+/// Use [`Transfer::detach`] to create a ticket. The ticket doesn't borrow the
+/// channel, so it can outlive the task that started the transfer. Attach it to
+/// the same channel to await or poll the transfer.
+#[derive(Debug)]
+pub struct TransferTicket {
+    index: usize,
+    instance: crate::Instance,
+    snapshot: Option<Descriptor>,
+    retries: u32,
+    rearm: Option<u64>,
+    empty: bool,
+}
+
+impl TransferTicket {
+    /// Returns the number of the channel that runs the transfer
+    pub fn channel(&self) -> usize {
+        self.index
+    }
+
+    /// Attach the transfer to `channel`, and resume the future
+    ///
+    /// The future continues where the detached future stopped, including its
+    /// retries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` isn't the channel that runs the transfer.
+    pub fn attach(self, channel: &Channel) -> Transfer<'_> {
+        assert!(
+            channel.channel() == self.index && channel.instance() == self.instance,
+            "Transfer ticket belongs to another DMA channel"
+        );
+        Transfer {
+            channel,
+            snapshot: self.snapshot,
+            retries: self.retries,
+            rearm: self.rearm,
+            empty: self.empty,
+            _pinned: PhantomPinned,
+        }
+    }
+}
+
 impl Drop for Transfer<'_> {
     fn drop(&mut self) {
-        self.channel.disable();
-        self.channel.clear_complete();
-        self.channel.clear_error();
-        interrupt::free(|cs| {
-            let waker = self.channel.waker.borrow(cs);
-            let mut waker = waker.borrow_mut();
-            *waker = None;
-        });
+        if !self.empty {
+            self.channel.disable();
+            self.channel.clear_complete();
+            self.channel.clear_error();
+            clear_waker(self.channel);
+        }
     }
 }
@@ -22,6 +22,30 @@
 //! `DMA` and `DMAMUX` constants for the addresses. You're always responsible
 //! for configuring the number of DMA channels.
 //!
+//! `imxrt-dma` supports the eDMA controller that pairs with a DMAMUX, like the
+//! controllers on i.MX RT 10xx and 1170 chips. It doesn't support the eDMA3 and
+//! eDMA4 controllers on i.MX RT 118x chips. Those controllers assign each channel a
+//! secure or non-secure context through TRDC, and they use a different register
+//! layout. A transfer that crosses security worlds fails with a bus error; consult
+//! your reference manual to configure TRDC before you use DMA in dual-world
+//! firmware.
+//!
+//! eDMA3 and eDMA4 replace the DMAMUX with a request source in each channel's own
+//! register page, and they move the channel's control and status out of the
+//! controller's shared registers. Supporting them needs a second register backend
+//! for [`Channel`](crate::channel::Channel) and `Dma`. The [`peripheral`] traits
+//! describe a peripheral by its request signal and data register, so they don't
+//! depend on the controller; a signal would select the channel's request source
+//! instead of a DMAMUX slot.
+//!
+//! `imxrt-dma` doesn't depend on a register access layer. It describes the DMA
+//! registers itself, and only needs the register block addresses. Pointers from any
+//! register access crate work, as do addresses straight from your reference manual;
+//! see [`Dma::from_addresses`].
+//!
+//! The DMA controller and multiplexer clocks must be enabled before you use any
+//! channels. See the [`clock`] module to check or enable the clock gates.
+//!
 //! With those three parameters, assign a `Dma` to a static. Then, use that
 //! object to create DMA [`Channel`](crate::channel::Channel)s.
 //!
@@ -37,20 +61,88 @@
 //! let mut channel = unsafe { DMA.channel(7) };
 //! ```
 //!
+//! Alternatively, use [`split`](crate::Dma::split) to safely take all channels at
+//! once.
+//!
 //! Once you have a channel, you can use the higher-level DMA APIs, like
 //!
 //! - [`memcpy`](crate::memcpy::memcpy) for memory copies.
 //! - [`write`](crate::peripheral::write) to transmit data from memory to
-//! a peripheral.
+//!   a peripheral.
 //! - [`read`](crate::peripheral::read) to receive data from a peripheral.
 //! - [`full_duplex`](crate::peripheral::full_duplex) to read / write with a
-//! peripheral using a single buffer.
+//!   peripheral using a single buffer.
+//! - [`half_duplex`](crate::peripheral::half_duplex) to write to a peripheral,
+//!   turn it around, then read from it, like an RS-485 transaction.
+//! - [`bridge`] to move data between peripherals with different element sizes.
+//! - [`double_buffer`] to continuously stream data to or
+//!   from a peripheral, like an audio interface.
+//! - [`scan`] to capture a sequence of results for every hardware trigger, like
+//!   an ADC scan.
+//! - [`waveform`] to continuously stream a table of values to a peripheral, like
+//!   PWM duty cycles.
+//! - [`paced`] to write into a register, or copy between buffers, at a
+//!   timer-paced rate, like GPIO bit-banging.
+//! - [`reshape`] to byte-swap or re-stride elements with a pair of linked
+//!   channels.
+//!
+//! The [`flexio`] module adapts FlexIO shifter buffers for DMA transfers. An
+//! [`epilogue`] performs a fixed write after any transfer completes. To chain
+//! transfers with scatter / gather, lease descriptors from a [`pool`], and use
+//! [`segments`] to be notified as segments of the chain complete. With the
+//! `alloc` feature, allocate descriptor chains on the heap. The [`owned`] module
+//! runs transfers that own their buffers, like static buffers declared with
+//! [`dma_buffer!`]. To receive data for a flash programmer, like an over-the-air
+//! update, [`staging`] buffers check that the DMA engine never targets flash.
 //!
 //! Peripheral transfers depends on a peripheral's DMA support. These are signaled
-//! through various [`peripheral`](crate::peripheral) traits.
+//! through various [`peripheral`](crate::peripheral) traits. Enable a chip feature
+//! to describe DMA request signals with the [`signal`] enums.
 //!
 //! For a lower-level API, use the [`channel`](crate::channel) objects and helper
-//! functions.
+//! functions, or describe a transfer with a [`tcd::Tcd`]. To assign channels to
+//! drivers at compile time, [`claim`] them. To share one channel among multiple
+//! tasks, use a [`shared`] channel. To time-slice one channel across multiple
+//! streams, use a [`multiplex`] stream multiplexer. To retry transfers after bus
+//! errors, give the channel a [`retry`] policy. To send log messages in the
+//! background, use a [`logger`]. To detect transfers that stall, supervise them with
+//! a [`watchdog`]. To place transfers on a global timebase, [`timestamp`] them. To
+//! check transfers against their deadlines before you queue them, use [`admission`]
+//! control. To find the fastest copy between two memories, calibrate a [`tuning`].
+//! To batch the completions of many small transfers, [`coalesce`] their interrupts.
+//! To signal RTOS threads, or other executors, from channel interrupts, route them
+//! to a [`notify`] handler. To switch a channel between operating modes, capture and
+//! apply a [`profile`]. To move a channel between the cores of a multi-core chip,
+//! [`handoff`] its state. To record a channel's state in a crash dump, [`describe`]
+//! it. To check buffers against the chip's memory map, enable a chip feature and see
+//! the `memory` module. For buffers in cached memory, like external SDRAM, see
+//! [`cache`].
+//!
+//! ### Features
+//!
+//! Three default features select the driver's capabilities. Disable the default
+//! features to drop the code you don't use.
+//!
+//! - `async` provides the transfer futures, and the channel wakers behind
+//!   [`on_interrupt()`](crate::Dma::on_interrupt). Most modules require `async`.
+//! - `scatter-gather` provides descriptor pools and chains, segment events, and
+//!   epilogues.
+//! - `ring-buffers` provides the logger and, with the other two features, double
+//!   buffers.
+//!
+//! Without any of the three, the driver still allocates channels, and you can
+//! configure and poll transfers with the [`channel`] API or a
+//! [`tcd::Tcd`].
+//!
+//! ### Panic-free hot paths
+//!
+//! Panicking methods, like [`Channel::enable`](crate::channel::Channel::enable),
+//! have fallible `try_` counterparts. Configure channels with the `try_` setters,
+//! enable them with [`try_enable`](crate::channel::Channel::try_enable), and
+//! dispatch interrupts with `try_on_interrupt`, `on_error`, or `poll`; those paths
+//! can't panic, so they're safe in hard real-time interrupt handlers. The
+//! `ci/no-panic` program checks this: it fails to link if a panic is reachable from
+//! those paths.
 //!
 //! ### License
 //!
@@ -67,18 +159,96 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "validate", not(feature = "imxrt1060")))]
+compile_error!("The `validate` feature needs a chip feature, like `imxrt1060`.");
+
+#[cfg(feature = "async")]
+pub mod admission;
+pub mod arbitration;
+#[cfg(feature = "async")]
+pub mod bridge;
+pub mod cache;
 pub mod channel;
+pub mod claim;
+pub mod clock;
+#[cfg(feature = "async")]
+pub mod coalesce;
+pub mod describe;
+#[cfg(all(
+    feature = "async",
+    feature = "scatter-gather",
+    feature = "ring-buffers"
+))]
+pub mod double_buffer;
 mod element;
+#[cfg(feature = "scatter-gather")]
+pub mod epilogue;
+mod errata;
 mod error;
+#[cfg(feature = "executor")]
+pub mod executor;
+#[cfg(feature = "async")]
+pub mod flexio;
+pub mod handoff;
+mod instance;
+#[cfg(feature = "async")]
 mod interrupt;
+#[cfg(feature = "ring-buffers")]
+pub mod logger;
+#[cfg(feature = "async")]
 pub mod memcpy;
+#[cfg(feature = "imxrt1060")]
+pub mod memory;
+#[cfg(feature = "async")]
+pub mod multiplex;
+#[cfg(feature = "async")]
+pub mod notify;
+#[cfg(feature = "async")]
+pub mod owned;
+#[cfg(feature = "async")]
+pub mod paced;
 pub mod peripheral;
+#[cfg(feature = "scatter-gather")]
+pub mod pool;
+pub mod profile;
 mod ral;
+#[cfg(feature = "async")]
+pub mod reshape;
+pub mod retry;
+#[cfg(feature = "async")]
+pub mod scan;
+#[cfg(all(feature = "async", feature = "scatter-gather"))]
+pub mod segments;
+#[cfg(feature = "async")]
+pub mod shared;
+pub mod signal;
+#[cfg(feature = "async")]
+pub mod staging;
+mod suspend;
+mod sync;
+pub mod tcd;
+#[cfg(feature = "async")]
+pub mod timestamp;
+#[cfg(feature = "async")]
+pub mod tuning;
+#[cfg(feature = "async")]
+pub mod watchdog;
+#[cfg(feature = "async")]
+pub mod waveform;
 
 pub use element::Element;
-pub use error::Error;
-pub use interrupt::Transfer;
+pub use error::{ConfigError, Error, ErrorStatus};
+pub use instance::Instance;
+#[cfg(feature = "async")]
+pub use interrupt::{Transfer, TransferTicket};
 pub use ral::tcd::BandwidthControl;
+pub use suspend::{Activity, Suspended};
 
 /// A DMA result
 pub type Result<T> = core::result::Result<T, Error>;
@@ -93,7 +263,14 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub struct Dma<const CHANNELS: usize> {
     controller: ral::Static<ral::dma::RegisterBlock>,
     multiplexer: ral::Static<ral::dmamux::RegisterBlock>,
+    #[cfg(feature = "async")]
     wakers: [SharedWaker; CHANNELS],
+    #[cfg(feature = "async")]
+    notifiers: [notify::SharedNotifier; CHANNELS],
+    /// One bit for each channel that's split or claimed from the driver.
+    claimed: core::sync::atomic::AtomicU32,
+    /// Which controller this is, and its interrupt lines.
+    instance: Instance,
 }
 
 // Safety: OK to allocate a DMA driver in a static context.
@@ -118,14 +295,71 @@ impl<const CHANNELS: usize> Dma<CHANNELS> {
     ///
     /// An incorrect `CHANNELS` value prevents proper bounds checking when
     /// allocating channels. This may result in DMA channels that point to
-    /// invalid memory.
+    /// invalid memory. The build fails if `CHANNELS` is zero, or greater than
+    /// 32, the most channels that a DMA controller supports.
     pub const unsafe fn new(controller: *const (), multiplexer: *const ()) -> Self {
+        const { assert!(CHANNELS > 0 && CHANNELS <= 32, "Invalid DMA channel count") };
         Self {
             controller: ral::Static(controller.cast()),
             multiplexer: ral::Static(multiplexer.cast()),
+            #[cfg(feature = "async")]
             wakers: [NO_WAKER; CHANNELS],
+            #[cfg(feature = "async")]
+            notifiers: [notify::NO_NOTIFIER; CHANNELS],
+            claimed: core::sync::atomic::AtomicU32::new(0),
+            instance: Instance::single(CHANNELS),
         }
     }
+
+    /// Create the DMA driver from register block addresses.
+    ///
+    /// Use this when you're not using a register access layer, and you have the
+    /// DMA controller and DMA multiplexer addresses from your reference manual.
+    ///
+    /// ```
+    /// use imxrt_dma::Dma;
+    ///
+    /// // Safety: addresses and channel count are valid for an i.MX RT 1060.
+    /// static DMA: Dma<32> = unsafe { Dma::from_addresses(0x400E_8000, 0x400E_C000) };
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`new`](Self::new) for the safety requirements.
+    pub const unsafe fn from_addresses(controller: usize, multiplexer: usize) -> Self {
+        Self::new(controller as *const (), multiplexer as *const ())
+    }
+
+    /// Create the DMA driver from register block base addresses.
+    ///
+    /// Use this with a peripheral access crate, or your own register layer, that
+    /// exposes the eDMA and DMAMUX register blocks as pointers. The driver doesn't
+    /// take ownership of any other crate's peripheral instances. With a chip
+    /// feature, the `memory` module provides the addresses.
+    ///
+    /// ```
+    /// use imxrt_dma::Dma;
+    /// # mod pac {
+    /// #   pub struct DMA0; impl DMA0 { pub const PTR: *const u32 = 0x400E_8000 as _; }
+    /// #   pub struct DMAMUX; impl DMAMUX { pub const PTR: *const u32 = 0x400E_C000 as _; }
+    /// # }
+    ///
+    /// // Safety: addresses and channel count are valid for an i.MX RT 1060.
+    /// static DMA: Dma<32> = unsafe {
+    ///     Dma::from_base_addresses(
+    ///         pac::DMA0::PTR.cast_mut().cast(),
+    ///         pac::DMAMUX::PTR.cast_mut().cast(),
+    ///     )
+    /// };
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`new`](Self::new) for the safety requirements.
+    pub const unsafe fn from_base_addresses(edma: *mut (), dmamux: *mut ()) -> Self {
+        Self::new(edma.cast_const(), dmamux.cast_const())
+    }
 }
 
+#[cfg(feature = "async")]
 use interrupt::{SharedWaker, NO_WAKER};
@@ -0,0 +1,299 @@
+//! A DMA-backed sink for log messages.
+//!
+//! Formatting log messages is cheap. Sending them over a slow UART isn't. A
+//! [`Logger`] copies each message into a ring buffer, and returns. A dedicated
+//! DMA channel sends the buffered bytes to the UART in the background, so your
+//! control loop only spends the time it takes to copy the bytes.
+//!
+//! Place the logger in a static, then [`start`](Logger::start) it with a channel
+//! and a [`Destination`]. Bytes written before `start` stay in the buffer until the
+//! logger starts. Route the channel's interrupt handler to [`Logger::on_interrupt`],
+//! not to [`Dma::on_interrupt`](crate::Dma::on_interrupt). The logger sends the
+//! next bytes from its interrupt handler.
+//!
+//! When the buffer is full, the logger's [`Overflow`] policy decides which bytes
+//! are lost. The logger counts the lost bytes; see [`dropped`](Logger::dropped).
+//!
+//! ```no_run
+//! use core::fmt::Write;
+//! use imxrt_dma::{channel::Channel, logger::{Logger, Overflow}, peripheral};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct Lpuart2;
+//! # unsafe impl peripheral::Destination<u8> for Lpuart2 {
+//! #   fn destination_signal(&self) -> u32 { 0 }
+//! #   fn destination_address(&self) -> *const u8 { panic!() }
+//! #   fn enable_destination(&mut self) { panic!() }
+//! #   fn disable_destination(&mut self) { panic!() }
+//! # }
+//!
+//! static LOGGER: Logger<Lpuart2, 1024> = Logger::new(Overflow::DropNewest);
+//!
+//! // #[cortex_m_rt::interrupt]
+//! fn DMA7() {
+//!     LOGGER.on_interrupt();
+//! }
+//!
+//! let lpuart2 = // A LPUART transmitter
+//!     # Lpuart2;
+//! let channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! // TODO unmask DMA7 interrupt!
+//! LOGGER.start(channel_7, lpuart2);
+//!
+//! let error = 0.25f32;
+//! writeln!(LOGGER.writer(), "error: {error}").ok();
+//! ```
+//!
+//! To use the logger for `defmt`, implement `defmt::Logger`, and pass the encoded
+//! bytes to [`write`](Logger::write).
+
+use crate::{
+    cache,
+    channel::{self, Channel, Configuration},
+    peripheral::Destination,
+    sync::Mutex,
+};
+
+use core::cell::{RefCell, UnsafeCell};
+
+/// Describes what a [`Logger`] drops when its buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// Drop the oldest bytes to make room for the newest bytes
+    ///
+    /// The logger discards the bytes that the DMA channel hasn't started to send.
+    /// Bytes that the channel is sending are never dropped, so the newest bytes
+    /// may still be truncated.
+    DropOldest,
+    /// Keep the oldest bytes, and drop the newest bytes that don't fit
+    DropNewest,
+}
+
+/// The logger's ring buffer indices, and the DMA channel that drains it
+///
+/// Indices increase forever. The position in the buffer is the index modulo
+/// the buffer's size.
+struct State<D> {
+    sink: Option<(Channel, D)>,
+    overflow: Overflow,
+    /// The first byte that isn't sent.
+    read: usize,
+    /// The number of bytes, starting at `read`, that the channel is sending.
+    flight: usize,
+    /// The next byte to write.
+    write: usize,
+    /// The number of bytes lost to overflow.
+    dropped: usize,
+}
+
+/// A ring buffer for log messages that a DMA channel sends to a peripheral
+///
+/// `N` is the size of the buffer, in bytes. See the [module documentation](crate::logger)
+/// for more information.
+pub struct Logger<D, const N: usize> {
+    buffer: UnsafeCell<[u8; N]>,
+    state: Mutex<RefCell<State<D>>>,
+}
+
+// Safety: the buffer is only written in a critical section, and never where the
+// DMA channel is reading. The destination is only accessed in a critical section.
+unsafe impl<D: Send, const N: usize> Sync for Logger<D, N> {}
+
+impl<D, const N: usize> Logger<D, N>
+where
+    D: Destination<u8>,
+{
+    /// Create a logger with an `overflow` policy
+    ///
+    /// The build fails if `N` is zero.
+    pub const fn new(overflow: Overflow) -> Self {
+        const { assert!(N > 0, "Logger needs a non-empty buffer") };
+        Logger {
+            buffer: UnsafeCell::new([0; N]),
+            state: Mutex::new(RefCell::new(State {
+                sink: None,
+                overflow,
+                read: 0,
+                flight: 0,
+                write: 0,
+                dropped: 0,
+            })),
+        }
+    }
+
+    /// Start sending buffered bytes to `destination` using `channel`
+    ///
+    /// The logger owns the channel and the destination from now on. Enable the
+    /// channel's interrupt in the NVIC, and call [`on_interrupt`](Self::on_interrupt)
+    /// from the interrupt handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the logger is already started.
+    pub fn start(&self, mut channel: Channel, mut destination: D) {
+        channel.disable();
+        channel.set_disable_on_completion(true);
+        channel.set_interrupt_on_completion(true);
+        channel.clear_complete();
+        channel.clear_error();
+        channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+        // Safety: hardware address must be valid, otherwise impl is unsound.
+        // One byte per request.
+        unsafe {
+            channel::set_destination_hardware(&mut channel, destination.destination_address());
+            channel.set_minor_loop_bytes(1);
+        }
+        destination.enable_destination();
+
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            assert!(state.sink.is_none(), "Logger is already started");
+            state.sink = Some((channel, destination));
+            self.service(&mut state);
+        });
+    }
+
+    /// Buffer `bytes` for the DMA channel to send
+    ///
+    /// Returns the number of bytes that fit in the buffer. Bytes that don't fit
+    /// are dropped, according to the logger's [`Overflow`] policy. `write` never
+    /// waits for the DMA channel, so it's safe to call from any execution context.
+    pub fn write(&self, bytes: &[u8]) -> usize {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            if state.overflow == Overflow::DropOldest
+                && bytes.len() > N - (state.write - state.read)
+            {
+                let queued = state.write - state.read - state.flight;
+                state.dropped += queued;
+                state.write -= queued;
+            }
+            let accepted = bytes.len().min(N - (state.write - state.read));
+            state.dropped += bytes.len() - accepted;
+
+            let (mut position, mut bytes) = (state.write % N, &bytes[..accepted]);
+            while !bytes.is_empty() {
+                let len = bytes.len().min(N - position);
+                // Safety: the bytes are free, so the DMA channel isn't reading
+                // them. The copy is within the buffer.
+                unsafe {
+                    let buffer = self.buffer.get().cast::<u8>();
+                    core::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.add(position), len);
+                }
+                bytes = &bytes[len..];
+                position = 0;
+            }
+            state.write += accepted;
+
+            self.service(&mut state);
+            accepted
+        })
+    }
+
+    /// Returns a writer for formatted messages
+    ///
+    /// The writer never returns an error. Formatted bytes that don't fit are
+    /// dropped.
+    pub fn writer(&self) -> Writer<'_, D, N> {
+        Writer { logger: self }
+    }
+
+    /// Send the next buffered bytes once the DMA channel completes
+    ///
+    /// Call this from the DMA channel's interrupt handler.
+    pub fn on_interrupt(&self) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            if let Some((channel, _)) = &state.sink {
+                channel.clear_interrupt();
+            }
+            self.service(&mut state);
+        });
+    }
+
+    /// Wait until the DMA channel has sent every buffered byte
+    ///
+    /// `flush` polls the DMA channel, so it works with interrupts disabled, like in
+    /// a panic handler. Returns immediately if the logger isn't started.
+    pub fn flush(&self) {
+        while !self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            self.service(&mut state);
+            state.sink.is_none() || state.read == state.write
+        }) {}
+    }
+
+    /// Returns the number of bytes lost to overflow
+    pub fn dropped(&self) -> usize {
+        self.state.lock(|state| state.borrow().dropped)
+    }
+
+    /// Finish the bytes in flight, and start sending the next buffered bytes
+    fn service(&self, state: &mut State<D>) {
+        let State {
+            sink,
+            read,
+            flight,
+            write,
+            dropped,
+            ..
+        } = state;
+        let Some((channel, _)) = sink.as_mut() else {
+            return;
+        };
+        if *flight > 0 {
+            if channel.is_error() {
+                channel.disable();
+                channel.clear_error();
+                *dropped += *flight;
+            } else if !channel.is_complete() {
+                return;
+            }
+            channel.clear_complete();
+            *read += *flight;
+            *flight = 0;
+        }
+        if *read == *write {
+            return;
+        }
+        let start = *read % N;
+        let len = (*write - *read).min(N - start).min(0x7FFF);
+        // Safety: the bytes are written, and they won't be written again
+        // until the DMA channel has sent them.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(self.buffer.get().cast::<u8>().add(start), len) };
+        cache::prepare_source(bytes);
+        // Safety: the logger outlives the transfer, since the logger owns the
+        // channel. One byte per minor loop, and as many iterations as bytes.
+        let enable = unsafe {
+            channel::set_source_linear_buffer(channel, bytes);
+            channel.set_transfer_iterations(len as u16);
+            channel.try_enable()
+        };
+        if enable.is_err() {
+            // The channel won't send a corrupt transfer. Drop the bytes.
+            *read += len;
+            *dropped += len;
+            return;
+        }
+        *flight = len;
+    }
+}
+
+/// Writes formatted messages into a [`Logger`]
+///
+/// Use [`Logger::writer`] to create a writer.
+pub struct Writer<'a, D, const N: usize> {
+    logger: &'a Logger<D, N>,
+}
+
+impl<D, const N: usize> core::fmt::Write for Writer<'_, D, N>
+where
+    D: Destination<u8>,
+{
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        self.logger.write(string.as_bytes());
+        Ok(())
+    }
+}
@@ -1,15 +1,25 @@
 //! DMA-powered memcpy
+//!
+//! A copy of zero elements completes on its first poll. It never touches the
+//! DMA channel. To report progress during a long copy, use [`with_progress`]. To
+//! share the bus, and the executor, during a long copy, use [`chunked`]. For many
+//! small copies, keep a few channels ready in a [`MemcpyPool`]. To copy one buffer
+//! into many buffers, use `broadcast`. To check a copy's result as it runs, use
+//! `verified`.
 
 use crate::{
+    cache,
     channel::{self, Channel},
     interrupt::Transfer,
-    Element, Error,
+    sync::Mutex,
+    BandwidthControl, Element, Error,
 };
 
 use core::{
+    cell::{RefCell, UnsafeCell},
     future::Future,
     pin::Pin,
-    task::{Context, Poll},
+    task::{Context, Poll, Waker},
 };
 
 /// A memcpy operation
@@ -19,6 +29,8 @@ use core::{
 pub struct Memcpy<'a, E> {
     transfer: Transfer<'a>,
     channel: &'a Channel,
+    started: bool,
+    cache: cache::Destination,
     _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
 }
 
@@ -55,14 +67,33 @@ pub struct Memcpy<'a, E> {
 /// memcpy::memcpy(&source, &mut destination, &mut channel_7).await?;
 /// # Ok(()) }
 /// ```
+///
+/// A copy of zero elements completes on its first poll, without touching the channel.
+///
+/// ```
+/// use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// use imxrt_dma::memcpy;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # let mut channel_7 = unsafe { DMA.channel(7) };
+///
+/// let source: [u32; 0] = [];
+/// let mut destination = [0u32; 5];
+///
+/// let copy = pin!(memcpy::memcpy(&source, &mut destination, &mut channel_7));
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert_eq!(copy.poll(&mut cx), Poll::Ready(Ok(())));
+/// ```
 pub fn memcpy<'a, E: Element>(
     source: &'a [E],
     destination: &'a mut [E],
     channel: &'a mut Channel,
 ) -> Memcpy<'a, E> {
-    channel.disable();
+    let elements = source.len().min(destination.len());
+    if elements == 0 {
+        return empty(channel);
+    }
 
-    channel.set_disable_on_completion(true);
+    channel.disable();
 
     // Safety: buffers borrowed by `memcpy`, and will be valid
     // while a transfer is in progress.
@@ -71,33 +102,763 @@ pub fn memcpy<'a, E: Element>(
         channel::set_destination_linear_buffer(channel, destination);
     }
 
-    // Turn off any DMAMUX configuration.
-    //
-    // Alternatively, we could use an always-on transfer, which might not need an
-    // explicit "start()" activation. This means we could express the transfer
-    // as a series of major loops, each transferring sizeof(E) bytes in the minor
-    // loop. TBD...
-    channel.set_channel_configuration(channel::Configuration::Off);
+    // Safety: transferring the minimum number of elements between buffers.
+    unsafe { prepare(channel, elements, core::mem::size_of::<E>()) };
 
-    // Transfer all elements in a single major loop
-    //
-    // Safety: transferring the minimum number of bytes between buffers,
-    // and there's only one major loop to perform the transfer.
+    cache::prepare_source(&source[..elements]);
+    let cache = cache::Destination::prepare(destination);
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        channel,
+        started: false,
+        cache,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Perform a DMA-powered copy of elements from one part of a buffer to
+/// another part of the same buffer
+///
+/// This is the DMA equivalent of [`slice::copy_within`]. `source` is a range
+/// within `buffer` that's copied to the elements starting at `destination`.
+/// The two regions may overlap. When the destination follows the source,
+/// the DMA channel copies elements from the end of the source to the start
+/// of the source, so that no element is overwritten before it's copied.
+///
+/// # Panics
+///
+/// Panics if either the source or the destination range exceeds the end of
+/// `buffer`, or if the end of the source range is before its start.
+///
+/// # Example
+///
+/// Shift a buffer of `u16`s one element to the right.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut buffer = [1u16, 2, 3, 4, 5];
+/// memcpy::copy_within(&mut buffer, 0..4, 1, &mut channel_7).await?;
+/// assert_eq!(buffer, [1, 1, 2, 3, 4]);
+/// # Ok(()) }
+/// ```
+pub fn copy_within<'a, E: Element>(
+    buffer: &'a mut [E],
+    source: core::ops::Range<usize>,
+    destination: usize,
+    channel: &'a mut Channel,
+) -> Memcpy<'a, E> {
+    assert!(
+        source.start <= source.end && source.end <= buffer.len(),
+        "DMA copy source range exceeds buffer"
+    );
+    let len = source.end - source.start;
+    assert!(
+        destination <= buffer.len() - len,
+        "DMA copy destination exceeds buffer"
+    );
+    if len == 0 {
+        return empty(channel);
+    }
+
+    channel.disable();
+
+    let size = core::mem::size_of::<E>();
+    let base = buffer.as_mut_ptr();
+    // Safety: both regions are within `buffer`, which is borrowed by the
+    // future. We never form overlapping references to the regions. A copy
+    // towards higher addresses runs backwards, so overlapping elements are read
+    // before they're written.
+    unsafe {
+        if destination > source.start {
+            let last = len - 1;
+            channel.set_source_address(base.add(source.start + last));
+            channel.set_source_offset((size as i16).wrapping_neg());
+            channel.set_destination_address(base.add(destination + last));
+            channel.set_destination_offset((size as i16).wrapping_neg());
+        } else {
+            channel.set_source_address(base.add(source.start));
+            channel.set_source_offset(size as i16);
+            channel.set_destination_address(base.add(destination));
+            channel.set_destination_offset(size as i16);
+        }
+        channel.set_source_attributes::<E>(0);
+        channel.set_destination_attributes::<E>(0);
+        channel.set_source_last_address_adjustment(0);
+        channel.set_destination_last_address_adjustment(0);
+
+        prepare(channel, len, size);
+    }
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        channel,
+        started: false,
+        cache: cache::Destination::none(),
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Perform a DMA-powered copy of one stream out of an interleaved `source` buffer
+///
+/// `source` interleaves `streams` streams of elements, like an interleaved stereo
+/// audio buffer with a left and right sample per frame. This copies the elements of
+/// stream `index` from each frame into the linear `destination` buffer. The transfer
+/// copies the minimum number of elements between the complete frames in `source`
+/// and the destination. Any incomplete frame at the end of `source` is not copied.
+///
+/// To split all streams, perform one `deinterleave` per stream.
+///
+/// # Panics
+///
+/// Panics if `index` is not less than `streams`, or if the stride between elements
+/// of a stream is larger than 32767 bytes.
+///
+/// # Example
+///
+/// Split an interleaved stereo buffer into left and right buffers.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let stereo = [0u16; 2 * 64];
+/// let mut left = [0; 64];
+/// let mut right = [0; 64];
+///
+/// memcpy::deinterleave(&stereo, 2, 0, &mut left, &mut channel_7).await?;
+/// memcpy::deinterleave(&stereo, 2, 1, &mut right, &mut channel_7).await?;
+/// # Ok(()) }
+/// ```
+pub fn deinterleave<'a, E: Element>(
+    source: &'a [E],
+    streams: usize,
+    index: usize,
+    destination: &'a mut [E],
+    channel: &'a mut Channel,
+) -> Memcpy<'a, E> {
+    let stride = stream_stride::<E>(streams, index);
+    let elements = (source.len() / streams).min(destination.len());
+    if elements == 0 {
+        return empty(channel);
+    }
+
+    channel.disable();
+
+    // Safety: the strided source only reads elements within complete
+    // frames of `source`. Both buffers are borrowed by the future.
+    unsafe {
+        channel.set_source_address(source.as_ptr().wrapping_add(index));
+        channel.set_source_offset(stride);
+        channel.set_source_attributes::<E>(0);
+        channel.set_source_last_address_adjustment(0);
+        channel::set_destination_linear_buffer(channel, destination);
+
+        prepare(channel, elements, core::mem::size_of::<E>());
+    }
+
+    Memcpy {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        channel,
+        started: false,
+        cache: cache::Destination::none(),
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Perform a DMA-powered copy of a linear `source` into one stream of an
+/// interleaved `destination` buffer
+///
+/// This is the inverse of [`deinterleave`]. `destination` interleaves `streams`
+/// streams of elements. This copies elements from `source` into stream `index`
+/// of each frame of the destination. Elements of the other streams are not
+/// modified. The transfer copies the minimum number of elements between the
+/// source and the complete frames in `destination`.
+///
+/// To merge all streams, perform one `interleave` per stream.
+///
+/// # Panics
+///
+/// Panics if `index` is not less than `streams`, or if the stride between elements
+/// of a stream is larger than 32767 bytes.
+///
+/// # Example
+///
+/// Merge left and right buffers into an interleaved stereo buffer.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let left = [0u16; 64];
+/// let right = [0u16; 64];
+/// let mut stereo = [0; 2 * 64];
+///
+/// memcpy::interleave(&left, &mut stereo, 2, 0, &mut channel_7).await?;
+/// memcpy::interleave(&right, &mut stereo, 2, 1, &mut channel_7).await?;
+/// # Ok(()) }
+/// ```
+pub fn interleave<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    streams: usize,
+    index: usize,
+    channel: &'a mut Channel,
+) -> Memcpy<'a, E> {
+    let stride = stream_stride::<E>(streams, index);
+    let elements = source.len().min(destination.len() / streams);
+    if elements == 0 {
+        return empty(channel);
+    }
+
+    channel.disable();
+
+    // Safety: the strided destination only writes elements within complete
+    // frames of `destination`. Both buffers are borrowed by the future.
     unsafe {
-        channel.set_minor_loop_bytes(
-            core::mem::size_of::<E>().saturating_mul(source.len().min(destination.len())) as u32,
-        );
-        channel.set_transfer_iterations(1);
+        channel::set_source_linear_buffer(channel, source);
+        channel.set_destination_address(destination.as_ptr().wrapping_add(index));
+        channel.set_destination_offset(stride);
+        channel.set_destination_attributes::<E>(0);
+        channel.set_destination_last_address_adjustment(0);
+
+        prepare(channel, elements, core::mem::size_of::<E>());
     }
 
     Memcpy {
         // Safety: transfer is properly prepared
         transfer: unsafe { Transfer::new(channel) },
         channel,
+        started: false,
+        cache: cache::Destination::none(),
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Computes the offset, in bytes, between elements of one interleaved stream
+fn stream_stride<E>(streams: usize, index: usize) -> i16 {
+    assert!(index < streams, "DMA stream index exceeds the stream count");
+    let stride = streams.saturating_mul(core::mem::size_of::<E>());
+    assert!(
+        stride <= i16::MAX as usize,
+        "DMA stream stride is too large"
+    );
+    stride as i16
+}
+
+/// A 2D block copy
+///
+/// `Blit` yields when it's copied a rectangle of elements between two pitched
+/// buffers. Use the [`blit`] function to define the transfer.
+pub struct Blit<'a, E> {
+    transfer: Transfer<'a>,
+    channel: &'a Channel,
+    /// The rows left to copy after the running transfer, when each row is its
+    /// own transfer
+    rows: usize,
+    _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Perform a DMA-powered copy of a `width` x `height` rectangle of elements
+/// between two pitched buffers
+///
+/// `source` and `destination` start at the top-left element of the rectangle,
+/// and each buffer's pitch describes the number of elements between the start
+/// of one row and the start of the next row. For example, to copy into a 320 x 240
+/// framebuffer at row `y` and column `x`, use `&mut framebuffer[y * 320 + x..]` as
+/// the destination, and 320 as the destination pitch.
+///
+/// If both pitches equal the width, the rectangle is contiguous, and the transfer
+/// is a plain copy. Otherwise, each row is one minor loop, and the transfer uses
+/// minor loop offsets to skip between rows. If minor loop offsets can't describe
+/// the rectangle, because a row is larger than 1023 bytes, there are more than
+/// 32767 rows, or both buffers skip a different number of elements between rows,
+/// the future copies one row per transfer. It restarts the channel after each row.
+///
+/// The DMA channel's multiplexer is always on, so the transfer runs without software
+/// service requests. You're responsible for enabling any interrupts, and calling
+/// [`on_interrupt`](crate::Dma::on_interrupt) if the interrupt fires. Otherwise, you
+/// may poll the transfer until completion.
+///
+/// # Panics
+///
+/// Panics if
+///
+/// - the rectangle is wider than either pitch. An empty rectangle completes on its
+///   first poll.
+/// - either buffer is too small to hold the rectangle.
+///
+/// # Example
+///
+/// Draw a 16 x 16 sprite into a 320 x 240 framebuffer.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let sprite = [0xF800u16; 16 * 16];
+/// let mut framebuffer = [0u16; 320 * 240];
+/// let (x, y) = (100, 50);
+///
+/// memcpy::blit(
+///     &sprite,
+///     16,
+///     &mut framebuffer[y * 320 + x..],
+///     320,
+///     (16, 16),
+///     &mut channel_7,
+/// )
+/// .await?;
+/// # Ok(()) }
+/// ```
+pub fn blit<'a, E: Element>(
+    source: &'a [E],
+    source_pitch: usize,
+    destination: &'a mut [E],
+    destination_pitch: usize,
+    (width, height): (usize, usize),
+    channel: &'a mut Channel,
+) -> Blit<'a, E> {
+    let size = core::mem::size_of::<E>();
+
+    if width == 0 || height == 0 {
+        return Blit {
+            transfer: Transfer::empty(channel),
+            channel,
+            rows: 0,
+            _elem: core::marker::PhantomData,
+        };
+    }
+    assert!(
+        width <= source_pitch && width <= destination_pitch,
+        "DMA blit rectangle is wider than the pitch"
+    );
+    assert!(
+        (height - 1) * source_pitch + width <= source.len(),
+        "DMA blit source is too small"
+    );
+    assert!(
+        (height - 1) * destination_pitch + width <= destination.len(),
+        "DMA blit destination is too small"
+    );
+
+    let row = (width * size) as u32;
+    let source_skip = ((source_pitch - width) * size) as i32;
+    let destination_skip = ((destination_pitch - width) * size) as i32;
+    let offset = if source_skip == destination_skip {
+        Some(channel::MinorLoopOffset::Both(source_skip))
+    } else if source_skip == 0 {
+        Some(channel::MinorLoopOffset::Destination(destination_skip))
+    } else if destination_skip == 0 {
+        Some(channel::MinorLoopOffset::Source(source_skip))
+    } else {
+        None
+    };
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+
+    // Safety: the rectangles are within the borrowed buffers, which are
+    // valid while the transfer is in progress. The minor loop offsets, or
+    // the last address adjustments, move the addresses to the start of
+    // each row.
+    let rows = unsafe {
+        channel.set_source_address(source.as_ptr());
+        channel.set_source_offset(size as i16);
+        channel.set_source_attributes::<E>(0);
+        channel.set_source_last_address_adjustment(0);
+
+        channel.set_destination_address(destination.as_ptr());
+        channel.set_destination_offset(size as i16);
+        channel.set_destination_attributes::<E>(0);
+        channel.set_destination_last_address_adjustment(0);
+
+        if source_skip == 0 && destination_skip == 0 {
+            channel.set_minor_loop_bytes(row.saturating_mul(height as u32));
+            channel.set_transfer_iterations(1);
+            0
+        } else if height <= 0x7FFF
+            && offset.is_some_and(|offset| {
+                channel
+                    .try_set_minor_loop_bytes_with_offset(row, offset)
+                    .is_ok()
+            })
+        {
+            channel.set_transfer_iterations(height as u16);
+            0
+        } else {
+            channel.set_minor_loop_bytes(row);
+            channel.set_transfer_iterations(1);
+            channel.set_source_last_address_adjustment(source_skip);
+            channel.set_destination_last_address_adjustment(destination_skip);
+            height - 1
+        }
+    };
+
+    // The always-on request signal runs every minor loop, and
+    // 'disable on completion' stops the channel after the last row.
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+
+    let channel: &'a Channel = channel;
+    Blit {
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        channel,
+        rows,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+impl<E> Future for Blit<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        loop {
+            let poll = transfer.as_mut().poll(cx);
+            if this.rows == 0 || poll != Poll::Ready(Ok(())) {
+                return poll;
+            }
+            this.rows -= 1;
+            // The last address adjustments moved the addresses to the next row.
+            // Safety: the channel describes the next row of the rectangle.
+            transfer.set(unsafe { Transfer::new(this.channel) });
+        }
+    }
+}
+
+/// A copy of one buffer into many buffers
+///
+/// `Broadcast` yields when it's copied the source into every destination. Use the
+/// [`broadcast`] function to define the transfer.
+#[cfg(feature = "scatter-gather")]
+pub struct Broadcast<'a, E, const N: usize> {
+    transfer: Transfer<'a>,
+    caches: [cache::Destination; N],
+    _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Perform a DMA-powered copy of `source` into each of the `destinations`
+///
+/// Each destination receives the minimum number of elements between the source and
+/// that destination. The copy describes each destination with one descriptor in
+/// `tcds`, and links the descriptors into a chain. The DMA channel runs the whole
+/// chain without CPU work between destinations, and disables itself after the last
+/// destination. Use it to mirror configuration tables, or to feed redundant
+/// outputs.
+///
+/// The channel's multiplexer is always on, so the copy runs without software
+/// service requests. If the channel interrupts on completion, only the last
+/// destination generates the interrupt.
+///
+/// # Example
+///
+/// Mirror a table into two copies.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy, tcd::Tcd};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let table = [1u32, 2, 3, 4];
+/// let (mut primary, mut backup) = ([0; 4], [0; 4]);
+/// let mut tcds = [Tcd::new(); 2];
+///
+/// memcpy::broadcast(&table, [&mut primary, &mut backup], &mut tcds, &mut channel_7).await?;
+/// assert_eq!(primary, backup);
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "scatter-gather")]
+pub fn broadcast<'a, E: Element, const N: usize>(
+    source: &'a [E],
+    destinations: [&'a mut [E]; N],
+    tcds: &'a mut [crate::tcd::Tcd; N],
+    channel: &'a mut Channel,
+) -> Broadcast<'a, E, N> {
+    use crate::tcd::Tcd;
+
+    let size = core::mem::size_of::<E>();
+    let mut template = Tcd::new();
+    template.set_source_address(source.as_ptr());
+    template.set_source_offset(size as i16);
+    template.set_source_attributes::<E>(0);
+    template.set_destination_offset(size as i16);
+    template.set_destination_attributes::<E>(0);
+
+    let mut destinations = destinations;
+    let caches = core::array::from_fn(|index| {
+        let destination = &mut *destinations[index];
+        let elements = source.len().min(destination.len());
+        let tcd = &mut tcds[index];
+        *tcd = template;
+        if elements == 0 {
+            // The chain skips descriptors without transfer iterations.
+            return cache::Destination::none();
+        }
+        tcd.set_destination_address(destination.as_ptr());
+        tcd.set_minor_loop_bytes((elements * size) as u32);
+        tcd.set_transfer_iterations(1);
+        cache::Destination::prepare(&mut destination[..elements])
+    });
+
+    cache::prepare_source(source);
+    Broadcast {
+        transfer: run_chain(tcds, channel),
+        caches,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Link `tcds`, and prepare `channel` to run the chain without software service
+/// requests
+///
+/// The last non-empty descriptor stops the channel, and it interrupts if the
+/// channel interrupts on completion. If every descriptor is empty, the transfer
+/// never touches the channel.
+#[cfg(feature = "scatter-gather")]
+fn run_chain<'a>(tcds: &'a mut [crate::tcd::Tcd], channel: &'a mut Channel) -> Transfer<'a> {
+    let Some(last) = tcds.iter().rposition(|tcd| tcd.transfer_iterations() != 0) else {
+        return Transfer::empty(channel);
+    };
+    let interrupt = crate::tcd::Tcd::capture(channel).interrupt_on_completion();
+    tcds[last].set_disable_on_completion(true);
+    tcds[last].set_interrupt_on_completion(interrupt);
+
+    channel.disable();
+    channel.clear_error();
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+    // Safety: caller's descriptors copy within borrowed buffers, and the
+    // transfer borrows the descriptors until it completes or is dropped.
+    if let Some(first) = crate::tcd::link(tcds) {
+        unsafe { tcds[first].apply(channel) };
+    }
+    // Safety: transfer is properly prepared
+    unsafe { Transfer::new(channel) }
+}
+
+#[cfg(feature = "scatter-gather")]
+impl<E, const N: usize> Future for Broadcast<'_, E, N> {
+    type Output = Result<(), Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        let transfer = unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.transfer) };
+        let poll = transfer.poll(cx);
+        if let Poll::Ready(Ok(())) = poll {
+            self.caches.iter().for_each(cache::Destination::complete);
+        }
+        poll
+    }
+}
+
+/// A copy that checks its own result
+///
+/// `Verified` yields once it's copied the source into the destination, and checked
+/// a window of the copy. Use the [`verified`] function to define the transfer.
+#[cfg(feature = "scatter-gather")]
+pub struct Verified<'a, E> {
+    transfer: Transfer<'a>,
+    destination: cache::Destination,
+    readback: cache::Destination,
+    /// The window of the source that's read back.
+    expected: &'a [E],
+    /// The buffer that receives the window of the destination.
+    check: &'a mut [E],
+}
+
+/// Perform a DMA-powered copy of `source` into `destination`, then read back part
+/// of the copy
+///
+/// The copy moves the minimum number of elements between the source and
+/// destination. The first descriptor in `tcds` describes that copy. The second
+/// descriptor, chained to the first, copies a window of the destination back into
+/// `check`. The window has as many elements as `check`, or as the copy, whichever
+/// is fewer. It starts at element `offset`, modulo the length of the copy, and it
+/// never runs past the end of the copy. Once the channel completes both
+/// descriptors, the future compares the window against the source.
+///
+/// Advance `offset` between copies, and a small check buffer covers a large
+/// destination over time. The check catches copies that didn't land, or that
+/// landed somewhere else, without comparing the whole buffer on the CPU.
+///
+/// The channel's multiplexer is always on, so the copy runs without software
+/// service requests. If the channel interrupts on completion, only the read back
+/// generates the interrupt. If `check` is empty, the copy doesn't read anything
+/// back.
+///
+/// # Errors
+///
+/// Returns [`Error::Mismatch`] if the window doesn't match the source.
+///
+/// # Example
+///
+/// Copy a frame, and check a different 16 elements of the frame each time.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy, tcd::Tcd};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let frame = [0xA5u16; 480];
+/// let mut output = [0u16; 480];
+/// let mut check = [0u16; 16];
+/// let mut tcds = [Tcd::new(); 2];
+///
+/// for offset in (0..).step_by(check.len()) {
+///     memcpy::verified(&frame, &mut output, &mut check, offset, &mut tcds, &mut channel_7).await?;
+/// }
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "scatter-gather")]
+pub fn verified<'a, E: Element + PartialEq>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    check: &'a mut [E],
+    offset: usize,
+    tcds: &'a mut [crate::tcd::Tcd; 2],
+    channel: &'a mut Channel,
+) -> Verified<'a, E> {
+    use crate::tcd::Tcd;
+
+    let size = core::mem::size_of::<E>();
+    let elements = source.len().min(destination.len());
+    let window = check.len().min(elements);
+    let start = if window == 0 {
+        0
+    } else {
+        (offset % elements).min(elements - window)
+    };
+
+    let [copy, readback] = tcds;
+    *copy = Tcd::new();
+    *readback = Tcd::new();
+    if elements != 0 {
+        copy.set_source_address(source.as_ptr());
+        copy.set_source_offset(size as i16);
+        copy.set_source_attributes::<E>(0);
+        copy.set_destination_address(destination.as_ptr());
+        copy.set_destination_offset(size as i16);
+        copy.set_destination_attributes::<E>(0);
+        copy.set_minor_loop_bytes((elements * size) as u32);
+        copy.set_transfer_iterations(1);
+    }
+    if window != 0 {
+        readback.set_source_address(destination[start..].as_ptr());
+        readback.set_source_offset(size as i16);
+        readback.set_source_attributes::<E>(0);
+        readback.set_destination_address(check.as_ptr());
+        readback.set_destination_offset(size as i16);
+        readback.set_destination_attributes::<E>(0);
+        readback.set_minor_loop_bytes((window * size) as u32);
+        readback.set_transfer_iterations(1);
+    }
+
+    cache::prepare_source(source);
+    let check = &mut check[..window];
+    Verified {
+        destination: if elements != 0 {
+            cache::Destination::prepare(&mut destination[..elements])
+        } else {
+            cache::Destination::none()
+        },
+        readback: if window != 0 {
+            cache::Destination::prepare(check)
+        } else {
+            cache::Destination::none()
+        },
+        transfer: run_chain(tcds, channel),
+        expected: &source[start..start + window],
+        check,
+    }
+}
+
+#[cfg(feature = "scatter-gather")]
+impl<E: Element + PartialEq> Future for Verified<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        let this = unsafe { self.get_unchecked_mut() };
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        core::task::ready!(transfer.poll(cx))?;
+        this.destination.complete();
+        this.readback.complete();
+        // The DMA engine wrote the check buffer behind the compiler's back.
+        let matches = this
+            .check
+            .iter()
+            .zip(this.expected)
+            .all(|(check, expected)| {
+                // Safety: the check buffer is borrowed, and valid for reads.
+                unsafe { core::ptr::read_volatile(check) == *expected }
+            });
+        Poll::Ready(if matches {
+            Ok(())
+        } else {
+            Err(Error::Mismatch)
+        })
+    }
+}
+
+/// A copy that moves no elements, and never touches the channel
+fn empty<'a, E>(channel: &'a mut Channel) -> Memcpy<'a, E> {
+    Memcpy {
+        transfer: Transfer::empty(channel),
+        channel,
+        started: false,
+        cache: cache::Destination::none(),
         _elem: core::marker::PhantomData,
     }
 }
 
+/// Finish preparing a software-started, single major loop memory transfer
+///
+/// # Safety
+///
+/// Caller must have already configured the channel's source and destination,
+/// and they must be valid for `elements` elements of `size` bytes.
+unsafe fn prepare(channel: &mut Channel, elements: usize, size: usize) {
+    channel.set_disable_on_completion(true);
+
+    // Turn off any DMAMUX configuration.
+    //
+    // Alternatively, we could use an always-on transfer, which might not need an
+    // explicit "start()" activation. This means we could express the transfer
+    // as a series of major loops, each transferring sizeof(E) bytes in the minor
+    // loop. TBD...
+    channel.set_channel_configuration(channel::Configuration::Off);
+
+    // Transfer all elements in a single major loop
+    channel.set_minor_loop_bytes(size.saturating_mul(elements) as u32);
+    channel.set_transfer_iterations(1);
+}
+
 impl<E> Future for Memcpy<'_, E> {
     type Output = Result<(), Error>;
 
@@ -105,11 +866,547 @@ impl<E> Future for Memcpy<'_, E> {
         // Safety: data not moved
         let transfer = unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.transfer) };
         let poll = transfer.poll(cx);
-        if poll.is_pending() && !self.channel.is_active() {
+        // Only request service once. A second request that races with the
+        // end of the transfer would repeat the copy.
+        if poll.is_pending() && !self.started {
             self.channel.start();
+            // Safety: OK to toggle a bool...
+            *unsafe { &mut self.as_mut().get_unchecked_mut().started } = true;
+        }
+        if let Poll::Ready(Ok(())) = poll {
+            self.cache.complete();
         }
         poll
     }
 }
 
 // Drop handled by Transfer impl
+
+/// A memcpy that reports its progress
+///
+/// `Progress` copies the elements in chunks, and reports the number of elements
+/// copied after each chunk. Use [`with_progress`] to define the copy, and await
+/// each report with [`next_progress`](Progress::next_progress).
+pub struct Progress<'a, E> {
+    channel: &'a mut Channel,
+    /// The number of elements to copy.
+    elements: usize,
+    /// The number of elements copied by completed chunks.
+    copied: usize,
+    /// The number of elements per chunk.
+    every: usize,
+    /// The size of the chunk that's running, if any.
+    running: Option<usize>,
+    cache: cache::Destination,
+    _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Perform a DMA-powered `memcpy`, reporting progress every `every` elements
+///
+/// Like [`memcpy()`], the copy moves the minimum number of
+/// elements between the two buffers. The DMA channel copies `every` elements at a
+/// time, and the copy reports the total number of elements copied after each chunk.
+/// Use the reports to update a progress bar, or to feed a watchdog, during a long
+/// copy. The copy starts on the first poll, and the channel waits for the next poll
+/// between chunks.
+///
+/// Enable the channel's interrupt on completion, and call [`on_interrupt`](crate::Dma::on_interrupt)
+/// when the interrupt fires. Otherwise, poll the copy until it completes. With
+/// the `cache` feature, the destination is only coherent once the copy completes.
+///
+/// # Panics
+///
+/// Panics if `every` is zero.
+///
+/// # Example
+///
+/// Copy 64 KiB, and feed the watchdog after every 4 KiB.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # fn feed_watchdog() {}
+/// # async fn f(source: &[u32], destination: &mut [u32]) -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+///
+/// let mut copy = memcpy::with_progress(source, destination, 1024, &mut channel_7);
+/// while let Some(copied) = copy.next_progress().await {
+///     let _copied: usize = copied?;
+///     feed_watchdog();
+/// }
+/// # Ok(()) }
+/// ```
+pub fn with_progress<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    every: usize,
+    channel: &'a mut Channel,
+) -> Progress<'a, E> {
+    assert!(every > 0, "DMA progress interval is empty");
+    let elements = source.len().min(destination.len());
+    let mut cache = cache::Destination::none();
+    if elements > 0 {
+        channel.disable();
+        // Safety: buffers borrowed by the copy, and will be valid while a
+        // chunk is in progress. Each chunk continues from the end of the
+        // previous chunk.
+        unsafe {
+            channel::set_source_linear_buffer(channel, source);
+            channel::set_destination_linear_buffer(channel, destination);
+            channel.set_source_last_address_adjustment(0);
+            channel.set_destination_last_address_adjustment(0);
+            channel.set_disable_on_completion(true);
+            channel.set_channel_configuration(channel::Configuration::Off);
+            channel.set_transfer_iterations(1);
+        }
+        cache::prepare_source(&source[..elements]);
+        cache = cache::Destination::prepare(destination);
+    }
+    Progress {
+        channel,
+        elements,
+        copied: 0,
+        every,
+        running: None,
+        cache,
+        _elem: core::marker::PhantomData,
+    }
+}
+
+impl<'a, E: Element> Progress<'a, E> {
+    /// Poll for the next progress report
+    ///
+    /// Returns the total number of elements copied so far. Returns
+    /// `Poll::Ready(None)` once the copy completes, and all progress is reported.
+    /// After an error, the copy stops.
+    pub fn poll_progress(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<usize, Error>>> {
+        if self.copied == self.elements {
+            return Poll::Ready(None);
+        }
+        crate::interrupt::register_waker(self.channel, cx.waker());
+        let chunk = match self.running {
+            Some(chunk) => chunk,
+            None => {
+                let chunk = self.every.min(self.elements - self.copied);
+                // Safety: the chunk is within both buffers, and the addresses
+                // continue from the previous chunk.
+                let enable = unsafe {
+                    self.channel
+                        .set_minor_loop_bytes((chunk * core::mem::size_of::<E>()) as u32);
+                    self.channel.clear_complete();
+                    self.channel.try_enable()
+                };
+                if let Err(err) = enable {
+                    self.copied = self.elements;
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+                self.channel.start();
+                self.running = Some(chunk);
+                chunk
+            }
+        };
+        if self.channel.is_error() {
+            let es = self.channel.error_status();
+            self.channel.clear_error();
+            self.channel.disable();
+            self.running = None;
+            self.copied = self.elements;
+            return Poll::Ready(Some(Err(es.into())));
+        }
+        if !self.channel.is_complete() {
+            return Poll::Pending;
+        }
+        self.channel.clear_complete();
+        self.running = None;
+        self.copied += chunk;
+        if self.copied == self.elements {
+            self.cache.complete();
+        }
+        Poll::Ready(Some(Ok(self.copied)))
+    }
+
+    /// Wait for the next progress report
+    ///
+    /// See [`poll_progress`](Self::poll_progress) for more information.
+    pub fn next_progress(&mut self) -> NextProgress<'_, 'a, E> {
+        NextProgress { progress: self }
+    }
+}
+
+impl<E> Drop for Progress<'_, E> {
+    fn drop(&mut self) {
+        if self.running.is_some() {
+            self.channel.disable();
+            while self.channel.is_active() {}
+            self.channel.clear_complete();
+            self.channel.clear_error();
+        }
+        if self.elements > 0 {
+            crate::interrupt::clear_waker(self.channel);
+        }
+    }
+}
+
+/// A future that resolves with the next progress report
+///
+/// Use [`Progress::next_progress`] to create this future.
+pub struct NextProgress<'p, 'a, E> {
+    progress: &'p mut Progress<'a, E>,
+}
+
+impl<E: Element> Future for NextProgress<'_, '_, E> {
+    type Output = Option<Result<usize, Error>>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.progress.poll_progress(cx)
+    }
+}
+
+/// A memcpy that yields between chunks
+///
+/// Use [`chunked`] to define the copy.
+pub struct Chunked<'a, E> {
+    progress: Progress<'a, E>,
+}
+
+/// Perform a DMA-powered `memcpy` in chunks of `chunk` elements, yielding to the
+/// executor between chunks
+///
+/// Like [`memcpy()`], the copy moves the minimum number of elements between the
+/// two buffers. After each chunk, the copy wakes itself and returns `Poll::Pending`,
+/// and the DMA channel waits until the next poll to start the next chunk. Other
+/// tasks, and other bus masters, run between chunks, and dropping the copy between
+/// chunks cancels it without waiting on a long minor loop. To also throttle each
+/// chunk, see [`bandwidth_control`](Chunked::bandwidth_control).
+///
+/// Enable the channel's interrupt on completion, and call [`on_interrupt`](crate::Dma::on_interrupt)
+/// when the interrupt fires. Otherwise, poll the copy until it completes.
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+///
+/// # Example
+///
+/// Copy a frame buffer out of SDRAM in 8 KiB chunks, with the DMA engine stalling
+/// after each read and write.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, memcpy, BandwidthControl};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f(sdram: &[u32], frame: &mut [u32]) -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+///
+/// memcpy::chunked(sdram, frame, 2048, &mut channel_7)
+///     .bandwidth_control(Some(BandwidthControl::Stall4Cycles))
+///     .await?;
+/// # Ok(()) }
+/// ```
+pub fn chunked<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    chunk: usize,
+    channel: &'a mut Channel,
+) -> Chunked<'a, E> {
+    Chunked {
+        progress: with_progress(source, destination, chunk, channel),
+    }
+}
+
+impl<E: Element> Chunked<'_, E> {
+    /// Set the bandwidth control for every chunk
+    ///
+    /// `None` disables bandwidth control. See
+    /// [`Channel::set_bandwidth_control`](crate::channel::Channel::set_bandwidth_control)
+    /// for more information.
+    pub fn bandwidth_control(self, bandwidth: Option<BandwidthControl>) -> Self {
+        if self.progress.elements > 0 {
+            self.progress.channel.set_bandwidth_control(bandwidth);
+        }
+        self
+    }
+}
+
+impl<E: Element> Future for Chunked<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let progress = &mut self.progress;
+        match progress.poll_progress(cx) {
+            Poll::Ready(None) => Poll::Ready(Ok(())),
+            Poll::Ready(Some(Err(err))) => Poll::Ready(Err(err)),
+            Poll::Ready(Some(Ok(copied))) if copied == progress.elements => Poll::Ready(Ok(())),
+            Poll::Ready(Some(Ok(_))) => {
+                // Yield, and start the next chunk on the next poll.
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Which pooled channels are in use, and how each one is configured
+struct PoolState<const N: usize> {
+    /// Bit `n` is set if channel `n` is copying.
+    busy: u32,
+    /// The element size that each channel is configured for, or zero if the
+    /// channel needs a full configuration.
+    warm: [usize; N],
+    /// Wakers of the tasks waiting for a channel.
+    waiting: [Option<Waker>; N],
+}
+
+/// A pool of channels kept ready for memory-to-memory copies
+///
+/// A [`memcpy()`] writes the whole transfer control descriptor for every copy.
+/// A pool remembers how it configured each channel. When a channel's last copy
+/// used the same element type, the next copy only writes the addresses and the
+/// size. Repeated small copies spend less time setting up the channel.
+///
+/// [`copy`](Self::copy) waits for a free channel, then copies on that channel.
+/// Up to `N` copies run at the same time. Enable each channel's interrupt on
+/// completion, and call [`on_interrupt`](crate::Dma::on_interrupt) for each
+/// channel when its interrupt fires.
+///
+/// ```no_run
+/// use imxrt_dma::memcpy::MemcpyPool;
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+///
+/// let mut channels = // DMA channels 6 and 7
+///     # unsafe { [DMA.channel(6), DMA.channel(7)] };
+/// for channel in &mut channels {
+///     channel.set_interrupt_on_completion(true);
+/// }
+/// let pool = MemcpyPool::new(channels);
+///
+/// let source = [4u32, 5, 6, 7, 8];
+/// let mut destination = [0; 5];
+/// for _ in 0..100 {
+///     pool.copy(&source, &mut destination).await?;
+/// }
+/// # Ok(()) }
+/// ```
+pub struct MemcpyPool<const N: usize> {
+    channels: UnsafeCell<[Channel; N]>,
+    bandwidth: Option<BandwidthControl>,
+    state: Mutex<RefCell<PoolState<N>>>,
+}
+
+// Safety: a channel is only accessed by the copy that marked it busy.
+// Channels may be sent across execution contexts.
+unsafe impl<const N: usize> Sync for MemcpyPool<N> {}
+
+impl<const N: usize> MemcpyPool<N> {
+    /// Keep `channels` ready for copies
+    ///
+    /// The build fails if `N` is zero, or greater than 32.
+    pub const fn new(channels: [Channel; N]) -> Self {
+        const {
+            assert!(
+                N > 0 && N <= 32,
+                "MemcpyPool needs between 1 and 32 channels"
+            )
+        };
+        MemcpyPool {
+            channels: UnsafeCell::new(channels),
+            bandwidth: None,
+            state: Mutex::new(RefCell::new(PoolState {
+                busy: 0,
+                warm: [0; N],
+                waiting: [const { None }; N],
+            })),
+        }
+    }
+
+    /// Set the bandwidth control for every copy
+    ///
+    /// `None` disables bandwidth control. See
+    /// [`Channel::set_bandwidth_control`](crate::channel::Channel::set_bandwidth_control)
+    /// for more information.
+    pub const fn with_bandwidth_control(mut self, bandwidth: Option<BandwidthControl>) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Release the pooled channels
+    pub fn into_channels(self) -> [Channel; N] {
+        self.channels.into_inner()
+    }
+
+    /// Copy the minimum number of elements between `source` and `destination`
+    /// on the next free channel
+    ///
+    /// See [`memcpy()`] for more information. A copy of zero elements doesn't wait
+    /// for a channel.
+    pub fn copy<'a, E: Element>(
+        &'a self,
+        source: &'a [E],
+        destination: &'a mut [E],
+    ) -> PooledCopy<'a, E, N> {
+        let empty = source.is_empty() || destination.is_empty();
+        PooledCopy {
+            pool: self,
+            source,
+            destination: (!empty).then_some(destination),
+            index: None,
+            copy: None,
+        }
+    }
+
+    /// Mark a free channel busy, and return its index
+    ///
+    /// If every channel is busy, the task waits for a channel.
+    fn acquire(&self, cx: &mut Context<'_>) -> Option<usize> {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let free = !state.busy & (u32::MAX >> (32 - N));
+            if free != 0 {
+                let index = free.trailing_zeros() as usize;
+                state.busy |= 1 << index;
+                return Some(index);
+            }
+            let slot = match state.waiting.iter().position(Option::is_none) {
+                Some(slot) => slot,
+                None => {
+                    // Wake the oldest task, so that it registers again.
+                    if let Some(waker) = state.waiting[0].take() {
+                        waker.wake();
+                    }
+                    state.waiting.rotate_left(1);
+                    N - 1
+                }
+            };
+            state.waiting[slot] = Some(cx.waker().clone());
+            None
+        })
+    }
+
+    /// Free channel `index`, and wake the tasks waiting for a channel
+    ///
+    /// If `warm` is `false`, the next copy on the channel configures the whole
+    /// channel.
+    fn release(&self, index: usize, warm: bool) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.busy &= !(1 << index);
+            if !warm {
+                state.warm[index] = 0;
+            }
+            for waker in state.waiting.iter_mut().filter_map(Option::take) {
+                waker.wake();
+            }
+        });
+    }
+
+    /// Prepare and return a copy on busy channel `index`
+    fn start<'a, E: Element>(
+        &'a self,
+        index: usize,
+        source: &'a [E],
+        destination: &'a mut [E],
+    ) -> Memcpy<'a, E> {
+        // Safety: the channel is busy, so this copy has exclusive access.
+        let channel = unsafe { &mut (*self.channels.get())[index] };
+        let size = core::mem::size_of::<E>();
+        let warm = self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            core::mem::replace(&mut state.warm[index], size) == size
+        });
+        let elements = source.len().min(destination.len());
+
+        channel.disable();
+        // Safety: buffers borrowed by the copy, and will be valid while the
+        // transfer is in progress. Transferring the minimum number of elements
+        // between buffers. A warm channel describes the same kind of copy, so
+        // only the addresses and sizes change.
+        unsafe {
+            if warm {
+                channel::validate_source(source);
+                channel::validate_destination(destination);
+                channel.set_source_address(source.as_ptr());
+                channel.set_destination_address(destination.as_ptr());
+                channel.set_source_last_address_adjustment(
+                    (core::mem::size_of_val(source) as i32).wrapping_neg(),
+                );
+                channel.set_destination_last_address_adjustment(
+                    (core::mem::size_of_val(destination) as i32).wrapping_neg(),
+                );
+                channel.set_minor_loop_bytes(size.saturating_mul(elements) as u32);
+            } else {
+                channel.set_bandwidth_control(self.bandwidth);
+                channel::set_source_linear_buffer(channel, source);
+                channel::set_destination_linear_buffer(channel, destination);
+                prepare(channel, elements, size);
+            }
+        }
+
+        cache::prepare_source(&source[..elements]);
+        let cache = cache::Destination::prepare(destination);
+
+        Memcpy {
+            // Safety: transfer is properly prepared
+            transfer: unsafe { Transfer::new(channel) },
+            channel,
+            started: false,
+            cache,
+            _elem: core::marker::PhantomData,
+        }
+    }
+}
+
+/// A copy that runs on a channel from a [`MemcpyPool`]
+///
+/// Use [`MemcpyPool::copy`] to create this future. Dropping the future cancels
+/// the copy, and frees the channel.
+pub struct PooledCopy<'a, E, const N: usize> {
+    pool: &'a MemcpyPool<N>,
+    source: &'a [E],
+    /// Taken once the copy starts.
+    destination: Option<&'a mut [E]>,
+    /// The busy channel.
+    index: Option<usize>,
+    copy: Option<Memcpy<'a, E>>,
+}
+
+impl<E: Element, const N: usize> Future for PooledCopy<'_, E, N> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the copy is structurally pinned, and it's only dropped in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.copy.is_none() {
+            let Some(destination) = this.destination.take() else {
+                return Poll::Ready(Ok(()));
+            };
+            let Some(index) = this.pool.acquire(cx) else {
+                this.destination = Some(destination);
+                return Poll::Pending;
+            };
+            this.index = Some(index);
+            this.copy = Some(this.pool.start(index, this.source, destination));
+        }
+
+        let copy = unsafe { Pin::new_unchecked(this.copy.as_mut().unwrap()) };
+        let result = core::task::ready!(copy.poll(cx));
+        this.copy = None;
+        if let Some(index) = this.index.take() {
+            this.pool.release(index, result.is_ok());
+        }
+        Poll::Ready(result)
+    }
+}
+
+impl<E, const N: usize> Drop for PooledCopy<'_, E, N> {
+    fn drop(&mut self) {
+        // Cancel the copy before freeing its channel.
+        self.copy = None;
+        if let Some(index) = self.index.take() {
+            self.pool.release(index, true);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! The chip's DMA-accessible memory map.
+//!
+//! A DMA transfer that uses memory outside of the chip's memory map either fails
+//! with a bus error, or it silently does nothing. A destination in flash never
+//! changes. Use [`check_source`] and [`check_destination`] to check buffers before
+//! you describe a transfer.
+//!
+//! Enable the `validate` feature to perform these checks whenever you assign a
+//! buffer to a channel with the helpers in the [`channel`](crate::channel) module.
+//! With `validate`, the helpers panic if a buffer isn't DMA-accessible. Without the
+//! `cache` feature, they also log a `defmt` warning for a buffer in a cacheable
+//! region, if you enable the `defmt` feature.
+//!
+//! The checks return the buffer's [`Region`]. If the region
+//! [is cacheable](Region::is_cacheable), you're responsible for cleaning or
+//! invalidating the data cache around the transfer. The DMA engine does not
+//! observe the CPU's caches. See the [`cache`](crate::cache) module for more
+//! information.
+//!
+//! ```
+//! use imxrt_dma::memory::Region;
+//!
+//! let flash = Region::of(0x6000_1000).unwrap();
+//! assert_eq!(flash, Region::FlexSpi);
+//! assert!(!flash.is_writable());
+//! assert!(Region::of(0x1000_0000).is_none());
+//! ```
+//!
+//! The DMA engine reaches the tightly-coupled memories through the Cortex-M7's TCM
+//! slave port, but only the FlexRAM banks that are allocated to each TCM. A buffer
+//! beyond the allocation is in the memory map, and the transfer still ends with a
+//! bus error. If you place DMA buffers in ITCM or DTCM, describe the FlexRAM
+//! allocation with a [`Tcm`], and check the buffers with it:
+//!
+//! ```
+//! use imxrt_dma::{memory::{Region, Tcm}, ConfigError};
+//!
+//! // 64 KiB of ITCM, 128 KiB of DTCM.
+//! let tcm = Tcm::new(64 * 1024, 128 * 1024);
+//! let allocated = unsafe { core::slice::from_raw_parts(0x2001_0000 as *const u8, 64) };
+//! assert_eq!(tcm.check_source(allocated), Ok(Region::Dtcm));
+//!
+//! let unallocated = unsafe { core::slice::from_raw_parts(0x2002_0000 as *const u8, 64) };
+//! assert_eq!(tcm.check_source(unallocated), Err(ConfigError::UnallocatedTcm));
+//! ```
+
+use crate::ConfigError;
+
+/// The DMA controller's register block
+///
+/// Pass this to [`Dma::from_base_addresses`](crate::Dma::from_base_addresses), with
+/// [`DMAMUX`] and [`CHANNELS`]. Describe the controller with [`INSTANCE`]:
+///
+/// ```
+/// use imxrt_dma::{memory, Dma};
+///
+/// // Safety: addresses and channel count are valid for this chip.
+/// static DMA: Dma<{ memory::CHANNELS }> =
+///     unsafe { Dma::from_base_addresses(memory::EDMA, memory::DMAMUX) }
+///         .with_instance(memory::INSTANCE);
+/// ```
+pub const EDMA: *mut () = 0x400E_8000 as *mut ();
+
+/// The DMA multiplexer's register block
+pub const DMAMUX: *mut () = 0x400E_C000 as *mut ();
+
+/// The number of DMA channels
+pub const CHANNELS: usize = 32;
+
+/// The DMA controller, with 16 interrupt lines shared by channels `n` and `n + 16`
+pub const INSTANCE: crate::Instance = crate::Instance::new(0, 0, 16);
+
+/// A region of the chip's memory map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Region {
+    /// Instruction tightly-coupled memory
+    ///
+    /// The DMA engine can read and write the allocated ITCM. See [`Tcm`].
+    Itcm,
+    /// Boot ROM
+    Rom,
+    /// Data tightly-coupled memory
+    ///
+    /// The DMA engine can read and write the allocated DTCM. See [`Tcm`].
+    Dtcm,
+    /// On-chip RAM, including FlexRAM OCRAM
+    Ocram,
+    /// Peripheral registers on the AIPS buses
+    Peripheral,
+    /// FlexSPI memory, typically the boot flash
+    FlexSpi,
+    /// FlexSPI2 memory
+    FlexSpi2,
+    /// External memory on the SEMC
+    Semc,
+}
+
+/// The memory map, as `(start, end, region)`, with exclusive `end`
+///
+/// See Table 2-1 of the reference manual.
+const MEMORY_MAP: [(usize, usize, Region); 8] = [
+    (0x0000_0000, 0x0008_0000, Region::Itcm),
+    (0x0020_0000, 0x0022_0000, Region::Rom),
+    (0x2000_0000, 0x2008_0000, Region::Dtcm),
+    (0x2020_0000, 0x2030_0000, Region::Ocram),
+    (0x4000_0000, 0x4080_0000, Region::Peripheral),
+    (0x6000_0000, 0x7000_0000, Region::FlexSpi),
+    (0x7000_0000, 0x7F00_0000, Region::FlexSpi2),
+    (0x8000_0000, 0xE000_0000, Region::Semc),
+];
+
+impl Region {
+    /// Returns the region that contains `address`, or `None` if the DMA engine
+    /// can't access the address
+    pub fn of(address: usize) -> Option<Region> {
+        MEMORY_MAP
+            .iter()
+            .find(|(start, end, _)| (*start..*end).contains(&address))
+            .map(|(_, _, region)| *region)
+    }
+
+    /// Returns `true` if the DMA engine can write to this region
+    ///
+    /// The DMA engine can't write to the boot ROM or, through the FlexSPI's
+    /// memory-mapped interface, the boot flash.
+    pub const fn is_writable(self) -> bool {
+        !matches!(self, Region::Rom | Region::FlexSpi)
+    }
+
+    /// Returns `true` if this region is a tightly-coupled memory
+    ///
+    /// The FlexRAM decides how much of a tightly-coupled memory exists. Check
+    /// buffers in these regions with a [`Tcm`].
+    pub const fn is_tcm(self) -> bool {
+        matches!(self, Region::Itcm | Region::Dtcm)
+    }
+
+    /// Returns `true` if the CPU may cache this region
+    ///
+    /// Transfers in cacheable regions need cache maintenance. Tightly-coupled
+    /// memories and peripherals are never cached.
+    pub const fn is_cacheable(self) -> bool {
+        matches!(
+            self,
+            Region::Ocram | Region::FlexSpi | Region::FlexSpi2 | Region::Semc
+        )
+    }
+}
+
+/// Returns the region that contains the entire buffer
+fn region<E>(buffer: &[E]) -> Result<Region, ConfigError> {
+    let start = buffer.as_ptr() as usize;
+    let size = core::mem::size_of_val(buffer);
+    let region = Region::of(start).ok_or(ConfigError::InaccessibleMemory)?;
+    if size > 0 && Region::of(start + size - 1) != Some(region) {
+        return Err(ConfigError::InaccessibleMemory);
+    }
+    Ok(region)
+}
+
+/// Check that the DMA engine can read from `buffer`
+///
+/// Returns the buffer's region, or an error if the DMA engine can't read the
+/// entire buffer.
+pub fn check_source<E>(buffer: &[E]) -> Result<Region, ConfigError> {
+    region(buffer)
+}
+
+/// Check that the DMA engine can write to `buffer`
+///
+/// Returns the buffer's region, or an error if the DMA engine can't write the
+/// entire buffer.
+pub fn check_destination<E>(buffer: &[E]) -> Result<Region, ConfigError> {
+    let region = region(buffer)?;
+    if !region.is_writable() {
+        return Err(ConfigError::ReadOnlyDestination);
+    }
+    Ok(region)
+}
+
+/// The size of a FlexRAM bank, in bytes
+const FLEXRAM_BANK: usize = 32 * 1024;
+
+/// The FlexRAM banks allocated to the tightly-coupled memories
+///
+/// The FlexRAM divides 16 banks between OCRAM, ITCM, and DTCM. A fuse selects the
+/// allocation at reset; firmware may change it through IOMUXC_GPR. Each TCM starts
+/// at its region's base address, and spans its allocated banks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tcm {
+    itcm: usize,
+    dtcm: usize,
+}
+
+impl Tcm {
+    /// The allocation of unfused parts: 128 KiB of ITCM, and 128 KiB of DTCM
+    pub const DEFAULT: Tcm = Tcm::new(128 * 1024, 128 * 1024);
+
+    /// Describe `itcm` bytes of ITCM, and `dtcm` bytes of DTCM
+    pub const fn new(itcm: usize, dtcm: usize) -> Self {
+        Tcm { itcm, dtcm }
+    }
+
+    /// Describe the allocation from the value of IOMUXC_GPR17
+    ///
+    /// Each bank has two bits: `0b10` allocates the bank to DTCM, and `0b11`
+    /// allocates it to ITCM. GPR17 only takes effect if IOMUXC_GPR16 selects it;
+    /// otherwise, the fuses decide the allocation.
+    ///
+    /// ```
+    /// use imxrt_dma::memory::Tcm;
+    ///
+    /// // Banks 0 and 1 are ITCM, bank 2 is DTCM, and the rest are OCRAM.
+    /// let tcm = Tcm::from_bank_config(0x5555_556F);
+    /// assert_eq!(tcm, Tcm::new(64 * 1024, 32 * 1024));
+    /// ```
+    pub const fn from_bank_config(gpr17: u32) -> Self {
+        let (mut itcm, mut dtcm) = (0, 0);
+        let mut bank = 0;
+        while bank < 16 {
+            match (gpr17 >> (2 * bank)) & 0b11 {
+                0b11 => itcm += FLEXRAM_BANK,
+                0b10 => dtcm += FLEXRAM_BANK,
+                _ => {}
+            }
+            bank += 1;
+        }
+        Tcm { itcm, dtcm }
+    }
+
+    /// Returns the size of the ITCM, in bytes
+    pub const fn itcm_len(&self) -> usize {
+        self.itcm
+    }
+
+    /// Returns the size of the DTCM, in bytes
+    pub const fn dtcm_len(&self) -> usize {
+        self.dtcm
+    }
+
+    /// Check that the DMA engine can read from `buffer`
+    ///
+    /// Like [`check_source`], and returns [`ConfigError::UnallocatedTcm`] if the
+    /// buffer is in a tightly-coupled memory, beyond the allocated banks.
+    pub fn check_source<E>(&self, buffer: &[E]) -> Result<Region, ConfigError> {
+        self.check(buffer, check_source(buffer)?)
+    }
+
+    /// Check that the DMA engine can write to `buffer`
+    ///
+    /// Like [`check_destination`], and returns [`ConfigError::UnallocatedTcm`] if
+    /// the buffer is in a tightly-coupled memory, beyond the allocated banks.
+    pub fn check_destination<E>(&self, buffer: &[E]) -> Result<Region, ConfigError> {
+        self.check(buffer, check_destination(buffer)?)
+    }
+
+    /// Check that the buffer in `region` is within its TCM allocation
+    fn check<E>(&self, buffer: &[E], region: Region) -> Result<Region, ConfigError> {
+        let (base, len) = match region {
+            Region::Itcm => (0x0000_0000, self.itcm),
+            Region::Dtcm => (0x2000_0000, self.dtcm),
+            _ => return Ok(region),
+        };
+        let end = buffer.as_ptr() as usize + core::mem::size_of_val(buffer);
+        if end > base + len {
+            return Err(ConfigError::UnallocatedTcm);
+        }
+        Ok(region)
+    }
+}
@@ -0,0 +1,324 @@
+//! Time-slice one DMA channel across multiple streams.
+//!
+//! When there aren't enough DMA channels for every peripheral, a [`Multiplexer`] lets
+//! several logical streams take turns using one hardware channel. Each stream has its
+//! own peripheral and buffer. The multiplexer gives each stream a *slice* of at most a
+//! few elements, then reprograms the channel for the next stream. The multiplexer
+//! visits streams round-robin until all streams are complete.
+//!
+//! Use [`ReadStream`] and [`WriteStream`] to describe peripheral streams. Implement
+//! [`Stream`] for other kinds of streams.
+//!
+//! A stream only transfers data during its slice. Make sure that a peripheral can
+//! tolerate waiting for the other streams' slices; for instance, a receiver needs a
+//! FIFO or a flow control mechanism.
+
+use crate::{
+    channel::{self, Channel, Configuration},
+    interrupt,
+    peripheral::{Destination, Source},
+    ConfigError, Element, Error,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A logical stream that uses a DMA channel for a slice at a time
+///
+/// # Safety
+///
+/// `start` must describe a valid transfer in the channel. Memory referenced by the
+/// transfer must remain valid until `stop`.
+pub unsafe trait Stream {
+    /// Configure `channel` to transfer at most `max` elements
+    ///
+    /// Returns the number of elements described by the slice. Return `0` when the stream
+    /// is complete. The multiplexer enables the channel after `start` returns.
+    fn start(&mut self, channel: &mut Channel, max: usize) -> usize;
+    /// The slice of `elements` elements completed, or was canceled
+    ///
+    /// `completed` is `true` if the DMA channel moved all elements in the slice.
+    fn stop(&mut self, elements: usize, completed: bool);
+}
+
+/// A stream that receives a buffer from a source peripheral
+pub struct ReadStream<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    position: usize,
+}
+
+impl<'a, S, E> ReadStream<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Receive `buffer` from `source`
+    pub fn new(source: &'a mut S, buffer: &'a mut [E]) -> Self {
+        ReadStream {
+            source,
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of elements received
+    pub fn received(&self) -> usize {
+        self.position
+    }
+}
+
+unsafe impl<S, E> Stream for ReadStream<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn start(&mut self, channel: &mut Channel, max: usize) -> usize {
+        let remaining = &mut self.buffer[self.position..];
+        let elements = remaining.len().min(max);
+        if elements == 0 {
+            return 0;
+        }
+        channel.set_channel_configuration(Configuration::enable(self.source.source_signal()));
+        // Safety: hardware source address must be valid, otherwise impl is unsound.
+        // Destination buffer lifetime is captured by the stream. The transfer
+        // iterations ensure that we do not exceed the end of the buffer.
+        unsafe {
+            channel::set_source_hardware(channel, self.source.source_address());
+            channel::set_destination_linear_buffer(channel, &mut remaining[..elements]);
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(elements as u16);
+        }
+        self.source.enable_source();
+        elements
+    }
+    fn stop(&mut self, elements: usize, completed: bool) {
+        self.source.disable_source();
+        if completed {
+            self.position += elements;
+        }
+    }
+}
+
+/// A stream that sends a buffer to a destination peripheral
+pub struct WriteStream<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    destination: &'a mut D,
+    buffer: &'a [E],
+    position: usize,
+}
+
+impl<'a, D, E> WriteStream<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Send `buffer` to `destination`
+    pub fn new(buffer: &'a [E], destination: &'a mut D) -> Self {
+        WriteStream {
+            destination,
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of elements sent
+    pub fn sent(&self) -> usize {
+        self.position
+    }
+}
+
+unsafe impl<D, E> Stream for WriteStream<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    fn start(&mut self, channel: &mut Channel, max: usize) -> usize {
+        let remaining = &self.buffer[self.position..];
+        let elements = remaining.len().min(max);
+        if elements == 0 {
+            return 0;
+        }
+        channel.set_channel_configuration(Configuration::enable(
+            self.destination.destination_signal(),
+        ));
+        // Safety: hardware destination address must be valid, otherwise impl is unsound.
+        // Source buffer lifetime is captured by the stream. The transfer iterations
+        // ensure that we do not exceed the end of the buffer.
+        unsafe {
+            channel::set_source_linear_buffer(channel, &remaining[..elements]);
+            channel::set_destination_hardware(channel, self.destination.destination_address());
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(elements as u16);
+        }
+        self.destination.enable_destination();
+        elements
+    }
+    fn stop(&mut self, elements: usize, completed: bool) {
+        self.destination.disable_destination();
+        if completed {
+            self.position += elements;
+        }
+    }
+}
+
+/// Time-slices a DMA channel across streams
+///
+/// The future resolves when all streams are complete, or when the DMA channel
+/// reports an error. Use [`Multiplexer::new`] to create the future.
+///
+/// # Example
+///
+/// Send to one LPUART, and receive from another LPUART, with one DMA channel.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, multiplex::{Multiplexer, ReadStream, Stream, WriteStream}, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let (mut lpuart2, mut lpuart4) = // Two LPUART peripherals
+///     # (X, X);
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+///
+/// let message = *b"Hello world";
+/// let mut received = [0u8; 16];
+///
+/// let mut write = WriteStream::new(&message, &mut lpuart2);
+/// let mut read = ReadStream::new(&mut lpuart4, &mut received);
+/// let mut streams: [&mut dyn Stream; 2] = [&mut write, &mut read];
+///
+/// // Each stream moves at most four bytes before the next stream's turn.
+/// Multiplexer::new(&mut channel_7, &mut streams, 4).await?;
+/// # Ok(()) }
+/// ```
+pub struct Multiplexer<'a, 's> {
+    channel: &'a mut Channel,
+    streams: &'a mut [&'s mut dyn Stream],
+    slice: usize,
+    /// The stream that's using the channel, and the number of elements in its slice
+    active: Option<(usize, usize)>,
+    /// The next stream to visit
+    next: usize,
+}
+
+impl<'a, 's> Multiplexer<'a, 's> {
+    /// Time-slice `channel` across `streams`, with at most `slice` elements per slice
+    ///
+    /// The multiplexer manages the channel's configuration. It starts the first slice
+    /// when it's first polled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` is zero, or if `slice` is greater than 32767.
+    pub fn new(
+        channel: &'a mut Channel,
+        streams: &'a mut [&'s mut dyn Stream],
+        slice: usize,
+    ) -> Self {
+        assert!(
+            slice > 0 && slice <= 0x7FFF,
+            "Invalid DMA multiplexer slice"
+        );
+        channel.disable();
+        channel.set_disable_on_completion(true);
+        channel.clear_complete();
+        channel.clear_error();
+        Multiplexer {
+            channel,
+            streams,
+            slice,
+            active: None,
+            next: 0,
+        }
+    }
+
+    /// Stop the active slice
+    fn stop(&mut self, completed: bool) {
+        if let Some((index, elements)) = self.active.take() {
+            self.channel.disable();
+            while self.channel.is_active() {}
+            self.channel.clear_complete();
+            self.streams[index].stop(elements, completed);
+        }
+    }
+
+    /// Start the next stream's slice
+    ///
+    /// Returns `false` if all streams are complete, or an error if the channel
+    /// won't enable.
+    fn start(&mut self) -> Result<bool, ConfigError> {
+        for _ in 0..self.streams.len() {
+            let index = self.next;
+            self.next = (self.next + 1) % self.streams.len();
+            let elements = self.streams[index].start(self.channel, self.slice);
+            if elements > 0 {
+                self.active = Some((index, elements));
+                // Safety: stream describes a valid transfer.
+                if let Err(err) = unsafe { self.channel.try_enable() } {
+                    self.stop(false);
+                    return Err(err);
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl Future for Multiplexer<'_, '_> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the multiplexer is not self-referential.
+        let this = unsafe { self.get_unchecked_mut() };
+        interrupt::register_waker(this.channel, cx.waker());
+        loop {
+            if this.channel.is_error() {
+                let es = this.channel.error_status();
+                this.channel.clear_error();
+                this.stop(false);
+                return Poll::Ready(Err(es.into()));
+            } else if this.active.is_none() {
+                if !this.start()? {
+                    return Poll::Ready(Ok(()));
+                }
+            } else if this.channel.is_complete() {
+                this.stop(true);
+            } else {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl Drop for Multiplexer<'_, '_> {
+    fn drop(&mut self) {
+        self.stop(false);
+        self.channel.clear_error();
+        interrupt::clear_waker(self.channel);
+    }
+}
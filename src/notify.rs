@@ -0,0 +1,139 @@
+//! Route channel interrupts to any executor.
+//!
+//! [`Dma::on_interrupt`](crate::Dma::on_interrupt) wakes the task that awaits the
+//! channel's transfer. That serves async tasks, but not an RTIC software task, or
+//! an RTOS thread that blocks on a task notification. Give those channels a
+//! [`Notifier`]. After `on_interrupt` wakes the channel's waker, it calls the
+//! channel's notifier, so one firmware image can mix async tasks and RTOS threads
+//! as DMA consumers.
+//!
+//! Any `Fn(usize)` that's `Sync` is a notifier; it receives the channel number.
+//! Use a function to spawn an RTIC task, or to notify a FreeRTOS task. For bare
+//! polling loops, use a [`Flag`].
+//!
+//! ```no_run
+//! use imxrt_dma::{notify::{Flag, Notifier}, Dma};
+//! static DMA: Dma<32> = // Handle to DMA driver.
+//! # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! // Channel 7 serves a polling loop.
+//! static LPUART2_TX: Flag = Flag::new();
+//! DMA.set_notifier(7, Some(&LPUART2_TX));
+//!
+//! // Channel 8 serves an RTOS thread.
+//! fn notify_thread(channel: usize) {
+//!     // xTaskNotifyFromISR(...);
+//!     # let _ = channel;
+//! }
+//! static LPSPI4_RX: fn(usize) = notify_thread;
+//! DMA.set_notifier(8, Some(&LPSPI4_RX));
+//!
+//! // Simulate channel 7's interrupt.
+//! LPUART2_TX.notify(7);
+//! assert!(LPUART2_TX.take());
+//! assert!(!LPUART2_TX.take());
+//! ```
+//!
+//! A notifier runs in the interrupt handler, with the channel's interrupt already
+//! cleared. It should only signal its consumer. The consumer inspects the channel,
+//! and handles the transfer's completion or error.
+
+use core::{
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{sync::Mutex, ConfigError, Dma};
+
+/// Signals a channel's consumer from the channel's interrupt handler
+///
+/// See the [module documentation](crate::notify) for more information.
+pub trait Notifier: Sync {
+    /// Signal that the channel identified by `channel` completed, or reported an
+    /// error
+    fn notify(&self, channel: usize);
+}
+
+impl<F: Fn(usize) + Sync> Notifier for F {
+    fn notify(&self, channel: usize) {
+        self(channel)
+    }
+}
+
+/// A notifier that sets a flag
+///
+/// Poll the flag with [`take`](Self::take).
+#[derive(Debug, Default)]
+pub struct Flag(AtomicBool);
+
+impl Flag {
+    /// Create a flag that isn't set
+    pub const fn new() -> Self {
+        Flag(AtomicBool::new(false))
+    }
+
+    /// Returns `true` if the flag is set
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Clear the flag, and return `true` if it was set
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Notifier for Flag {
+    fn notify(&self, _: usize) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+pub(crate) type SharedNotifier = Mutex<Cell<Option<&'static dyn Notifier>>>;
+#[allow(clippy::declare_interior_mutable_const)] // Very convenient, and usage for static init deemed OK in clippy docs
+pub(crate) const NO_NOTIFIER: SharedNotifier = Mutex::new(Cell::new(None));
+
+impl<const CHANNELS: usize> Dma<CHANNELS> {
+    /// Route the interrupts of the channel identified by `channel` to `notifier`
+    ///
+    /// Use `None` to remove the channel's notifier. The channel's waker is
+    /// unaffected; [`on_interrupt`](Self::on_interrupt) and [`on_error`](Self::on_error)
+    /// wake the waker, then call the notifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than or equal to the maximum number of channels.
+    /// See [`try_set_notifier`](Self::try_set_notifier) for a non-panicking version.
+    pub fn set_notifier(&self, channel: usize, notifier: Option<&'static dyn Notifier>) {
+        self.try_set_notifier(channel, notifier)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Route the interrupts of the channel identified by `channel` to `notifier`,
+    /// or return an error if the channel is invalid
+    ///
+    /// See [`set_notifier`](Self::set_notifier) for more information.
+    pub fn try_set_notifier(
+        &self,
+        channel: usize,
+        notifier: Option<&'static dyn Notifier>,
+    ) -> Result<(), ConfigError> {
+        let shared = self
+            .notifiers
+            .get(channel)
+            .ok_or(ConfigError::InvalidChannel)?;
+        shared.lock(|shared| shared.set(notifier));
+        Ok(())
+    }
+
+    /// Call the notifier of the channel identified by `channel`, if it has one
+    pub(crate) fn notify(&self, channel: usize) {
+        let notifier = self
+            .notifiers
+            .get(channel)
+            .and_then(|shared| shared.lock(Cell::get));
+        if let Some(notifier) = notifier {
+            notifier.notify(channel);
+        }
+    }
+}
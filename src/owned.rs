@@ -0,0 +1,377 @@
+//! Peripheral transfers with owned buffers.
+//!
+//! The futures in this module take ownership of their buffer, and return it once the
+//! transfer completes. The buffer doesn't need to outlive the future, since the
+//! future releases it if it's canceled. A buffer is any [`Buffer`]:
+//!
+//! - a static buffer declared with [`dma_buffer!`](crate::dma_buffer).
+//! - a `Box<[E]>`, or a `Vec<E>`, with the `alloc` feature.
+//!
+//! ```no_run
+//! use imxrt_dma::{dma_buffer, owned, channel::Channel};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct X;
+//! # unsafe impl imxrt_dma::peripheral::Source<u8> for X {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u8 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//! dma_buffer! {
+//!     /// Receives LPUART data.
+//!     struct RxBuffer([u8; 32]) = [0; 32];
+//! }
+//!
+//! # async fn f() -> imxrt_dma::Result<()> {
+//! let mut lpuart = // A LPUART peripheral
+//!     # X;
+//! let mut channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! let buffer = RxBuffer::take().unwrap();
+//! let (buffer, result) = owned::read(&mut channel_7, &mut lpuart, buffer).await;
+//! result?;
+//! let received: &[u8; 32] = &buffer;
+//! # Ok(()) }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::UnsafeCell,
+    future::Future,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    pin::Pin,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+};
+
+use crate::{
+    channel::Channel,
+    peripheral::{self, Destination, Source},
+    Element, Error,
+};
+
+/// A buffer that a transfer may own
+///
+/// # Safety
+///
+/// The slice returned by [`into_raw`](Buffer::into_raw) must be valid for reads and
+/// writes, and must not move, until it's passed to [`from_raw`](Buffer::from_raw).
+/// Nothing else may access the slice in the meantime.
+pub unsafe trait Buffer<E>: Sized {
+    /// Release the buffer's elements
+    fn into_raw(self) -> NonNull<[E]>;
+    /// Reclaim the buffer from its elements
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from this type's `into_raw`, and it may only be reclaimed
+    /// once.
+    unsafe fn from_raw(raw: NonNull<[E]>) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<E> Buffer<E> for Box<[E]> {
+    fn into_raw(self) -> NonNull<[E]> {
+        NonNull::from(Box::leak(self))
+    }
+    unsafe fn from_raw(raw: NonNull<[E]>) -> Self {
+        Box::from_raw(raw.as_ptr())
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<E> Buffer<E> for Vec<E> {
+    fn into_raw(self) -> NonNull<[E]> {
+        self.into_boxed_slice().into_raw()
+    }
+    unsafe fn from_raw(raw: NonNull<[E]>) -> Self {
+        Box::from_raw(raw.as_ptr()).into_vec()
+    }
+}
+
+/// Storage for a [`dma_buffer!`](crate::dma_buffer)
+#[doc(hidden)]
+pub struct Storage<T> {
+    buffer: UnsafeCell<T>,
+    taken: AtomicBool,
+}
+
+// Safety: the buffer is only accessed through its token, and there's only
+// one token.
+unsafe impl<T: Send> Sync for Storage<T> {}
+
+impl<T> Storage<T> {
+    pub const fn new(buffer: T) -> Self {
+        Storage {
+            buffer: UnsafeCell::new(buffer),
+            taken: AtomicBool::new(false),
+        }
+    }
+    /// Returns `true` the first time it's called
+    pub fn take(&self) -> bool {
+        !self.taken.swap(true, Ordering::AcqRel)
+    }
+    pub fn get(&self) -> *mut T {
+        self.buffer.get()
+    }
+}
+
+/// Declare a static DMA buffer, and a token that owns it
+///
+/// `dma_buffer!` declares a zero-sized type that owns a static array. Use the
+/// type's `take()` function to acquire the token. `take()` returns `None` after
+/// the first call, so the program has at most one token. The token dereferences
+/// to the array, and it's a [`Buffer`](crate::owned::Buffer) for the
+/// [`owned`](crate::owned) transfers.
+///
+/// ```
+/// use imxrt_dma::dma_buffer;
+///
+/// dma_buffer! {
+///     /// Samples for the DAC.
+///     pub struct Samples([u16; 64]) = [0; 64];
+/// }
+///
+/// let mut samples = Samples::take().unwrap();
+/// samples[0] = 0x7FF;
+/// assert!(Samples::take().is_none());
+/// assert_eq!(core::mem::size_of_val(&samples), 0);
+/// ```
+#[macro_export]
+macro_rules! dma_buffer {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident([$elem:ty; $len:expr]) = $init:expr;
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            _private: (),
+        }
+
+        impl $name {
+            fn storage() -> &'static $crate::owned::Storage<[$elem; $len]> {
+                static STORAGE: $crate::owned::Storage<[$elem; $len]> =
+                    $crate::owned::Storage::new($init);
+                &STORAGE
+            }
+
+            /// Take the token that owns the buffer
+            ///
+            /// Returns `None` if the token was already taken.
+            #[allow(dead_code)]
+            $vis fn take() -> ::core::option::Option<Self> {
+                if Self::storage().take() {
+                    ::core::option::Option::Some($name { _private: () })
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = [$elem; $len];
+            fn deref(&self) -> &Self::Target {
+                // Safety: the token is unique, and it's borrowed.
+                unsafe { &*Self::storage().get() }
+            }
+        }
+
+        impl ::core::ops::DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                // Safety: the token is unique, and it's mutably borrowed.
+                unsafe { &mut *Self::storage().get() }
+            }
+        }
+
+        // Safety: the storage is static, and only the token accesses it. The
+        // token is consumed while the slice is released.
+        unsafe impl $crate::owned::Buffer<$elem> for $name {
+            fn into_raw(self) -> ::core::ptr::NonNull<[$elem]> {
+                let buffer: *mut [$elem] = Self::storage().get();
+                // Safety: a static's address isn't null.
+                unsafe { ::core::ptr::NonNull::new_unchecked(buffer) }
+            }
+            unsafe fn from_raw(_: ::core::ptr::NonNull<[$elem]>) -> Self {
+                $name { _private: () }
+            }
+        }
+    };
+}
+
+/// A DMA transfer that receives data from hardware into an owned buffer
+///
+/// The future resolves with the buffer, and the transfer's result. Use
+/// [`read()`] to construct this future.
+pub struct Read<'a, S, E, B>
+where
+    S: Source<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    read: ManuallyDrop<peripheral::Read<'a, S, E>>,
+    buffer: Option<NonNull<[E]>>,
+    _buffer: PhantomData<B>,
+}
+
+// Safety: the future owns the buffer.
+unsafe impl<'a, S, E, B> Send for Read<'a, S, E, B>
+where
+    S: Source<E>,
+    E: Element + Send,
+    B: Buffer<E> + Send,
+    peripheral::Read<'a, S, E>: Send,
+{
+}
+
+impl<S, E, B> Future for Read<'_, S, E, B>
+where
+    S: Source<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    type Output = (B, Result<(), Error>);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the read future isn't moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        let result = match unsafe { Pin::new_unchecked(&mut *this.read) }.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let buffer = this.buffer.take().expect("polled after completion");
+        // Safety: the transfer is complete, so the DMA engine no longer
+        // references the buffer. The pointer came from the buffer.
+        Poll::Ready((unsafe { B::from_raw(buffer) }, result))
+    }
+}
+
+impl<S, E, B> Drop for Read<'_, S, E, B>
+where
+    S: Source<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    fn drop(&mut self) {
+        // Safety: dropped once. This cancels the transfer before the
+        // buffer is freed.
+        unsafe { ManuallyDrop::drop(&mut self.read) };
+        if let Some(buffer) = self.buffer.take() {
+            // Safety: the pointer came from the buffer.
+            drop(unsafe { B::from_raw(buffer) });
+        }
+    }
+}
+
+/// Use a DMA channel to receive an owned `buffer` of elements from the source peripheral
+///
+/// See [`peripheral::read`] for more information.
+pub fn read<'a, S, E, B>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: B,
+) -> Read<'a, S, E, B>
+where
+    S: Source<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    let buffer = buffer.into_raw();
+    // Safety: the future frees the buffer only after the transfer completes, or
+    // after the transfer is canceled.
+    let read = peripheral::read(channel, source, unsafe { &mut *buffer.as_ptr() });
+    Read {
+        read: ManuallyDrop::new(read),
+        buffer: Some(buffer),
+        _buffer: PhantomData,
+    }
+}
+
+/// A DMA transfer that sends an owned buffer to hardware
+///
+/// The future resolves with the buffer, and the transfer's result. Use
+/// [`write()`] to construct this future.
+pub struct Write<'a, D, E, B>
+where
+    D: Destination<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    write: ManuallyDrop<peripheral::Write<'a, D, E>>,
+    buffer: Option<NonNull<[E]>>,
+    _buffer: PhantomData<B>,
+}
+
+// Safety: the future owns the buffer.
+unsafe impl<'a, D, E, B> Send for Write<'a, D, E, B>
+where
+    D: Destination<E>,
+    E: Element + Send,
+    B: Buffer<E> + Send,
+    peripheral::Write<'a, D, E>: Send,
+{
+}
+
+impl<D, E, B> Future for Write<'_, D, E, B>
+where
+    D: Destination<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    type Output = (B, Result<(), Error>);
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the write future isn't moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        let result = match unsafe { Pin::new_unchecked(&mut *this.write) }.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let buffer = this.buffer.take().expect("polled after completion");
+        // Safety: the transfer is complete, so the DMA engine no longer
+        // references the buffer. The pointer came from the buffer.
+        Poll::Ready((unsafe { B::from_raw(buffer) }, result))
+    }
+}
+
+impl<D, E, B> Drop for Write<'_, D, E, B>
+where
+    D: Destination<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    fn drop(&mut self) {
+        // Safety: dropped once. This cancels the transfer before the
+        // buffer is freed.
+        unsafe { ManuallyDrop::drop(&mut self.write) };
+        if let Some(buffer) = self.buffer.take() {
+            // Safety: the pointer came from the buffer.
+            drop(unsafe { B::from_raw(buffer) });
+        }
+    }
+}
+
+/// Use a DMA channel to send an owned `buffer` of elements to the destination peripheral
+///
+/// See [`peripheral::write`] for more information.
+pub fn write<'a, D, E, B>(
+    channel: &'a mut Channel,
+    buffer: B,
+    destination: &'a mut D,
+) -> Write<'a, D, E, B>
+where
+    D: Destination<E>,
+    E: Element,
+    B: Buffer<E>,
+{
+    let buffer = buffer.into_raw();
+    // Safety: the future frees the buffer only after the transfer completes, or
+    // after the transfer is canceled.
+    let write = peripheral::write(channel, unsafe { &*buffer.as_ptr() }, destination);
+    Write {
+        write: ManuallyDrop::new(write),
+        buffer: Some(buffer),
+        _buffer: PhantomData,
+    }
+}
@@ -0,0 +1,364 @@
+//! Timer-paced transfers to memory-mapped registers.
+//!
+//! Some protocols don't have a DMA-capable peripheral. Instead, you bit-bang the
+//! protocol by writing values, at precise times, into registers. Examples include
+//! GPIO data, set, clear, and toggle registers, and FlexIO shifter buffers driven
+//! without a FlexIO DMA request.
+//!
+//! A paced transfer uses a PIT timer to request DMA service. The DMA channel writes
+//! one element into the register for each PIT timer period. Since the PIT timers
+//! correspond to the first four DMA channels, paced transfers only work on DMA
+//! channels 0 through 3. You're responsible for configuring and starting the PIT
+//! timer that corresponds to the DMA channel.
+//!
+//! Use [`write_register`] to write a buffer into a register. Use [`copy`] to copy
+//! between two memory buffers, a few elements at a time. Use [`write_registers`]
+//! to play back a script of writes into a small set of registers. A script may be
+//! paced by a PIT timer, or by any other DMA request.
+//!
+//! # Building protocols
+//!
+//! Combine paced transfers with channel-to-channel links to describe more complex
+//! signals. For instance, a WS2812 driver might pace one channel with a PIT timer,
+//! and use [`set_minor_loop_link`](crate::channel::Channel::set_minor_loop_link) to
+//! link to other channels that write the GPIO set and clear registers within the same
+//! bit period. [`set_major_loop_link`](crate::channel::Channel::set_major_loop_link)
+//! starts another channel after a paced transfer completes, so you can chain transfers
+//! back-to-back, like a data frame followed by a reset period.
+
+use crate::{
+    channel::{self, Channel, Configuration, MinorLoopOffset},
+    interrupt::Transfer,
+    ConfigError, Element, Error,
+};
+
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Describes what paces a [`write_registers`] script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trigger {
+    /// The PIT timer that corresponds to the DMA channel
+    ///
+    /// Only the first four DMA channels support this trigger.
+    Timer,
+    /// A DMA request signal, like a peripheral's request
+    ///
+    /// See Table 4-3 of the reference manual.
+    Request(u32),
+}
+
+/// A paced transfer
+///
+/// The future resolves when the DMA channel has written all elements into the
+/// destination. Use [`write_register`], [`copy`], or [`write_registers`] to
+/// construct this future.
+pub struct Paced<'a, E: Element> {
+    channel: &'a Channel,
+    transfer: Transfer<'a>,
+    _elem: PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Write each element of `buffer` into `register`, one element per PIT timer period
+///
+/// The DMA channel uses the PIT timer that corresponds to the channel. Configure and
+/// start the timer before, or after, awaiting the future. The timer period is the time
+/// between each write into the register.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Safety
+///
+/// `register` must be a valid address for the DMA engine to write elements of type `E`.
+/// Writes to `register` must not violate the invariants of any other driver.
+///
+/// # Panics
+///
+/// Panics if the DMA channel is not one of the first four DMA channels. Panics if
+/// the buffer has more than 32767 elements. An empty buffer completes on the
+/// future's first poll, without touching the channel.
+///
+/// # Example
+///
+/// Toggle a GPIO pin once every PIT timer period.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, paced};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_2: Channel = // DMA channel 2, paced by PIT timer 2
+///     # unsafe { DMA.channel(2) };
+///
+/// const GPIO2_DR_TOGGLE: *const u32 = 0x401B_C08C as _;
+/// const PIN: u32 = 1 << 3;
+/// let toggles = [PIN; 16];
+///
+/// // TODO configure and start PIT timer 2!
+///
+/// // Safety: the toggle register is valid, and we own this GPIO pin.
+/// unsafe { paced::write_register(&mut channel_2, &toggles, GPIO2_DR_TOGGLE) }.await?;
+/// # Ok(()) }
+/// ```
+pub unsafe fn write_register<'a, E: Element>(
+    channel: &'a mut Channel,
+    buffer: &'a [E],
+    register: *const E,
+) -> Paced<'a, E> {
+    assert!(buffer.len() <= 0x7FFF, "DMA paced buffer is too large");
+    check_periodic(channel);
+    if buffer.is_empty() {
+        return empty(channel);
+    }
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::AlwaysOnPeriodic);
+    // Safety: caller ensures that the register is valid. Source buffer lifetime
+    // captured by future. One element per iteration, so we do not exceed the
+    // end of the source.
+    channel::set_source_linear_buffer(channel, buffer);
+    channel::set_destination_hardware(channel, register);
+    channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+    channel.set_transfer_iterations(buffer.len() as u16);
+
+    Paced {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: Transfer::new(channel),
+        _elem: PhantomData,
+    }
+}
+
+/// Copy `chunk` elements from `source` into `destination` for every PIT timer period
+///
+/// Copies the minimum number of elements between the two buffers. If the number of
+/// elements isn't a multiple of `chunk`, the transfer leaves off the remainder.
+/// The DMA channel uses the PIT timer that corresponds to the channel. For example,
+/// a 10us PIT timer period with a `chunk` of four copies four elements every 10us.
+///
+/// The destination may be memory that another peripheral reads, like a DAC's
+/// data buffer, or a set of shadow registers with consecutive addresses.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Panics
+///
+/// Panics if the DMA channel is not one of the first four DMA channels. Panics if
+/// `chunk` is zero, if no chunks fit in non-empty buffers, if there are more than 32767
+/// chunks, or if the chunk size cannot be described by a minor loop. If either buffer
+/// is empty, the transfer completes on the future's first poll.
+///
+/// # Example
+///
+/// Copy two samples every PIT timer period.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, paced};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_0: Channel = // DMA channel 0, paced by PIT timer 0
+///     # unsafe { DMA.channel(0) };
+///
+/// let samples = [1u16, 2, 3, 4, 5, 6];
+/// let mut output = [0u16; 6];
+///
+/// // TODO configure and start PIT timer 0!
+///
+/// paced::copy(&mut channel_0, &samples, &mut output, 2).await?;
+/// # Ok(()) }
+/// ```
+pub fn copy<'a, E: Element>(
+    channel: &'a mut Channel,
+    source: &'a [E],
+    destination: &'a mut [E],
+    chunk: usize,
+) -> Paced<'a, E> {
+    assert!(chunk > 0, "DMA paced chunk is empty");
+    check_periodic(channel);
+    if source.is_empty() || destination.is_empty() {
+        return empty(channel);
+    }
+    let chunks = source.len().min(destination.len()) / chunk;
+    assert!(chunks > 0, "DMA paced buffers are smaller than a chunk");
+    assert!(chunks <= 0x7FFF, "DMA paced buffers have too many chunks");
+    let nbytes = chunk
+        .checked_mul(core::mem::size_of::<E>())
+        .and_then(|nbytes| u32::try_from(nbytes).ok())
+        .expect("DMA paced chunk is too large");
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::AlwaysOnPeriodic);
+    // Safety: buffer lifetimes captured by future. The minor loops and transfer
+    // iterations ensure that we do not exceed the end of either buffer.
+    unsafe {
+        channel::set_source_linear_buffer(channel, source);
+        channel::set_destination_linear_buffer(channel, destination);
+        channel.set_minor_loop_bytes(nbytes);
+        channel.set_transfer_iterations(chunks as u16);
+    }
+
+    Paced {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        _elem: PhantomData,
+    }
+}
+
+/// Play back a script of writes into `count` consecutive registers
+///
+/// `registers` is the address of the first register. For each trigger, the DMA channel
+/// writes the next `count` values of the script into the registers, in address
+/// order. The future resolves when the DMA channel writes the last value.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Safety
+///
+/// `registers` must be the address of `count` consecutive registers, and each must
+/// be valid for the DMA engine to write elements of type `E`. Writes to the registers
+/// must not violate the invariants of any other driver.
+///
+/// # Panics
+///
+/// Panics if `count` is zero, if the script length is not a multiple of `count`,
+/// or if there are more than 32767 triggers in the script. Panics if the registers
+/// span more than 1023 bytes. Panics if the trigger is [`Timer`](Trigger::Timer), and
+/// the DMA channel is not one of the first four DMA channels. An empty script
+/// completes on the future's first poll.
+///
+/// # Example
+///
+/// Reconfigure two consecutive registers on every trigger from DMA request 42.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, paced};
+///
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// const REGISTERS: *const u32 = 0x4000_0000 as _;
+/// let script = [
+///     0x01, 0x10, // First trigger
+///     0x02, 0x20, // Second trigger
+///     0x03, 0x30, // Third trigger
+/// ];
+///
+/// // Safety: the registers are valid, and no other driver uses them.
+/// unsafe {
+///     paced::write_registers(&mut channel_7, &script, REGISTERS, 2, paced::Trigger::Request(42))
+/// }
+/// .await?;
+/// # Ok(()) }
+/// ```
+pub unsafe fn write_registers<'a, E: Element>(
+    channel: &'a mut Channel,
+    script: &'a [E],
+    registers: *const E,
+    count: usize,
+    trigger: Trigger,
+) -> Paced<'a, E> {
+    assert!(
+        count > 0 && script.len().is_multiple_of(count),
+        "Invalid DMA register script"
+    );
+    let triggers = script.len() / count;
+    assert!(triggers <= 0x7FFF, "DMA register script is too large");
+    let size = core::mem::size_of::<E>();
+    let nbytes = count
+        .checked_mul(size)
+        .filter(|&nbytes| nbytes <= 0x3FF)
+        .expect("DMA register script has too many registers") as i32;
+    if trigger == Trigger::Timer {
+        check_periodic(channel);
+    }
+    if script.is_empty() {
+        return empty(channel);
+    }
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(match trigger {
+        Trigger::Timer => Configuration::AlwaysOnPeriodic,
+        Trigger::Request(signal) => Configuration::enable(signal),
+    });
+    // Safety: caller ensures that the registers are valid. Script lifetime
+    // captured by future. Each minor loop writes all registers, then returns
+    // to the first register. The minor loops and transfer iterations ensure
+    // that we do not exceed the end of the script.
+    channel::set_source_linear_buffer(channel, script);
+    channel.set_destination_address(registers);
+    channel.set_destination_attributes::<E>(0);
+    if count == 1 {
+        channel.set_destination_offset(0);
+        channel.set_destination_last_address_adjustment(0);
+        channel.set_minor_loop_bytes(nbytes as u32);
+    } else {
+        channel.set_destination_offset(size as i16);
+        channel.set_destination_last_address_adjustment(-nbytes);
+        channel
+            .set_minor_loop_bytes_with_offset(nbytes as u32, MinorLoopOffset::Destination(-nbytes));
+    }
+    channel.set_transfer_iterations(triggers as u16);
+
+    Paced {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: Transfer::new(channel),
+        _elem: PhantomData,
+    }
+}
+
+/// Panics if the channel can't be paced by its PIT timer
+///
+/// Paced transfers check the channel before they check for an empty buffer, so
+/// that an empty buffer doesn't hide a channel that can't run the transfer.
+fn check_periodic(channel: &Channel) {
+    if !channel.supports_periodic_trigger() {
+        panic!("{}", ConfigError::PeriodicUnsupported);
+    }
+}
+
+/// A paced transfer that moves no elements, and never touches the channel
+fn empty<'a, E: Element>(channel: &'a mut Channel) -> Paced<'a, E> {
+    Paced {
+        channel,
+        transfer: Transfer::empty(channel),
+        _elem: PhantomData,
+    }
+}
+
+impl<E: Element> Future for Paced<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: no movement from transfer future...
+        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+    }
+}
+
+impl<E: Element> Drop for Paced<'_, E> {
+    fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
+        // The trigger keeps signaling, so don't wait for the signal
+        // to clear. Instead, stop servicing requests, and wait for any
+        // in-progress write.
+        self.channel.disable();
+        while self.channel.is_active() {}
+        // Drop `transfer` to finish cancellation...
+    }
+}
@@ -6,18 +6,67 @@
 //! Each future documents when it resolves. To wake the executor, you can
 //! route the DMA channel's interrupt handler to [`on_interrupt()`](crate::Dma::on_interrupt).
 //! Otherwise, you can poll the future in a loop.
+//!
+//! A transfer with an empty buffer completes on its first poll. It never touches
+//! the DMA channel or the peripheral.
+//!
+//! A transfer's buffer holds the peripheral's elements. Pass a slice, an array,
+//! or any buffer that views as a slice of elements, like a cache-[`Aligned`](crate::cache::Aligned)
+//! array. The buffer and the peripheral must agree on the element type:
+//!
+//! ```
+//! use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use imxrt_dma::{cache::Aligned, peripheral};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct Lpspi;
+//! # unsafe impl peripheral::Source<u16> for Lpspi {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u16 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//! # let mut channel = unsafe { DMA.channel(7) };
+//!
+//! let mut lpspi = // A Source<u16>
+//!     # Lpspi;
+//! let mut buffer = Aligned([0u16; 0]);
+//! let read = pin!(peripheral::read(&mut channel, &mut lpspi, &mut buffer));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(read.poll(&mut cx), Poll::Ready(Ok(())));
+//! ```
+//!
+//! Pairing a `u16` peripheral with a `u8` buffer doesn't build.
+//!
+//! ```compile_fail
+//! # use imxrt_dma::{cache::Aligned, peripheral};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct Lpspi;
+//! # unsafe impl peripheral::Source<u16> for Lpspi {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u16 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//! # let mut channel = unsafe { DMA.channel(7) };
+//! # let mut lpspi = Lpspi;
+//! let mut buffer = Aligned([0u8; 32]);
+//! let read = peripheral::read(&mut channel, &mut lpspi, &mut buffer);
+//! ```
+//!
+//! To move elements of different sizes, use [`read_packed`] and [`write_unpacked`].
+//! To receive a frame of unknown length, use [`read_until`].
 
+use super::Element;
+#[cfg(feature = "async")]
 use super::{
+    cache,
     channel::{self, Channel, Configuration},
-    Element, Error, Transfer,
+    Error, Transfer,
 };
 
-use core::{
-    future::Future,
-    marker::PhantomData,
-    pin::Pin,
-    task::{Context, Poll},
-};
+use core::task::{Context, Poll};
+#[cfg(feature = "async")]
+use core::{future::Future, marker::PhantomData, pin::Pin, ptr::NonNull};
 
 /// A peripheral that can be the source of DMA data
 ///
@@ -34,7 +83,8 @@ pub unsafe trait Source<E: Element> {
     /// Peripheral source request signal
     ///
     /// See Table 4-3 of the reference manual. A source may
-    /// has a qualifier like 'receive' in the name.
+    /// has a qualifier like 'receive' in the name. With a chip feature,
+    /// return a [`Signal`](crate::signal)'s `raw()` value.
     fn source_signal(&self) -> u32;
     /// Returns a pointer to the register from which the DMA channel
     /// reads data
@@ -55,6 +105,32 @@ pub unsafe trait Source<E: Element> {
     ///
     /// This may include undoing the actions in `enable_source`.
     fn disable_source(&mut self);
+    /// Poll for the peripheral to finish a transfer after the DMA channel completes
+    ///
+    /// Transfer futures poll this method once the DMA channel has moved all data,
+    /// and they resolve once it returns `Poll::Ready`. Use this to wait for the
+    /// peripheral to finish any work that follows the final DMA request. If this
+    /// returns `Poll::Pending`, arrange for the waker in `cx` to be woken, or
+    /// expect the executor to poll the future again.
+    ///
+    /// A future may poll this method again after it returns `Poll::Ready`. The
+    /// default implementation is immediately ready.
+    fn complete_source(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let _ = cx;
+        Poll::Ready(())
+    }
+    /// The number of elements to read for each DMA request
+    ///
+    /// Return the peripheral's receive FIFO watermark, if the peripheral requests
+    /// service once the FIFO holds that many elements. Transfers read a burst of
+    /// elements per request, so there are fewer requests. A transfer only uses
+    /// the burst if it evenly divides the buffer; otherwise, it reads one element
+    /// per request, so it never reads past the data that the peripheral signaled.
+    ///
+    /// The default implementation returns 1.
+    fn source_burst(&self) -> usize {
+        1
+    }
 }
 
 /// A peripheral that can be the destination for DMA data
@@ -72,7 +148,8 @@ pub unsafe trait Destination<E: Element> {
     /// Peripheral destination request signal
     ///
     /// See Table 4-3 of the reference manual. A destination mave
-    /// has a qualifier like 'transfer' in the name.
+    /// has a qualifier like 'transfer' in the name. With a chip feature,
+    /// return a [`Signal`](crate::signal)'s `raw()` value.
     fn destination_signal(&self) -> u32;
     /// Returns a pointer to the register into which the DMA channel
     /// writes data
@@ -90,13 +167,344 @@ pub unsafe trait Destination<E: Element> {
     ///
     /// This may include undoing the actions in `enable_destination`.
     fn disable_destination(&mut self);
+    /// Poll for the peripheral to finish a transfer after the DMA channel completes
+    ///
+    /// Transfer futures poll this method once the DMA channel has moved all data,
+    /// and they resolve once it returns `Poll::Ready`. Use this to wait until the
+    /// data is actually on the wire; for instance, wait for a transmit FIFO to
+    /// drain, or for a UART's transmit complete flag. If this returns
+    /// `Poll::Pending`, arrange for the waker in `cx` to be woken, or expect the
+    /// executor to poll the future again.
+    ///
+    /// [`write()`], [`write_unpacked`], [`full_duplex`], [`bridge`](crate::bridge::bridge),
+    /// and the [`owned`](crate::owned) writes poll this method, so awaiting any of them
+    /// means that the data left the peripheral. Streams that never complete, like
+    /// [`double_buffer`](crate::double_buffer), don't poll this method.
+    ///
+    /// A future may poll this method again after it returns `Poll::Ready`. The
+    /// default implementation is immediately ready.
+    fn complete_destination(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let _ = cx;
+        Poll::Ready(())
+    }
+    /// The number of elements to write for each DMA request
+    ///
+    /// Return the free space that the peripheral's transmit FIFO guarantees when
+    /// it requests service. Transfers write a burst of elements per request, so
+    /// there are fewer requests. A transfer only uses the burst if it evenly
+    /// divides the buffer; otherwise, it writes one element per request, so it
+    /// never overflows the FIFO.
+    ///
+    /// The default implementation returns 1.
+    fn destination_burst(&self) -> usize {
+        1
+    }
+}
+
+/// A FIFO-fronted peripheral register that receives DMA data
+///
+/// Many transmitters look the same to the DMA engine: a data register, a request
+/// signal, and a FIFO that requests service when its fill level drops to a
+/// watermark. `FifoDestination` describes that peripheral, so a HAL doesn't need
+/// its own [`Destination`] implementation. When the FIFO requests service, it has
+/// room for at least `depth - watermark` elements, so each DMA request writes a
+/// burst of that many elements.
+///
+/// ```
+/// use imxrt_dma::peripheral::{Destination, FifoDestination};
+///
+/// // Safety: the LPUART2 DATA register is valid for DMA writes.
+/// let lpuart2_tx = unsafe { FifoDestination::<u8>::new(0x4018_401C as *const u8, 67, 4, 1) };
+/// assert_eq!(lpuart2_tx.destination_burst(), 3);
+/// ```
+///
+/// By default, enabling and disabling the destination does nothing; the
+/// peripheral must already request DMA service. To enable and disable the
+/// peripheral's DMA requests with each transfer, set a
+/// [request control](Self::with_request_control).
+pub struct FifoDestination<E> {
+    address: *const E,
+    signal: u32,
+    depth: usize,
+    watermark: usize,
+    control: Option<fn(bool)>,
+}
+
+impl<E: Element> FifoDestination<E> {
+    /// Describe the FIFO behind `address`, which requests service with `signal`
+    ///
+    /// `depth` is the number of elements in the FIFO. The FIFO requests service
+    /// when it holds `watermark` elements, or fewer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `watermark` isn't less than `depth`.
+    ///
+    /// # Safety
+    ///
+    /// `address` must be a peripheral register that's valid for DMA writes of `E`,
+    /// for as long as the DMA channel uses the destination.
+    pub const unsafe fn new(
+        address: *const E,
+        signal: u32,
+        depth: usize,
+        watermark: usize,
+    ) -> Self {
+        assert!(
+            watermark < depth,
+            "FIFO watermark must be less than its depth"
+        );
+        FifoDestination {
+            address,
+            signal,
+            depth,
+            watermark,
+            control: None,
+        }
+    }
+
+    /// Enable and disable the peripheral's DMA requests with `control`
+    ///
+    /// Enabling the destination calls `control(true)`. Disabling the destination
+    /// calls `control(false)`.
+    pub const fn with_request_control(mut self, control: fn(bool)) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Returns the number of elements in the FIFO
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the fill level at which the FIFO requests service
+    pub const fn watermark(&self) -> usize {
+        self.watermark
+    }
+}
+
+// Safety: the destination only holds the address of a peripheral register.
+unsafe impl<E> Send for FifoDestination<E> {}
+
+// Safety: caller of `new` guarantees that the address is valid for DMA writes.
+unsafe impl<E: Element> Destination<E> for FifoDestination<E> {
+    fn destination_signal(&self) -> u32 {
+        self.signal
+    }
+    fn destination_address(&self) -> *const E {
+        self.address
+    }
+    fn enable_destination(&mut self) {
+        if let Some(control) = self.control {
+            control(true);
+        }
+    }
+    fn disable_destination(&mut self) {
+        if let Some(control) = self.control {
+            control(false);
+        }
+    }
+    fn destination_burst(&self) -> usize {
+        self.depth - self.watermark
+    }
+}
+
+/// A peripheral register that the DMA engine can read
+///
+/// Implemented for the RAL's read-only and read-write registers. See
+/// [`RegisterSource`].
+pub trait ReadableRegister: register::Sealed {}
+
+/// A peripheral register that the DMA engine can write
+///
+/// Implemented for the RAL's write-only and read-write registers. See
+/// [`RegisterDestination`].
+pub trait WritableRegister: register::Sealed {}
+
+mod register {
+    use ral_registers::{RORegister, RWRegister, WORegister};
+
+    pub trait Sealed {}
+
+    impl Sealed for RORegister<u32> {}
+    impl Sealed for RWRegister<u32> {}
+    impl Sealed for WORegister<u32> {}
+
+    impl super::ReadableRegister for RORegister<u32> {}
+    impl super::ReadableRegister for RWRegister<u32> {}
+    impl super::WritableRegister for RWRegister<u32> {}
+    impl super::WritableRegister for WORegister<u32> {}
+}
+
+/// Returns the address of the low `E` in a 32-bit `register`
+///
+/// The build fails if `E` is wider than the register.
+pub(crate) fn register_address<R, E: Element>(register: &R) -> *const E {
+    const {
+        assert!(
+            core::mem::size_of::<E>() <= 4,
+            "Element is wider than the register"
+        )
+    };
+    // The low bytes of a little-endian register are at its address.
+    (register as *const R).cast()
+}
+
+/// A register in an `imxrt-ral` register block that provides DMA data
+///
+/// Most peripherals read out a data register, and request service with one
+/// signal. `RegisterSource` is that peripheral, built from the register's RAL
+/// handle, so a HAL doesn't need its own [`Source`] implementation for each
+/// instance. Transfers of `u8` and `u16` read the low bytes of the register.
+///
+/// ```no_run
+/// use imxrt_dma::peripheral::{RegisterSource, Source};
+/// # struct Lpuart { DATA: ral_registers::RWRegister<u32> }
+/// # let lpuart2: &Lpuart = unsafe { &*(0x4018_401C as *const Lpuart) };
+///
+/// // Safety: the LPUART2 DATA register is valid for DMA reads.
+/// let lpuart2_rx = unsafe { RegisterSource::<u8>::new(&lpuart2.DATA, 66u32) };
+/// assert_eq!(lpuart2_rx.source_signal(), 66);
+/// ```
+///
+/// With a chip feature, pass a [`Signal`](crate::signal) for the request
+/// signal. By default, enabling and disabling the source does nothing. To enable
+/// and disable the peripheral's DMA requests with each transfer, set a
+/// [request control](Self::with_request_control).
+pub struct RegisterSource<E> {
+    address: *const E,
+    signal: u32,
+    control: Option<fn(bool)>,
+}
+
+impl<E: Element> RegisterSource<E> {
+    /// Describe `register`, which requests service with `signal`
+    ///
+    /// The build fails if `E` is wider than the register.
+    ///
+    /// # Safety
+    ///
+    /// `register` must be valid for DMA reads of `E`, for as long as the DMA channel
+    /// uses the source.
+    pub unsafe fn new(register: &impl ReadableRegister, signal: impl Into<u32>) -> Self {
+        RegisterSource {
+            address: register_address(register),
+            signal: signal.into(),
+            control: None,
+        }
+    }
+
+    /// Enable and disable the peripheral's DMA requests with `control`
+    ///
+    /// Enabling the source calls `control(true)`. Disabling the source calls
+    /// `control(false)`.
+    pub const fn with_request_control(mut self, control: fn(bool)) -> Self {
+        self.control = Some(control);
+        self
+    }
+}
+
+// Safety: the source only holds the address of a peripheral register.
+unsafe impl<E> Send for RegisterSource<E> {}
+
+// Safety: caller of `new` guarantees that the register is valid for DMA reads.
+unsafe impl<E: Element> Source<E> for RegisterSource<E> {
+    fn source_signal(&self) -> u32 {
+        self.signal
+    }
+    fn source_address(&self) -> *const E {
+        self.address
+    }
+    fn enable_source(&mut self) {
+        if let Some(control) = self.control {
+            control(true);
+        }
+    }
+    fn disable_source(&mut self) {
+        if let Some(control) = self.control {
+            control(false);
+        }
+    }
+}
+
+/// A register in an `imxrt-ral` register block that receives DMA data
+///
+/// The counterpart of [`RegisterSource`] for transmitters. Transfers of `u8` and
+/// `u16` write the low bytes of the register. For a FIFO that accepts bursts, see
+/// [`FifoDestination`].
+///
+/// ```no_run
+/// use imxrt_dma::peripheral::{Destination, RegisterDestination};
+/// # struct Lpuart { DATA: ral_registers::RWRegister<u32> }
+/// # let lpuart2: &Lpuart = unsafe { &*(0x4018_401C as *const Lpuart) };
+///
+/// // Safety: the LPUART2 DATA register is valid for DMA writes.
+/// let lpuart2_tx = unsafe { RegisterDestination::<u8>::new(&lpuart2.DATA, 67u32) };
+/// assert_eq!(lpuart2_tx.destination_signal(), 67);
+/// ```
+pub struct RegisterDestination<E> {
+    address: *const E,
+    signal: u32,
+    control: Option<fn(bool)>,
+}
+
+impl<E: Element> RegisterDestination<E> {
+    /// Describe `register`, which requests service with `signal`
+    ///
+    /// The build fails if `E` is wider than the register.
+    ///
+    /// # Safety
+    ///
+    /// `register` must be valid for DMA writes of `E`, for as long as the DMA channel
+    /// uses the destination.
+    pub unsafe fn new(register: &impl WritableRegister, signal: impl Into<u32>) -> Self {
+        RegisterDestination {
+            address: register_address(register),
+            signal: signal.into(),
+            control: None,
+        }
+    }
+
+    /// Enable and disable the peripheral's DMA requests with `control`
+    ///
+    /// Enabling the destination calls `control(true)`. Disabling the destination
+    /// calls `control(false)`.
+    pub const fn with_request_control(mut self, control: fn(bool)) -> Self {
+        self.control = Some(control);
+        self
+    }
+}
+
+// Safety: the destination only holds the address of a peripheral register.
+unsafe impl<E> Send for RegisterDestination<E> {}
+
+// Safety: caller of `new` guarantees that the register is valid for DMA writes.
+unsafe impl<E: Element> Destination<E> for RegisterDestination<E> {
+    fn destination_signal(&self) -> u32 {
+        self.signal
+    }
+    fn destination_address(&self) -> *const E {
+        self.address
+    }
+    fn enable_destination(&mut self) {
+        if let Some(control) = self.control {
+            control(true);
+        }
+    }
+    fn disable_destination(&mut self) {
+        if let Some(control) = self.control {
+            control(false);
+        }
+    }
 }
 
 /// A DMA transfer that receives data from hardware
 ///
 /// The future resolves when the peripheral has provided all
-/// expected data. Use [`read()`](crate::peripheral::read) to construct
+/// expected data, and the source [completes](Source::complete_source)
+/// the transfer. Use [`read()`](crate::peripheral::read) to construct
 /// this future.
+#[cfg(feature = "async")]
 pub struct Read<'a, S, E>
 where
     S: Source<E>,
@@ -105,35 +513,67 @@ where
     channel: &'a Channel,
     source: &'a mut S,
     transfer: Transfer<'a>,
+    transferred: bool,
+    cache: cache::Destination,
     _elem: PhantomData<&'a mut E>,
 }
 
+#[cfg(feature = "async")]
 impl<S, E> Future for Read<'_, S, E>
 where
     S: Source<E>,
     E: Element,
 {
     type Output = Result<(), Error>;
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // Safety: no movement from transfer future...
-        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.transfer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        if !self.transferred {
+            // Safety: no movement from transfer future...
+            let poll =
+                unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)?;
+            if poll.is_pending() {
+                return Poll::Pending;
+            }
+            // Safety: OK to toggle a bool...
+            *unsafe { &mut self.as_mut().get_unchecked_mut().transferred } = true;
+            self.cache.complete();
+        }
+        // Safety: the source reference isn't moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.source.complete_source(cx).map(Ok)
     }
 }
 
+#[cfg(feature = "async")]
 impl<S, E> Drop for Read<'_, S, E>
 where
     S: Source<E>,
     E: Element,
 {
     fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
         self.source.disable_source();
         while self.channel.is_hardware_signaling() {}
         // Drop `transfer` to finish cancellation...
     }
 }
 
-fn prepare_read<S, E>(channel: &mut Channel, source: &mut S, buffer: &mut [E])
-where
+/// Prepare `channel` to receive `buffer` from `source`
+///
+/// If `single` is set, the channel moves one element for each DMA request, whatever
+/// the source's preferred burst.
+#[cfg(feature = "async")]
+fn prepare_read<S, E>(
+    channel: &mut Channel,
+    source: &mut S,
+    buffer: &mut [E],
+    bus: Option<BusProfile>,
+    single: bool,
+) where
     S: Source<E>,
     E: Element,
 {
@@ -145,14 +585,38 @@ where
     // Destination buffer lifetime captured by future. The combination of minor
     // loops and transfer iterations ensure that we do not exceed the end of the
     // destination.
+    let preferred = if single {
+        1
+    } else {
+        bus_burst(bus, source.source_burst())
+    };
+    let burst = burst(preferred, buffer.len());
+    if let Some(bus) = bus {
+        channel.set_bandwidth_control(bus.bandwidth_control());
+    }
     unsafe {
         channel::set_source_hardware(channel, source.source_address());
         channel::set_destination_linear_buffer(channel, buffer);
-        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
-        channel.set_transfer_iterations(buffer.len() as u16);
+        channel.set_minor_loop_bytes((burst * core::mem::size_of::<E>()) as u32);
+        channel.set_transfer_iterations((buffer.len() / burst) as u16);
     }
+}
 
-    source.enable_source();
+/// A read that receives no elements, and never touches the channel or the source
+#[cfg(feature = "async")]
+fn empty_read<'a, S, E>(channel: &'a mut Channel, source: &'a mut S) -> Read<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    Read {
+        channel,
+        transfer: Transfer::empty(channel),
+        transferred: true,
+        cache: cache::Destination::none(),
+        source,
+        _elem: PhantomData,
+    }
 }
 
 /// Use a DMA channel to receive a `buffer` of elements from the source peripheral.
@@ -198,111 +662,178 @@ where
 /// ).await?;
 /// # Ok(()) }
 /// ```
-pub fn read<'a, S, E>(
+#[cfg(feature = "async")]
+pub fn read<'a, S, E, B>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut B,
+) -> Read<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+    B: AsMut<[E]> + ?Sized,
+{
+    read_with(channel, source, buffer.as_mut(), None)
+}
+
+/// Prepare a read, with an optional bus profile
+#[cfg(feature = "async")]
+fn read_with<'a, S, E>(
     channel: &'a mut Channel,
     source: &'a mut S,
     buffer: &'a mut [E],
+    bus: Option<BusProfile>,
 ) -> Read<'a, S, E>
 where
     S: Source<E>,
     E: Element,
 {
-    prepare_read(channel, source, buffer);
+    if buffer.is_empty() {
+        return empty_read(channel, source);
+    }
+    let cache = cache::Destination::prepare(buffer);
+    prepare_read(channel, source, buffer, bus, false);
+    source.enable_source();
     Read {
         channel,
         // Safety: transfer is correctly defined
         transfer: unsafe { Transfer::new(channel) },
+        transferred: false,
+        cache,
         source,
         _elem: PhantomData,
     }
 }
 
-/// A DMA transfer that sends data to hardware
+/// A DMA transfer that receives data from hardware until the peripheral signals
+/// the end of the data
 ///
-/// The future resolves when the device has sent all provided data.
-/// Use [`write()`](crate::peripheral::write) to construct this future.
-pub struct Write<'a, D, E>
+/// The future resolves with the number of elements received. Use
+/// [`read_until()`](crate::peripheral::read_until) to construct this future.
+#[cfg(feature = "async")]
+pub struct ReadUntil<'a, S, E, F>
 where
-    D: Destination<E>,
+    S: Source<E>,
     E: Element,
 {
     channel: &'a Channel,
-    destination: &'a mut D,
+    source: &'a mut S,
     transfer: Transfer<'a>,
-    _elem: PhantomData<&'a E>,
+    terminal: F,
+    /// The number of elements received, once the channel stops.
+    received: Option<usize>,
+    /// Set once `terminal` resolves, and the source is disabled.
+    terminated: bool,
+    cache: cache::Destination,
+    _elem: PhantomData<&'a mut E>,
 }
 
-impl<D, E> Future for Write<'_, D, E>
+#[cfg(feature = "async")]
+impl<S, E, F> Future for ReadUntil<'_, S, E, F>
 where
-    D: Destination<E>,
+    S: Source<E>,
     E: Element,
+    F: Future<Output = ()>,
 {
-    type Output = Result<(), Error>;
+    type Output = Result<usize, Error>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // Safety: no movement from transfer future...
-        unsafe { self.map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)
+        if self.transfer.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        // Safety: the transfer and the terminal condition are structurally pinned.
+        // Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.received.is_none() {
+            let len = this.channel.beginning_transfer_iterations() as usize;
+            let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+            let complete = transfer.poll(cx)?.is_ready();
+            if !complete && !this.terminated {
+                let terminal = unsafe { Pin::new_unchecked(&mut this.terminal) };
+                if terminal.poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                // Stop new requests. The channel still receives the elements that
+                // the peripheral already signaled.
+                this.source.disable_source();
+                this.terminated = true;
+            }
+            if this.terminated {
+                if !complete && this.channel.is_hardware_signaling() {
+                    // Check again on the next poll, instead of blocking the executor
+                    // until the request clears.
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                // Stop the channel between elements.
+                this.channel.disable();
+                while this.channel.is_active() {}
+                let received = if complete || this.channel.is_complete() {
+                    len
+                } else {
+                    len - this.channel.current_transfer_iterations() as usize
+                };
+                this.cache.complete();
+                return Poll::Ready(Ok(received));
+            }
+            this.received = Some(len);
+            this.cache.complete();
+        }
+        let received = this.received.unwrap_or_default();
+        this.source.complete_source(cx).map(|()| Ok(received))
     }
 }
 
-impl<D, E> Drop for Write<'_, D, E>
+#[cfg(feature = "async")]
+impl<S, E, F> Drop for ReadUntil<'_, S, E, F>
 where
-    D: Destination<E>,
+    S: Source<E>,
     E: Element,
 {
     fn drop(&mut self) {
-        self.destination.disable_destination();
+        if self.transfer.is_empty() {
+            return;
+        }
+        self.source.disable_source();
         while self.channel.is_hardware_signaling() {}
         // Drop `transfer` to finish cancellation...
     }
 }
 
-fn prepare_write<D, E>(channel: &mut Channel, buffer: &[E], destination: &mut D)
-where
-    D: Destination<E>,
-    E: Element,
-{
-    channel.disable();
-    channel.set_disable_on_completion(true);
-    channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
-    // Safety: hardware address must be valid, otherwise impl is unsound.
-    // Source buffer lifetime captured by future. The combination of minor
-    // loops and transfer iterations ensure that we do not exceed the end of the
-    // source.
-    unsafe {
-        channel::set_source_linear_buffer(channel, buffer);
-        channel::set_destination_hardware(channel, destination.destination_address());
-        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
-        channel.set_transfer_iterations(buffer.len() as u16);
-    }
-
-    destination.enable_destination();
-}
-
-/// Use a DMA channel to send a `buffer` of data to the destination peripheral.
+/// Use a DMA channel to receive up to a `buffer` of elements from the source
+/// peripheral, ending early when `terminal` resolves
 ///
-/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
-/// to wake the executor when the transfer completes. Otherwise, poll the future.
+/// This is the "read a frame of unknown length" pattern. `terminal` is a future that
+/// resolves once the peripheral signals the end of the data, like a UART that detects
+/// an idle line. When it resolves, the read disables the source's DMA requests, and
+/// lets the DMA channel receive the elements that the peripheral already signaled.
+/// While the request is still active, the read yields to the executor instead of
+/// waiting. Then, the read cancels the channel. The read resolves with the number of
+/// elements at the start of `buffer` that it received. If the buffer fills before
+/// `terminal` resolves, the read resolves with the buffer's length.
+///
+/// The read receives one element per DMA request, so the count is exact. The read
+/// polls `terminal` each time it wakes; arrange for `terminal` to wake the
+/// executor, like the peripheral's own interrupt handler.
+///
+/// # Panics
+///
+/// Panics if the buffer has more than 32767 elements.
 ///
 /// # Example
 ///
-/// Send five bytes to a LPUART device. Wake the executor when the transfer completes.
+/// Receive a LPUART frame that ends when the line goes idle.
 ///
 /// ```no_run
 /// use imxrt_dma::{peripheral, channel::Channel};
 /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
 /// # struct X;
-/// # unsafe impl peripheral::Destination<u8> for X {
-/// #   fn destination_signal(&self) -> u32 { 0 }
-/// #   fn destination_address(&self) -> *const u8 { panic!() }
-/// #   fn enable_destination(&mut self) { panic!() }
-/// #   fn disable_destination(&mut self) { panic!() }
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
 /// # }
-///
-/// // #[cortex_m_rt::interrupt]
-/// fn DMA7() {
-///     // Safety: DMA channel 7 valid and used by a future.
-///     unsafe { DMA.on_interrupt(7) };
-/// }
+/// # async fn idle_line() {}
 ///
 /// # async fn f() -> imxrt_dma::Result<()> {
 /// let mut lpuart = // A LPUART peripheral
@@ -310,57 +841,535 @@ where
 /// let mut channel_7: Channel = // DMA channel 7
 ///     # unsafe { DMA.channel(7) };
 ///
-/// channel_7.set_interrupt_on_completion(true);
-/// // TODO unmask interrupts in NVIC!
-///
-/// let buffer = [4u8, 5, 6, 7, 8];
-///
-/// peripheral::write(
-///     &mut channel_7,
-///     &buffer,
-///     &mut lpuart,
-/// ).await?;
+/// let mut buffer = [0u8; 256];
+/// // idle_line() resolves from the LPUART's IDLE interrupt.
+/// let received = peripheral::read_until(&mut channel_7, &mut lpuart, &mut buffer, idle_line()).await?;
+/// let frame = &buffer[..received];
+/// # let _ = frame;
 /// # Ok(()) }
 /// ```
-pub fn write<'a, D, E>(
+#[cfg(feature = "async")]
+pub fn read_until<'a, S, E, B, F>(
     channel: &'a mut Channel,
-    buffer: &'a [E],
-    destination: &'a mut D,
-) -> Write<'a, D, E>
+    source: &'a mut S,
+    buffer: &'a mut B,
+    terminal: F,
+) -> ReadUntil<'a, S, E, F>
 where
-    D: Destination<E>,
+    S: Source<E>,
     E: Element,
+    B: AsMut<[E]> + ?Sized,
+    F: Future<Output = ()>,
 {
-    prepare_write(channel, buffer, destination);
-    Write {
+    let buffer = buffer.as_mut();
+    assert!(buffer.len() <= 0x7FFF, "DMA read buffer is too large");
+    if buffer.is_empty() {
+        return ReadUntil {
+            channel,
+            transfer: Transfer::empty(channel),
+            terminal,
+            received: Some(0),
+            terminated: false,
+            cache: cache::Destination::none(),
+            source,
+            _elem: PhantomData,
+        };
+    }
+    let cache = cache::Destination::prepare(buffer);
+    // One element per minor loop, so the count is exact.
+    prepare_read(channel, source, buffer, None, true);
+    source.enable_source();
+    ReadUntil {
         channel,
-        destination,
         // Safety: transfer is correctly defined
         transfer: unsafe { Transfer::new(channel) },
+        terminal,
+        received: None,
+        terminated: false,
+        cache,
+        source,
         _elem: PhantomData,
     }
 }
 
-/// Indicates that a peripheral can read and write from a single buffer
-/// using two simultaneous DMA transfers
-///
-/// It's expected that the TX operation will drive the RX operation.
-/// A SPI transfer can be modeled in this manner.
-///
-/// # Safety
-///
-/// `Bidirectional` assumes the same safety requirements as source and
-/// destination. Addtionally, you ensure that the peripheral is capable
-/// of this kind of transfer from a single buffer.
-pub unsafe trait Bidirectional<E: Element>: Source<E> + Destination<E> {}
-
-/// A full-duplex DMA transfer from a single buffer
-///
-/// `FullDuplex` only works with [`Bidirectional`](crate::peripheral::Bidirectional)
-/// peripherals. The transfer acts on a single buffer, sending and receiving data
-/// element by element. It yields when all elements are sent and received.
+/// A DMA transfer that sends data to hardware
+///
+/// The future resolves when the device has sent all provided data, and
+/// the destination [completes](Destination::complete_destination) the
+/// transfer. Use [`write()`](crate::peripheral::write) to construct this future.
+#[cfg(feature = "async")]
+pub struct Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel: &'a Channel,
+    destination: &'a mut D,
+    transfer: Transfer<'a>,
+    transferred: bool,
+    _elem: PhantomData<&'a E>,
+}
+
+#[cfg(feature = "async")]
+impl<D, E> Future for Write<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    type Output = Result<(), Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.transfer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        if !self.transferred {
+            // Safety: no movement from transfer future...
+            let poll =
+                unsafe { self.as_mut().map_unchecked_mut(|this| &mut this.transfer) }.poll(cx)?;
+            if poll.is_pending() {
+                return Poll::Pending;
+            }
+            // Safety: OK to toggle a bool...
+            *unsafe { &mut self.as_mut().get_unchecked_mut().transferred } = true;
+        }
+        // Safety: the destination reference isn't moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.destination.complete_destination(cx).map(Ok)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, E> Drop for Write<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
+        self.destination.disable_destination();
+        while self.channel.is_hardware_signaling() {}
+        // Drop `transfer` to finish cancellation...
+    }
+}
+
+#[cfg(feature = "async")]
+fn prepare_write<D, E>(
+    channel: &mut Channel,
+    buffer: &[E],
+    destination: &mut D,
+    bus: Option<BusProfile>,
+) where
+    D: Destination<E>,
+    E: Element,
+{
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+    // Safety: hardware address must be valid, otherwise impl is unsound.
+    // Source buffer lifetime captured by future. The combination of minor
+    // loops and transfer iterations ensure that we do not exceed the end of the
+    // source.
+    let burst = burst(
+        bus_burst(bus, destination.destination_burst()),
+        buffer.len(),
+    );
+    if let Some(bus) = bus {
+        channel.set_bandwidth_control(bus.bandwidth_control());
+    }
+    unsafe {
+        channel::set_source_linear_buffer(channel, buffer);
+        channel::set_destination_hardware(channel, destination.destination_address());
+        channel.set_minor_loop_bytes((burst * core::mem::size_of::<E>()) as u32);
+        channel.set_transfer_iterations((buffer.len() / burst) as u16);
+    }
+}
+
+/// Returns the number of elements per minor loop
+///
+/// Uses the peripheral's `preferred` burst only if it evenly divides the buffer.
+#[cfg(feature = "async")]
+fn burst(preferred: usize, len: usize) -> usize {
+    if preferred > 1 && len.is_multiple_of(preferred) {
+        preferred
+    } else {
+        1
+    }
+}
+
+/// Returns the peripheral's `preferred` burst, limited by the bus profile
+#[cfg(feature = "async")]
+fn bus_burst(bus: Option<BusProfile>, preferred: usize) -> usize {
+    match bus {
+        Some(BusProfile::LowImpact) => 1,
+        _ => preferred,
+    }
+}
+
+/// A write that sends no elements, and never touches the channel or the destination
+#[cfg(feature = "async")]
+fn empty_write<'a, D, E>(channel: &'a mut Channel, destination: &'a mut D) -> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    Write {
+        channel,
+        destination,
+        transfer: Transfer::empty(channel),
+        transferred: true,
+        _elem: PhantomData,
+    }
+}
+
+/// Use a DMA channel to send a `buffer` of data to the destination peripheral.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Example
+///
+/// Send five bytes to a LPUART device. Wake the executor when the transfer completes.
+///
+/// ```no_run
+/// use imxrt_dma::{peripheral, channel::Channel};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// // #[cortex_m_rt::interrupt]
+/// fn DMA7() {
+///     // Safety: DMA channel 7 valid and used by a future.
+///     unsafe { DMA.on_interrupt(7) };
+/// }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART peripheral
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// channel_7.set_interrupt_on_completion(true);
+/// // TODO unmask interrupts in NVIC!
+///
+/// let buffer = [4u8, 5, 6, 7, 8];
+///
+/// peripheral::write(
+///     &mut channel_7,
+///     &buffer,
+///     &mut lpuart,
+/// ).await?;
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "async")]
+pub fn write<'a, D, E, B>(
+    channel: &'a mut Channel,
+    buffer: &'a B,
+    destination: &'a mut D,
+) -> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+    B: AsRef<[E]> + ?Sized,
+{
+    write_with(channel, buffer.as_ref(), destination, None)
+}
+
+/// Prepare a write, with an optional bus profile
+#[cfg(feature = "async")]
+fn write_with<'a, D, E>(
+    channel: &'a mut Channel,
+    buffer: &'a [E],
+    destination: &'a mut D,
+    bus: Option<BusProfile>,
+) -> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    if buffer.is_empty() {
+        return empty_write(channel, destination);
+    }
+    cache::prepare_source(buffer);
+    prepare_write(channel, buffer, destination, bus);
+    destination.enable_destination();
+    Write {
+        channel,
+        destination,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        transferred: false,
+        _elem: PhantomData,
+    }
+}
+
+/// Returns the number of peripheral elements `E` that pack into a memory element `W`
+///
+/// Panics if `W` isn't an exact multiple of `E`.
+#[cfg(feature = "async")]
+fn packing<E: Element, W: Element>() -> usize {
+    let (esize, wsize) = (core::mem::size_of::<E>(), core::mem::size_of::<W>());
+    assert!(
+        wsize.is_multiple_of(esize),
+        "Memory element size is not a multiple of the peripheral element size"
+    );
+    wsize / esize
+}
+
+/// Use a DMA channel to receive a `buffer` of wide elements from a narrow source peripheral.
+///
+/// For every DMA request, the DMA channel reads enough `E` elements from the source
+/// to fill one `W` element, then writes the `W` element into memory. For example, a
+/// `Source<u8>` receiving into a `[u32]` buffer performs four byte-sized reads from the
+/// peripheral for every word-sized write to memory. The wider memory accesses
+/// reduce the load on the memory bus.
+///
+/// The source must have enough data for a `W` element when it requests DMA service.
+/// For a peripheral with a FIFO, set the receive watermark to this packing ratio.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when the transfer completes. Otherwise, poll the future.
+///
+/// # Panics
+///
+/// Panics if the size of `W` is not a multiple of the size of `E`.
+///
+/// # Example
+///
+/// Receive 32 bytes from a LPUART peripheral, four bytes at a time.
+///
+/// ```no_run
+/// use imxrt_dma::{peripheral, channel::Channel};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART peripheral, with a receive watermark of 4
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut buffer = [0u32; 8];
+/// peripheral::read_packed(&mut channel_7, &mut lpuart, &mut buffer).await?;
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "async")]
+pub fn read_packed<'a, S, E, W>(
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [W],
+) -> Read<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+    W: Element,
+{
+    let packing = packing::<E, W>();
+    if buffer.is_empty() {
+        return empty_read(channel, source);
+    }
+    let cache = cache::Destination::prepare(buffer);
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+    // Safety: hardware source address must be valid, otherwise impl is unsound.
+    // Destination buffer lifetime captured by future. Each minor loop fills one
+    // W element, so the minor loops and transfer iterations ensure that we do not
+    // exceed the end of the destination.
+    unsafe {
+        channel::set_source_hardware(channel, source.source_address());
+        channel::set_destination_linear_buffer(channel, buffer);
+        channel.set_minor_loop_bytes((core::mem::size_of::<E>() * packing) as u32);
+        channel.set_transfer_iterations(buffer.len() as u16);
+    }
+    source.enable_source();
+
+    Read {
+        channel,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        transferred: false,
+        cache,
+        source,
+        _elem: PhantomData,
+    }
+}
+
+/// Use a DMA channel to send a `buffer` of wide elements to a narrow destination peripheral.
+///
+/// For every DMA request, the DMA channel reads one `W` element from memory, then
+/// writes it to the destination as `E` elements. For example, a `[u32]` buffer sent to
+/// a `Destination<u8>` performs one word-sized read from memory for every four
+/// byte-sized writes to the peripheral. See [`read_packed`] for more information.
+///
+/// The destination must have room for a `W` element when it requests DMA service.
+/// For a peripheral with a FIFO, set the transmit watermark so that the FIFO has room
+/// for this packing ratio.
+///
+/// # Panics
+///
+/// Panics if the size of `W` is not a multiple of the size of `E`.
+#[cfg(feature = "async")]
+pub fn write_unpacked<'a, D, E, W>(
+    channel: &'a mut Channel,
+    buffer: &'a [W],
+    destination: &'a mut D,
+) -> Write<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+    W: Element,
+{
+    let packing = packing::<E, W>();
+    if buffer.is_empty() {
+        return empty_write(channel, destination);
+    }
+    cache::prepare_source(buffer);
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+    // Safety: hardware address must be valid, otherwise impl is unsound.
+    // Source buffer lifetime captured by future. Each minor loop sends one
+    // W element, so the minor loops and transfer iterations ensure that we do
+    // not exceed the end of the source.
+    unsafe {
+        channel::set_source_linear_buffer(channel, buffer);
+        channel::set_destination_hardware(channel, destination.destination_address());
+        channel.set_minor_loop_bytes((core::mem::size_of::<E>() * packing) as u32);
+        channel.set_transfer_iterations(buffer.len() as u16);
+    }
+    destination.enable_destination();
+
+    Write {
+        channel,
+        destination,
+        // Safety: transfer is correctly defined
+        transfer: unsafe { Transfer::new(channel) },
+        transferred: false,
+        _elem: PhantomData,
+    }
+}
+
+/// Indicates that a peripheral can read and write from a single buffer
+/// using two simultaneous DMA transfers
+///
+/// It's expected that the TX operation will drive the RX operation.
+/// A SPI transfer can be modeled in this manner.
+///
+/// # Safety
+///
+/// `Bidirectional` assumes the same safety requirements as source and
+/// destination. Addtionally, you ensure that the peripheral is capable
+/// of this kind of transfer from a single buffer.
+pub unsafe trait Bidirectional<E: Element>: Source<E> + Destination<E> {
+    /// Perform any actions necessary to enable DMA transfers in both directions
+    ///
+    /// Full-duplex transfers call this method instead of `enable_source` and
+    /// `enable_destination`. Override this method if the peripheral enables both
+    /// requests at once, like a LPSPI's `DER` register. The default implementation
+    /// enables the destination, then the source.
+    fn enable_bidirectional(&mut self) {
+        self.enable_destination();
+        self.enable_source();
+    }
+    /// Perform any actions necessary to disable or cancel DMA transfers in both
+    /// directions
+    ///
+    /// Full-duplex transfers call this method instead of `disable_source` and
+    /// `disable_destination`. The default implementation disables the destination,
+    /// then the source.
+    fn disable_bidirectional(&mut self) {
+        self.disable_destination();
+        self.disable_source();
+    }
+}
+
+/// A peripheral that splits into independent receive and transmit halves
+///
+/// A LPUART or LPSPI receives and transmits at the same time, but a transfer
+/// needs `&mut` access to its source or destination. `split` borrows the
+/// peripheral once, and returns a half for each direction. Give each half to its
+/// own transfer, on its own channel, and run the transfers concurrently.
+///
+/// Each half must only access the registers for its direction, so that the halves
+/// never alias.
+///
+/// ```
+/// use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// use imxrt_dma::peripheral::{self, Destination, DmaPeripheral, Source};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// struct Lpuart { /* ... */ }
+/// struct Rx<'a>(&'a Lpuart);
+/// struct Tx<'a>(&'a Lpuart);
+///
+/// unsafe impl Source<u8> for Rx<'_> {
+///     // Access the receive registers...
+///     # fn source_signal(&self) -> u32 { 0 }
+///     # fn source_address(&self) -> *const u8 { panic!() }
+///     # fn enable_source(&mut self) { panic!() }
+///     # fn disable_source(&mut self) { panic!() }
+/// }
+/// unsafe impl Destination<u8> for Tx<'_> {
+///     // Access the transmit registers...
+///     # fn destination_signal(&self) -> u32 { 0 }
+///     # fn destination_address(&self) -> *const u8 { panic!() }
+///     # fn enable_destination(&mut self) { panic!() }
+///     # fn disable_destination(&mut self) { panic!() }
+/// }
+///
+/// impl DmaPeripheral<u8> for Lpuart {
+///     type SourceHalf<'a> = Rx<'a>;
+///     type DestinationHalf<'a> = Tx<'a>;
+///     fn split(&mut self) -> (Rx<'_>, Tx<'_>) {
+///         (Rx(self), Tx(self))
+///     }
+/// }
+///
+/// # let (mut rx_channel, mut tx_channel) = unsafe { (DMA.channel(7), DMA.channel(8)) };
+/// let mut lpuart = Lpuart { /* ... */ };
+/// let (mut rx, mut tx) = lpuart.split();
+/// let (mut received, sent) = ([0u8; 0], [0u8; 0]);
+///
+/// let mut read = pin!(peripheral::read(&mut rx_channel, &mut rx, &mut received));
+/// let mut write = pin!(peripheral::write(&mut tx_channel, &sent, &mut tx));
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert_eq!(read.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+/// assert_eq!(write.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+/// ```
+pub trait DmaPeripheral<E: Element> {
+    /// The receive half
+    type SourceHalf<'a>: Source<E>
+    where
+        Self: 'a;
+    /// The transmit half
+    type DestinationHalf<'a>: Destination<E>
+    where
+        Self: 'a;
+    /// Split the peripheral into its receive and transmit halves
+    fn split(&mut self) -> (Self::SourceHalf<'_>, Self::DestinationHalf<'_>);
+}
+
+/// A full-duplex DMA transfer from a single buffer
+///
+/// `FullDuplex` only works with [`Bidirectional`](crate::peripheral::Bidirectional)
+/// peripherals. The transfer acts on a single buffer, sending and receiving data
+/// element by element. It yields when all elements are sent and received, and
+/// the peripheral completes both directions.
 ///
 /// To create this future, use [`full_duplex()`].
+#[cfg(feature = "async")]
 pub struct FullDuplex<'a, P, E>
 where
     P: Bidirectional<E>,
@@ -369,6 +1378,7 @@ where
     rx_channel: &'a Channel,
     rx_transfer: Transfer<'a>,
     rx_done: bool,
+    cache: cache::Destination,
     tx_channel: &'a Channel,
     tx_transfer: Transfer<'a>,
     tx_done: bool,
@@ -437,23 +1447,43 @@ where
 /// ).await?;
 /// # Ok(()) }
 /// ```
-pub fn full_duplex<'a, P, E>(
+#[cfg(feature = "async")]
+pub fn full_duplex<'a, P, E, B>(
     rx_channel: &'a mut Channel,
     tx_channel: &'a mut Channel,
     peripheral: &'a mut P,
-    buffer: &'a mut [E],
+    buffer: &'a mut B,
 ) -> FullDuplex<'a, P, E>
 where
     P: Bidirectional<E>,
     E: Element,
+    B: AsMut<[E]> + ?Sized,
 {
-    prepare_write(tx_channel, buffer, peripheral);
-    prepare_read(rx_channel, peripheral, buffer);
+    let buffer = buffer.as_mut();
+    if buffer.is_empty() {
+        return FullDuplex {
+            rx_channel,
+            rx_transfer: Transfer::empty(rx_channel),
+            rx_done: true,
+            cache: cache::Destination::none(),
+            tx_channel,
+            tx_transfer: Transfer::empty(tx_channel),
+            tx_done: true,
+            peripheral,
+            _elem: PhantomData,
+        };
+    }
+    // Cleans the buffer before the transmit channel reads it.
+    let cache = cache::Destination::prepare(buffer);
+    prepare_write(tx_channel, buffer, peripheral, None);
+    prepare_read(rx_channel, peripheral, buffer, None, false);
+    peripheral.enable_bidirectional();
 
     FullDuplex {
         rx_channel,
         rx_transfer: unsafe { Transfer::new(rx_channel) },
         rx_done: false,
+        cache,
         tx_channel,
         tx_transfer: unsafe { Transfer::new(tx_channel) },
         tx_done: false,
@@ -462,6 +1492,7 @@ where
     }
 }
 
+#[cfg(feature = "async")]
 impl<P, E> Future for FullDuplex<'_, P, E>
 where
     P: Bidirectional<E>,
@@ -470,6 +1501,9 @@ where
     type Output = Result<(), Error>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.rx_transfer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
         if !self.rx_done {
             // Safety: pin projection OK, no movement from future...
             let poll = unsafe {
@@ -479,6 +1513,9 @@ where
             .poll(cx)?;
             // Safety: OK to toggle a bool...
             *unsafe { &mut self.as_mut().get_unchecked_mut().rx_done } = poll.is_ready();
+            if poll.is_ready() {
+                self.cache.complete();
+            }
         }
 
         if !self.tx_done {
@@ -493,23 +1530,592 @@ where
         }
 
         if self.tx_done && self.rx_done {
-            Poll::Ready(Ok(()))
-        } else {
-            Poll::Pending
+            // Safety: the peripheral reference isn't moved.
+            let peripheral = &mut unsafe { self.get_unchecked_mut() }.peripheral;
+            let tx = peripheral.complete_destination(cx);
+            let rx = peripheral.complete_source(cx);
+            if tx.is_ready() && rx.is_ready() {
+                return Poll::Ready(Ok(()));
+            }
         }
+        Poll::Pending
     }
 }
 
+#[cfg(feature = "async")]
 impl<P, E> Drop for FullDuplex<'_, P, E>
 where
     P: Bidirectional<E>,
     E: Element,
 {
     fn drop(&mut self) {
-        self.peripheral.disable_destination();
-        self.peripheral.disable_source();
+        if self.rx_transfer.is_empty() {
+            return;
+        }
+        self.peripheral.disable_bidirectional();
         while self.tx_channel.is_hardware_signaling() {}
         while self.rx_channel.is_hardware_signaling() {}
         // Drop the transfers to finish cancellation...
     }
 }
+
+/// The steps of a [`HalfDuplex`] transfer
+#[cfg(feature = "async")]
+enum Phase {
+    /// Sending the transmit buffer.
+    Transmit,
+    /// Waiting for the destination to complete.
+    Drain,
+    /// Receiving into the receive buffer.
+    Receive,
+    /// Waiting for the source to complete.
+    Complete,
+}
+
+/// A half-duplex DMA transfer that sends, then receives, with one channel
+///
+/// The future sends the transmit buffer, and waits for the peripheral to
+/// [complete](Destination::complete_destination) the transmit. Then, it calls the
+/// turnaround hook to change the peripheral's direction, and receives into the
+/// receive buffer. It resolves when the peripheral has provided all expected data,
+/// and the source [completes](Source::complete_source) the transfer.
+///
+/// To create this future, use [`half_duplex()`].
+#[cfg(feature = "async")]
+pub struct HalfDuplex<'a, P, E, F>
+where
+    P: Source<E> + Destination<E>,
+    E: Element,
+    F: FnOnce(&mut P),
+{
+    channel: NonNull<Channel>,
+    peripheral: &'a mut P,
+    receive: Option<&'a mut [E]>,
+    turnaround: Option<F>,
+    transfer: Transfer<'a>,
+    phase: Phase,
+    cache: cache::Destination,
+    _channel: PhantomData<&'a mut Channel>,
+}
+
+/// Send `transmit`, turn the peripheral around, then receive into `receive`
+///
+/// Use this for half-duplex peripherals that share one data line for both
+/// directions, like an RS-485 transceiver, or a three-wire SPI. Once the peripheral
+/// completes the transmit, the future calls `turnaround`. Use the hook to change the
+/// peripheral's direction; for instance, release the RS-485 driver enable, or switch
+/// the SPI data pin to an input. Then, the future receives from the peripheral,
+/// using the same DMA channel.
+///
+/// If `transmit` is empty, the future calls `turnaround`, then receives. If `receive`
+/// is empty, the future resolves after calling `turnaround`.
+///
+/// Consider using a DMA interrupt handler that calls [`on_interrupt()`](crate::Dma::on_interrupt)
+/// to wake the executor when each transfer completes. Otherwise, poll the future.
+///
+/// # Example
+///
+/// Send a Modbus request over RS-485, then receive the response.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # impl X { fn set_driver_enable(&mut self, _: bool) {} }
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+/// # unsafe impl peripheral::Destination<u8> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART peripheral, connected to an RS-485 transceiver
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+/// channel_7.set_interrupt_on_completion(true);
+///
+/// let request = [0x01u8, 0x03, 0x00, 0x00, 0x00, 0x02, 0xC4, 0x0B];
+/// let mut response = [0u8; 9];
+///
+/// lpuart.set_driver_enable(true);
+/// peripheral::half_duplex(&mut channel_7, &mut lpuart, &request, &mut response, |lpuart| {
+///     lpuart.set_driver_enable(false)
+/// })
+/// .await?;
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "async")]
+pub fn half_duplex<'a, P, E, F, T, R>(
+    channel: &'a mut Channel,
+    peripheral: &'a mut P,
+    transmit: &'a T,
+    receive: &'a mut R,
+    turnaround: F,
+) -> HalfDuplex<'a, P, E, F>
+where
+    P: Source<E> + Destination<E>,
+    E: Element,
+    F: FnOnce(&mut P),
+    T: AsRef<[E]> + ?Sized,
+    R: AsMut<[E]> + ?Sized,
+{
+    let (transmit, receive) = (transmit.as_ref(), receive.as_mut());
+    let channel = NonNull::from(channel);
+    let transfer = if transmit.is_empty() {
+        // Safety: the future has exclusive access to the channel.
+        Transfer::empty(unsafe { channel.as_ref() })
+    } else {
+        cache::prepare_source(transmit);
+        // Safety: the future has exclusive access to the channel.
+        prepare_write(
+            unsafe { &mut *channel.as_ptr() },
+            transmit,
+            peripheral,
+            None,
+        );
+        peripheral.enable_destination();
+        // Safety: transfer is correctly defined
+        unsafe { Transfer::new(channel.as_ref()) }
+    };
+    HalfDuplex {
+        channel,
+        peripheral,
+        receive: Some(receive),
+        turnaround: Some(turnaround),
+        transfer,
+        phase: Phase::Transmit,
+        cache: cache::Destination::none(),
+        _channel: PhantomData,
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P, E, F> HalfDuplex<'_, P, E, F>
+where
+    P: Source<E> + Destination<E>,
+    E: Element,
+    F: FnOnce(&mut P),
+{
+    fn channel(&self) -> &Channel {
+        // Safety: the future has exclusive access to the channel.
+        unsafe { self.channel.as_ref() }
+    }
+
+    /// Turn the peripheral around, and prepare the receive transfer
+    fn turnaround(&mut self) {
+        if let Some(turnaround) = self.turnaround.take() {
+            turnaround(self.peripheral);
+        }
+        let buffer = self.receive.take().expect("polled after completion");
+        // Drop the transmit transfer before reconfiguring the channel.
+        // Safety: the future has exclusive access to the channel.
+        self.transfer = Transfer::empty(unsafe { &*self.channel.as_ptr() });
+        if !buffer.is_empty() {
+            self.cache = cache::Destination::prepare(buffer);
+            // Safety: the future has exclusive access to the channel, and the
+            // transmit transfer is finished.
+            prepare_read(
+                unsafe { &mut *self.channel.as_ptr() },
+                self.peripheral,
+                buffer,
+                None,
+                false,
+            );
+            self.peripheral.enable_source();
+            // Safety: transfer is correctly defined
+            self.transfer = unsafe { Transfer::new(&*self.channel.as_ptr()) };
+        }
+        self.phase = Phase::Receive;
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P, E, F> Future for HalfDuplex<'_, P, E, F>
+where
+    P: Source<E> + Destination<E>,
+    E: Element,
+    F: FnOnce(&mut P),
+{
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is only replaced in place, once it's dropped.
+        // Nothing else is pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            match this.phase {
+                Phase::Transmit => {
+                    // Safety: the transfer isn't moved.
+                    let poll = unsafe { Pin::new_unchecked(&mut this.transfer) }.poll(cx)?;
+                    if poll.is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.phase = Phase::Drain;
+                }
+                Phase::Drain => {
+                    if !this.transfer.is_empty() {
+                        if this.peripheral.complete_destination(cx).is_pending() {
+                            return Poll::Pending;
+                        }
+                        this.peripheral.disable_destination();
+                        while this.channel().is_hardware_signaling() {}
+                    }
+                    this.turnaround();
+                }
+                Phase::Receive => {
+                    // Safety: the transfer isn't moved.
+                    let poll = unsafe { Pin::new_unchecked(&mut this.transfer) }.poll(cx)?;
+                    if poll.is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.cache.complete();
+                    this.phase = Phase::Complete;
+                }
+                Phase::Complete => {
+                    if this.transfer.is_empty() {
+                        return Poll::Ready(Ok(()));
+                    }
+                    return this.peripheral.complete_source(cx).map(Ok);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P, E, F> Drop for HalfDuplex<'_, P, E, F>
+where
+    P: Source<E> + Destination<E>,
+    E: Element,
+    F: FnOnce(&mut P),
+{
+    fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
+        match self.phase {
+            Phase::Transmit | Phase::Drain => self.peripheral.disable_destination(),
+            Phase::Receive | Phase::Complete => self.peripheral.disable_source(),
+        }
+        while self.channel().is_hardware_signaling() {}
+        // Drop `transfer` to finish cancellation...
+    }
+}
+
+/// How a peripheral transfer shares the system bus
+///
+/// Each DMA request moves a burst of elements, and the DMA engine can stall
+/// between reads and writes to leave the bus to other masters. A profile picks
+/// both, so you don't need to tune the [bandwidth control](crate::BandwidthControl)
+/// and the burst yourself. Set a profile on a [`ReadBuilder`] or a [`WriteBuilder`].
+///
+/// ```
+/// use imxrt_dma::{peripheral::BusProfile, BandwidthControl};
+///
+/// assert_eq!(BusProfile::Throughput.bandwidth_control(), None);
+/// assert_eq!(BusProfile::LowImpact.bandwidth_control(), Some(BandwidthControl::Stall8Cycles));
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BusProfile {
+    /// Move the peripheral's preferred burst, without stalls
+    #[default]
+    Throughput,
+    /// Move the peripheral's preferred burst, stalling for 4 cycles after each
+    /// read and write
+    Balanced,
+    /// Move one element for each request, stalling for 8 cycles after each
+    /// read and write
+    ///
+    /// Single-element requests let the DMA engine arbitrate between channels
+    /// after every element.
+    LowImpact,
+}
+
+#[cfg(feature = "async")]
+impl BusProfile {
+    /// Returns the bandwidth control for this profile
+    pub const fn bandwidth_control(self) -> Option<crate::BandwidthControl> {
+        match self {
+            BusProfile::Throughput => None,
+            BusProfile::Balanced => Some(crate::BandwidthControl::Stall4Cycles),
+            BusProfile::LowImpact => Some(crate::BandwidthControl::Stall8Cycles),
+        }
+    }
+}
+
+/// Describes a transfer from a source peripheral into a buffer
+///
+/// Use [`Channel::transfer_from`] to create the builder. `await` the builder to
+/// run the transfer. Or, [`start`](ReadBuilder::start) the transfer and poll it
+/// without an executor.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, peripheral};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u8> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u8 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut lpuart = // A LPUART peripheral
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut buffer = [0u8; 32];
+/// channel_7
+///     .transfer_from(&mut lpuart, &mut buffer)
+///     .interrupt_on_completion(true)
+///     .await?;
+///
+/// // Without an executor:
+/// let mut read = channel_7.transfer_from(&mut lpuart, &mut buffer).start();
+/// while read.poll_complete().is_pending() {}
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "async")]
+pub struct ReadBuilder<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: &'a mut [E],
+    bus: Option<BusProfile>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, S, E> ReadBuilder<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Generate an interrupt when the transfer completes
+    ///
+    /// See [`Channel::set_interrupt_on_completion`].
+    pub fn interrupt_on_completion(self, intr: bool) -> Self {
+        self.channel.set_interrupt_on_completion(intr);
+        self
+    }
+
+    /// Tune the transfer's impact on the system bus
+    ///
+    /// The profile sets the channel's bandwidth control, and limits the
+    /// elements moved for each DMA request. Without a profile, the transfer
+    /// keeps the channel's bandwidth control, and moves the peripheral's
+    /// preferred burst. See [`BusProfile`] for more information.
+    pub fn bus_profile(mut self, bus: BusProfile) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Start the transfer
+    ///
+    /// Unlike the future returned by [`read()`], the transfer starts before the
+    /// future's first poll.
+    pub fn start(self) -> Read<'a, S, E> {
+        let mut read = read_with(self.channel, self.source, self.buffer, self.bus);
+        // If the channel won't enable, the first poll reports why.
+        let _ = read.transfer.begin();
+        read
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, S, E> core::future::IntoFuture for ReadBuilder<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<(), Error>;
+    type IntoFuture = Read<'a, S, E>;
+    fn into_future(self) -> Read<'a, S, E> {
+        read_with(self.channel, self.source, self.buffer, self.bus)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S, E> Read<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Poll the transfer without a `Context`
+    ///
+    /// Use `poll_complete` when you're not using an executor. Poll until the
+    /// transfer returns `Poll::Ready`. See [`Transfer::poll_complete`] for
+    /// more information.
+    ///
+    /// A read into an empty buffer is ready immediately.
+    ///
+    /// ```
+    /// use imxrt_dma::peripheral::Source;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # struct X;
+    /// # unsafe impl Source<u8> for X {
+    /// #   fn source_signal(&self) -> u32 { 0 }
+    /// #   fn source_address(&self) -> *const u8 { panic!() }
+    /// #   fn enable_source(&mut self) { panic!() }
+    /// #   fn disable_source(&mut self) { panic!() }
+    /// # }
+    /// # let mut channel_7 = unsafe { DMA.channel(7) };
+    /// # let mut lpuart = X;
+    ///
+    /// let mut buffer: [u8; 0] = [];
+    /// let mut read = channel_7.transfer_from(&mut lpuart, &mut buffer).start();
+    /// assert!(read.poll_complete().is_ready());
+    /// ```
+    pub fn poll_complete(&mut self) -> Poll<Result<(), Error>> {
+        // Safety: the future is not self-referential, so it may move
+        // between polls.
+        unsafe { Pin::new_unchecked(self) }
+            .poll(&mut Context::from_waker(core::task::Waker::noop()))
+    }
+}
+
+/// Describes a transfer from a buffer to a destination peripheral
+///
+/// Use [`Channel::transfer_to`] to create the builder. `await` the builder to
+/// run the transfer. Or, [`start`](WriteBuilder::start) the transfer and poll it
+/// without an executor. See [`ReadBuilder`] for an example.
+#[cfg(feature = "async")]
+pub struct WriteBuilder<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    buffer: &'a [E],
+    destination: &'a mut D,
+    bus: Option<BusProfile>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, D, E> WriteBuilder<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Generate an interrupt when the transfer completes
+    ///
+    /// See [`Channel::set_interrupt_on_completion`].
+    pub fn interrupt_on_completion(self, intr: bool) -> Self {
+        self.channel.set_interrupt_on_completion(intr);
+        self
+    }
+
+    /// Tune the transfer's impact on the system bus
+    ///
+    /// The profile sets the channel's bandwidth control, and limits the
+    /// elements moved for each DMA request. Without a profile, the transfer
+    /// keeps the channel's bandwidth control, and moves the peripheral's
+    /// preferred burst. See [`BusProfile`] for more information.
+    pub fn bus_profile(mut self, bus: BusProfile) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Start the transfer
+    ///
+    /// Unlike the future returned by [`write()`], the transfer starts before the
+    /// future's first poll.
+    pub fn start(self) -> Write<'a, D, E> {
+        let mut write = write_with(self.channel, self.buffer, self.destination, self.bus);
+        // If the channel won't enable, the first poll reports why.
+        let _ = write.transfer.begin();
+        write
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, D, E> core::future::IntoFuture for WriteBuilder<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    type Output = Result<(), Error>;
+    type IntoFuture = Write<'a, D, E>;
+    fn into_future(self) -> Write<'a, D, E> {
+        write_with(self.channel, self.buffer, self.destination, self.bus)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, E> Write<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Poll the transfer without a `Context`
+    ///
+    /// Use `poll_complete` when you're not using an executor. Poll until the
+    /// transfer returns `Poll::Ready`. See [`Transfer::poll_complete`] for
+    /// more information.
+    pub fn poll_complete(&mut self) -> Poll<Result<(), Error>> {
+        // Safety: the future is not self-referential, so it may move
+        // between polls.
+        unsafe { Pin::new_unchecked(self) }
+            .poll(&mut Context::from_waker(core::task::Waker::noop()))
+    }
+}
+
+#[cfg(feature = "async")]
+impl Channel {
+    /// Describe a transfer from `source` into `buffer`
+    ///
+    /// The builder configures the same transfer as [`read()`]. See
+    /// [`ReadBuilder`] for more information.
+    pub fn transfer_from<'a, S, E>(
+        &'a mut self,
+        source: &'a mut S,
+        buffer: &'a mut [E],
+    ) -> ReadBuilder<'a, S, E>
+    where
+        S: Source<E>,
+        E: Element,
+    {
+        ReadBuilder {
+            channel: self,
+            source,
+            buffer,
+            bus: None,
+        }
+    }
+
+    /// Describe a transfer from `buffer` to `destination`
+    ///
+    /// The builder configures the same transfer as [`write()`]. See
+    /// [`WriteBuilder`] for more information.
+    pub fn transfer_to<'a, D, E>(
+        &'a mut self,
+        buffer: &'a [E],
+        destination: &'a mut D,
+    ) -> WriteBuilder<'a, D, E>
+    where
+        D: Destination<E>,
+        E: Element,
+    {
+        WriteBuilder {
+            channel: self,
+            buffer,
+            destination,
+            bus: None,
+        }
+    }
+}
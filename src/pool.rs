@@ -0,0 +1,322 @@
+//! Static pools of transfer control descriptors.
+//!
+//! Scatter / gather transfers chain descriptors in memory. The DMA engine loads the
+//! next descriptor when a transfer's major loop completes. A [`TcdPool`] holds a fixed
+//! number of correctly-aligned descriptors, so you can build chains without a heap.
+//!
+//! [`lease`](TcdPool::lease) a descriptor from the pool. A [`Lease`] borrows the pool,
+//! so the pool outlives every descriptor in a chain. Dropping the lease returns the
+//! descriptor to the pool.
+//!
+//! Capture each transfer from a channel's configuration, link the descriptors, then
+//! load the first descriptor into the channel.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::{self, Channel}, pool::TcdPool, Transfer};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # async fn f() -> imxrt_dma::Result<()> {
+//!
+//! static POOL: TcdPool<4> = TcdPool::new();
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let first = [1u32, 2, 3, 4];
+//! let second = [5u32, 6, 7, 8];
+//! let mut destination = [0u32; 8];
+//!
+//! let mut head = POOL.lease();
+//! let mut tail = POOL.lease();
+//! unsafe {
+//!     channel.set_channel_configuration(channel::Configuration::Off);
+//!     channel.set_disable_on_completion(true);
+//!     channel.set_interrupt_on_completion(true);
+//!     let (copy, _) = destination.split_at_mut(4);
+//!     channel::set_source_linear_buffer(&mut channel, &first);
+//!     channel::set_destination_linear_buffer(&mut channel, copy);
+//!     channel.set_minor_loop_bytes(16);
+//!     channel.set_transfer_iterations(1);
+//!     head.capture(&channel);
+//!
+//!     let (_, copy) = destination.split_at_mut(4);
+//!     channel::set_source_linear_buffer(&mut channel, &second);
+//!     channel::set_destination_linear_buffer(&mut channel, copy);
+//!     tail.capture(&channel);
+//!
+//!     head.link(&tail);
+//!     head.load(&mut channel);
+//! }
+//!
+//! let transfer = unsafe { Transfer::new(&channel) };
+//! channel.start();
+//! transfer.await?;
+//! # Ok(()) }
+//! ```
+
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    channel::Channel,
+    ral::tcd::{Descriptor, CSR, NBYTES},
+    tcd::Tcd,
+    ConfigError,
+};
+
+#[allow(clippy::declare_interior_mutable_const)] // Only used to initialize arrays.
+const UNUSED: UnsafeCell<Descriptor> = UnsafeCell::new(Descriptor::EMPTY);
+#[allow(clippy::declare_interior_mutable_const)] // Only used to initialize arrays.
+const FREE: AtomicBool = AtomicBool::new(false);
+
+/// A fixed-size pool of transfer control descriptors
+///
+/// Each descriptor is aligned to 32 bytes, as required by the DMA engine. Assign the
+/// pool to a static, or keep it on the stack for as long as its chains run.
+pub struct TcdPool<const N: usize> {
+    descriptors: [UnsafeCell<Descriptor>; N],
+    leased: [AtomicBool; N],
+}
+
+// Safety: a descriptor is only accessed through its lease, and leases
+// are exclusive.
+unsafe impl<const N: usize> Sync for TcdPool<N> {}
+
+impl<const N: usize> TcdPool<N> {
+    /// Create a pool of `N` descriptors
+    pub const fn new() -> Self {
+        TcdPool {
+            descriptors: [UNUSED; N],
+            leased: [FREE; N],
+        }
+    }
+
+    /// Lease a descriptor from the pool
+    ///
+    /// # Panics
+    ///
+    /// Panics if all descriptors are leased. See [`try_lease`](Self::try_lease) for
+    /// a non-panicking version.
+    pub fn lease(&self) -> Lease<'_> {
+        self.try_lease().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Lease a descriptor from the pool
+    ///
+    /// Returns [`ConfigError::PoolExhausted`] if all descriptors are leased.
+    ///
+    /// ```
+    /// use imxrt_dma::{pool::TcdPool, ConfigError};
+    ///
+    /// static POOL: TcdPool<1> = TcdPool::new();
+    ///
+    /// let lease = POOL.try_lease().unwrap();
+    /// assert_eq!(POOL.try_lease().err(), Some(ConfigError::PoolExhausted));
+    ///
+    /// drop(lease);
+    /// assert_eq!(POOL.available(), 1);
+    /// ```
+    pub fn try_lease(&self) -> Result<Lease<'_>, ConfigError> {
+        self.leased
+            .iter()
+            .zip(self.descriptors.iter())
+            .find(|(leased, _)| !leased.swap(true, Ordering::Acquire))
+            .map(|(leased, descriptor)| {
+                // Safety: the flag was clear, so no other lease references
+                // this descriptor.
+                let descriptor = unsafe { &mut *descriptor.get() };
+                *descriptor = Descriptor::EMPTY;
+                Lease { descriptor, leased }
+            })
+            .ok_or(ConfigError::PoolExhausted)
+    }
+
+    /// Returns the number of descriptors available for lease
+    pub fn available(&self) -> usize {
+        self.leased
+            .iter()
+            .filter(|leased| !leased.load(Ordering::Relaxed))
+            .count()
+    }
+}
+
+impl<const N: usize> Default for TcdPool<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A descriptor leased from a [`TcdPool`]
+///
+/// Dropping the lease returns the descriptor to its pool.
+pub struct Lease<'a> {
+    descriptor: &'a mut Descriptor,
+    leased: &'a AtomicBool,
+}
+
+impl<'a> Lease<'a> {
+    /// Copy the transfer configured on `channel` into the descriptor
+    ///
+    /// The descriptor doesn't link to another descriptor. Use [`link`](Self::link)
+    /// to chain another descriptor after this one.
+    pub fn capture(&mut self, channel: &Channel) {
+        self.store(&Tcd::capture(channel));
+    }
+
+    /// Copy `tcd` into the descriptor
+    ///
+    /// The descriptor doesn't link to another descriptor.
+    pub fn store(&mut self, tcd: &Tcd) {
+        *self.descriptor = *tcd.descriptor();
+        self.unlink();
+    }
+
+    /// Enable or disable interrupt generation when this descriptor's transfer completes
+    ///
+    /// Use this to be notified after specific segments of a chain.
+    pub fn set_interrupt_on_completion(&mut self, intr: bool) {
+        if intr {
+            self.descriptor.CSR |= CSR::INTMAJOR::mask;
+        } else {
+            self.descriptor.CSR &= !CSR::INTMAJOR::mask;
+        }
+    }
+
+    /// Load `next` once this descriptor's major loop completes
+    ///
+    /// The link replaces this descriptor's destination last address adjustment.
+    pub fn link(&mut self, next: &Lease<'a>) {
+        self.descriptor.DLAST_SGA = next.address() as i32;
+        self.descriptor.CSR |= CSR::ESG::mask;
+    }
+
+    /// Remove the link to the next descriptor
+    pub fn unlink(&mut self) {
+        self.descriptor.DLAST_SGA = 0;
+        self.descriptor.CSR &= !CSR::ESG::mask;
+    }
+
+    /// Returns the address of the descriptor
+    pub fn address(&self) -> *const () {
+        (self.descriptor as *const Descriptor).cast()
+    }
+
+    /// Load the descriptor into the channel's transfer control descriptor
+    ///
+    /// The channel runs this descriptor's transfer, then any transfers linked
+    /// after it. If the descriptor uses a minor loop offset, this enables minor loop
+    /// mapping for the whole controller.
+    ///
+    /// # Safety
+    ///
+    /// The DMA engine reads every linked descriptor while the chain runs. You must
+    /// ensure that every lease in the chain lives until the transfer completes or is
+    /// canceled. Each descriptor must describe a valid transfer.
+    pub unsafe fn load(&self, channel: &mut Channel) {
+        if self.descriptor.NBYTES & (NBYTES::SMLOE::mask | NBYTES::DMLOE::mask) != 0 {
+            channel.enable_minor_loop_mapping();
+        }
+        // DONE must be clear before enabling scatter / gather.
+        channel.clear_complete();
+        channel.restore(self.descriptor);
+    }
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        self.leased.store(false, Ordering::Release);
+    }
+}
+
+/// A heap-allocated chain of descriptors
+///
+/// Use a `Chain` when the number of transfers isn't known until runtime. The
+/// descriptors are allocated once, and they don't move when the chain moves.
+/// [`load`](Chain::load) links each descriptor to the next. A `Chain` dereferences
+/// to its [`Tcd`]s, so you can configure each descriptor in place.
+///
+/// This type requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub struct Chain {
+    tcds: alloc::boxed::Box<[Tcd]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Chain {
+    /// Allocate a chain of `len` descriptors
+    pub fn new(len: usize) -> Self {
+        Chain {
+            tcds: alloc::vec![Tcd::new(); len].into_boxed_slice(),
+        }
+    }
+
+    /// Copy the transfer configured on `channel` into the descriptor at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn capture(&mut self, index: usize, channel: &Channel) {
+        self.tcds[index] = Tcd::capture(channel);
+    }
+
+    /// Copy `tcd` into the descriptor at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn store(&mut self, index: usize, tcd: &Tcd) {
+        self.tcds[index] = *tcd;
+    }
+
+    /// Link the descriptors, and load the first descriptor into the channel's
+    /// transfer control descriptor
+    ///
+    /// The channel runs each descriptor's transfer in order. The last descriptor
+    /// doesn't link to another descriptor. Descriptors without transfer iterations
+    /// are skipped. Returns `false`, and leaves the channel untouched, if every
+    /// descriptor is empty; there's nothing to run.
+    ///
+    /// ```
+    /// use imxrt_dma::pool::Chain;
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # let mut channel = unsafe { DMA.channel(7) };
+    ///
+    /// let mut chain = Chain::new(2);
+    /// // Neither descriptor describes a transfer.
+    /// assert!(!unsafe { chain.load(&mut channel) });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chain is empty.
+    ///
+    /// # Safety
+    ///
+    /// The DMA engine reads the descriptors while the chain runs. You must ensure that
+    /// the chain isn't dropped or modified until the transfer completes or is canceled.
+    /// Each descriptor must describe a valid transfer.
+    pub unsafe fn load(&mut self, channel: &mut Channel) -> bool {
+        match crate::tcd::link(&mut self.tcds) {
+            Some(first) => {
+                self.tcds[first].apply(channel);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::ops::Deref for Chain {
+    type Target = [Tcd];
+    fn deref(&self) -> &[Tcd] {
+        &self.tcds
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::ops::DerefMut for Chain {
+    fn deref_mut(&mut self) -> &mut [Tcd] {
+        &mut self.tcds
+    }
+}
@@ -0,0 +1,135 @@
+//! Save and restore a channel's configuration.
+//!
+//! Firmware that switches between operating modes often reprograms the same
+//! channels, in the same ways, over and over. A [`Profile`] records everything the
+//! channel needs for one mode: the DMAMUX configuration, the channel's priority,
+//! its error handling, and a template of the transfer control descriptor. Capture
+//! a profile once the channel is configured, and apply it when the firmware enters
+//! that mode again.
+//!
+//! `Profile` is plain data. Its fields are public, so you can also build profiles
+//! by hand, or keep them in constants.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, profile::Profile};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! // Configure the channel for a low-power sampling mode...
+//! let sampling = channel.capture_profile();
+//! // Configure the channel for a high-rate streaming mode...
+//! let streaming = channel.capture_profile();
+//!
+//! // Return to sampling.
+//! // Safety: the sampling transfer's buffers are still valid.
+//! unsafe { channel.apply_profile(&sampling) };
+//! # let _ = streaming;
+//! ```
+
+use crate::{
+    arbitration::ChannelPriority,
+    channel::{Channel, Configuration},
+    ral::dma::DCHPRI,
+    retry::RetryPolicy,
+    tcd::Tcd,
+    ConfigError,
+};
+
+/// A channel's complete logical configuration
+///
+/// See the [module documentation](crate::profile) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct Profile {
+    /// How the DMAMUX triggers the channel, and from which source
+    pub configuration: Configuration,
+    /// The channel's priority settings
+    ///
+    /// The group priority belongs to the controller, so applying a profile
+    /// ignores it.
+    pub priority: ChannelPriority,
+    /// How transfers retry after bus errors
+    pub retry: Option<RetryPolicy>,
+    /// The channel signals the error interrupt
+    pub interrupt_on_error: bool,
+    /// The transfer control descriptor template
+    pub tcd: Tcd,
+}
+
+impl Channel {
+    /// Capture the channel's configuration in a profile
+    ///
+    /// The profile's descriptor is the channel's descriptor as it is now. Capture
+    /// the profile before the channel runs the transfer, so that the descriptor
+    /// describes the whole transfer.
+    pub fn capture_profile(&self) -> Profile {
+        Profile {
+            configuration: self.channel_configuration(),
+            priority: ChannelPriority::from_register(
+                self.controller().DCHPRI[self.channel()].read(),
+            ),
+            retry: self.retry_policy(),
+            interrupt_on_error: self.interrupt_on_error(),
+            tcd: Tcd::capture(self),
+        }
+    }
+
+    /// Reconfigure the channel with `profile`
+    ///
+    /// `apply_profile` disables the channel, and waits for any active minor loop to
+    /// finish. It clears the channel's completion and error flags before it applies
+    /// the profile. The channel stays disabled.
+    ///
+    /// Under fixed arbitration, each channel in a group needs a unique priority.
+    /// `apply_profile` doesn't check the priorities of other channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the profile's priority is greater than 15, or if the channel doesn't
+    /// support the profile's DMAMUX configuration. See [`try_apply_profile`](Self::try_apply_profile)
+    /// for a non-panicking version.
+    ///
+    /// # Safety
+    ///
+    /// The profile's descriptor must describe a valid transfer. See
+    /// [`Tcd::apply`] for more information.
+    pub unsafe fn apply_profile(&mut self, profile: &Profile) {
+        self.try_apply_profile(profile)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Reconfigure the channel with `profile`, or return an error if the channel
+    /// can't take the profile
+    ///
+    /// Returns [`ConfigError::InvalidPriority`] or [`ConfigError::PeriodicUnsupported`]
+    /// before it touches the channel. See [`apply_profile`](Self::apply_profile) for
+    /// more information.
+    ///
+    /// # Safety
+    ///
+    /// See [`apply_profile`](Self::apply_profile).
+    pub unsafe fn try_apply_profile(&mut self, profile: &Profile) -> Result<(), ConfigError> {
+        if profile.priority.priority > DCHPRI::CHPRI::mask >> DCHPRI::CHPRI::offset {
+            return Err(ConfigError::InvalidPriority);
+        }
+        let periodic = matches!(
+            profile.configuration,
+            Configuration::Enable { periodic: true, .. } | Configuration::AlwaysOnPeriodic
+        );
+        if periodic && !self.supports_periodic_trigger() {
+            return Err(ConfigError::PeriodicUnsupported);
+        }
+
+        self.disable();
+        while self.is_active() {}
+        self.clear_complete();
+        self.clear_error();
+
+        self.try_set_channel_configuration(profile.configuration)?;
+        self.controller().DCHPRI[self.channel()].write(profile.priority.to_register());
+        self.set_retry_policy(profile.retry);
+        self.set_interrupt_on_error(profile.interrupt_on_error);
+        profile.tcd.apply(self);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 //! DMA register blocks and fields
 
+#![allow(non_upper_case_globals)]
+
 use super::{tcd, RORegister, RWRegister, WORegister};
 
 use core::ops::Index;
@@ -75,3 +77,153 @@ impl Index<usize> for ChannelPriorityRegisters {
         &self.0[idx]
     }
 }
+
+pub mod CR {
+    /// Enable Minor Loop Mapping
+    pub mod EMLM {
+        /// Offset (7 bits)
+        pub const offset: u32 = 7;
+        /// Mask (1 bit: 1 << 7)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Halt DMA Operations
+    pub mod HALT {
+        /// Offset (5 bits)
+        pub const offset: u32 = 5;
+        /// Mask (1 bit: 1 << 5)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// DMA Active Status
+    pub mod ACTIVE {
+        /// Offset (31 bits)
+        pub const offset: u32 = 31;
+        /// Mask (1 bit: 1 << 31)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Enable Round Robin Channel Arbitration
+    pub mod ERCA {
+        /// Offset (2 bits)
+        pub const offset: u32 = 2;
+        /// Mask (1 bit: 1 << 2)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Enable Round Robin Group Arbitration
+    pub mod ERGA {
+        /// Offset (3 bits)
+        pub const offset: u32 = 3;
+        /// Mask (1 bit: 1 << 3)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Channel Group 0 Priority
+    pub mod GRP0PRI {
+        /// Offset (8 bits)
+        pub const offset: u32 = 8;
+        /// Mask (1 bit: 1 << 8)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Channel Group 1 Priority
+    pub mod GRP1PRI {
+        /// Offset (10 bits)
+        pub const offset: u32 = 10;
+        /// Mask (1 bit: 1 << 10)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
+/// Channel Priority Register fields
+pub mod DCHPRI {
+    /// Channel Arbitration Priority
+    pub mod CHPRI {
+        /// Offset (0 bits)
+        pub const offset: u8 = 0;
+        /// Mask (4 bits: 0b1111 << 0)
+        pub const mask: u8 = 0b1111 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Channel Current Group Priority
+    pub mod GRPPRI {
+        /// Offset (4 bits)
+        pub const offset: u8 = 4;
+        /// Mask (2 bits: 0b11 << 4)
+        pub const mask: u8 = 0b11 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Disable Preempt Ability
+    pub mod DPA {
+        /// Offset (6 bits)
+        pub const offset: u8 = 6;
+        /// Mask (1 bit: 1 << 6)
+        pub const mask: u8 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+    /// Enable Channel Preemption
+    pub mod ECP {
+        /// Offset (7 bits)
+        pub const offset: u8 = 7;
+        /// Mask (1 bit: 1 << 7)
+        pub const mask: u8 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
@@ -43,8 +43,87 @@ impl RegisterBlock {
         self.CSR.write(0);
         self.BITER.write(0);
     }
+
+    /// Copy the TCD into memory
+    pub fn snapshot(&self) -> Descriptor {
+        Descriptor {
+            SADDR: self.SADDR.read(),
+            SOFF: self.SOFF.read(),
+            DATTR: self.DATTR.read(),
+            SATTR: self.SATTR.read(),
+            NBYTES: self.NBYTES.read(),
+            SLAST: self.SLAST.read(),
+            DADDR: self.DADDR.read(),
+            DOFF: self.DOFF.read(),
+            CITER: self.CITER.read(),
+            DLAST_SGA: self.DLAST_SGA.read(),
+            CSR: self.CSR.read(),
+            BITER: self.BITER.read(),
+        }
+    }
+
+    /// Restore a TCD from a [`snapshot`](Self::snapshot)
+    ///
+    /// The status bits of the control and status register are cleared.
+    pub fn restore(&self, descriptor: &Descriptor) {
+        self.SADDR.write(descriptor.SADDR);
+        self.SOFF.write(descriptor.SOFF);
+        self.DATTR.write(descriptor.DATTR);
+        self.SATTR.write(descriptor.SATTR);
+        self.NBYTES.write(descriptor.NBYTES);
+        self.SLAST.write(descriptor.SLAST);
+        self.DADDR.write(descriptor.DADDR);
+        self.DOFF.write(descriptor.DOFF);
+        self.CITER.write(descriptor.CITER);
+        self.DLAST_SGA.write(descriptor.DLAST_SGA);
+        self.BITER.write(descriptor.BITER);
+        self.CSR
+            .write(descriptor.CSR & !(CSR::START::mask | CSR::ACTIVE::mask | CSR::DONE::mask));
+    }
 }
 
+/// A transfer control descriptor in memory
+///
+/// The layout matches the [`RegisterBlock`]. The DMA engine loads a `Descriptor`
+/// into a channel's TCD when scatter / gather is enabled.
+#[repr(C, align(32))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Descriptor {
+    pub SADDR: u32,
+    pub SOFF: i16,
+    pub DATTR: u8,
+    pub SATTR: u8,
+    pub NBYTES: u32,
+    pub SLAST: i32,
+    pub DADDR: u32,
+    pub DOFF: i16,
+    pub CITER: u16,
+    pub DLAST_SGA: i32,
+    pub CSR: u16,
+    pub BITER: u16,
+}
+
+impl Descriptor {
+    /// A descriptor with all fields cleared
+    pub const EMPTY: Self = Descriptor {
+        SADDR: 0,
+        SOFF: 0,
+        DATTR: 0,
+        SATTR: 0,
+        NBYTES: 0,
+        SLAST: 0,
+        DADDR: 0,
+        DOFF: 0,
+        CITER: 0,
+        DLAST_SGA: 0,
+        CSR: 0,
+        BITER: 0,
+    };
+}
+
+const _STATIC_ASSERT_DESCRIPTOR_32_BYTES: [u32; 1] =
+    [0; (32 == core::mem::size_of::<Descriptor>()) as usize];
+
 mod ATTR {
     /// Destination data transfer size
     pub mod SIZE {
@@ -85,6 +164,34 @@ pub mod SATTR {
 
 pub mod CSR {
 
+    /// Channel Start
+    pub mod START {
+        /// Offset (0 bits)
+        pub const offset: u16 = 0;
+        /// Mask (1 bit: 1 << 0)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Enable Scatter/Gather Processing
+    pub mod ESG {
+        /// Offset (4 bits)
+        pub const offset: u16 = 4;
+        /// Mask (1 bit: 1 << 4)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
     /// Enable an interrupt when major iteration count completes.
     pub mod INTMAJOR {
         /// Offset (1 bits)
@@ -99,6 +206,20 @@ pub mod CSR {
         pub mod RW {}
     }
 
+    /// Enable an interrupt when major counter is half complete.
+    pub mod INTHALF {
+        /// Offset (2 bits)
+        pub const offset: u16 = 2;
+        /// Mask (1 bit: 1 << 2)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values
+        pub mod RW {}
+    }
+
     /// Disable Request
     pub mod DREQ {
         /// Offset (3 bits)
@@ -151,6 +272,34 @@ pub mod CSR {
         }
     }
 
+    /// Enable channel-to-channel linking on major loop complete
+    pub mod MAJORELINK {
+        /// Offset (5 bits)
+        pub const offset: u16 = 5;
+        /// Mask (1 bit: 1 << 5)
+        pub const mask: u16 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Major Loop Link Channel Number
+    pub mod MAJORLINKCH {
+        /// Offset (8 bits)
+        pub const offset: u16 = 8;
+        /// Mask (5 bits: 0x1f << 8)
+        pub const mask: u16 = 0x1f << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
     /// Channel Active
     pub mod ACTIVE {
         /// Offset (6 bits)
@@ -166,6 +315,69 @@ pub mod CSR {
     }
 }
 
+/// Minor loop byte count, with minor loop mapping enabled
+///
+/// The minor loop offset fields are only valid when the controller
+/// enables minor loop mapping, and at least one of `SMLOE` and
+/// `DMLOE` is set.
+pub mod NBYTES {
+    /// Source Minor Loop Offset Enable
+    pub mod SMLOE {
+        /// Offset (31 bits)
+        pub const offset: u32 = 31;
+        /// Mask (1 bit: 1 << 31)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Destination Minor Loop Offset Enable
+    pub mod DMLOE {
+        /// Offset (30 bits)
+        pub const offset: u32 = 30;
+        /// Mask (1 bit: 1 << 30)
+        pub const mask: u32 = 1 << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Minor Loop Offset
+    pub mod MLOFF {
+        /// Offset (10 bits)
+        pub const offset: u32 = 10;
+        /// Mask (20 bits: 0xfffff << 10)
+        pub const mask: u32 = 0xfffff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+
+    /// Minor Byte Transfer Count
+    pub mod NBYTES {
+        /// Offset (0 bits)
+        pub const offset: u32 = 0;
+        /// Mask (10 bits: 0x3ff << 0)
+        pub const mask: u32 = 0x3ff << offset;
+        /// Read-only values (empty)
+        pub mod R {}
+        /// Write-only values (empty)
+        pub mod W {}
+        /// Read-write values (empty)
+        pub mod RW {}
+    }
+}
+
 pub mod CITER {
     /// Current Major Iteration Count
     pub mod CITER {
@@ -175,6 +387,8 @@ pub mod CITER {
         pub mod W {}
         pub mod RW {}
     }
+
+    pub use super::ELINK::*;
 }
 
 pub mod BITER {
@@ -186,6 +400,38 @@ pub mod BITER {
         pub mod W {}
         pub mod RW {}
     }
+
+    pub use super::ELINK::*;
+}
+
+/// Fields shared by CITER and BITER when minor loop linking is enabled
+mod ELINK {
+    /// Enable channel-to-channel linking on minor-loop complete
+    pub mod ELINK {
+        pub const offset: u16 = 15;
+        pub const mask: u16 = 1 << offset;
+        pub mod R {}
+        pub mod W {}
+        pub mod RW {}
+    }
+
+    /// Minor Loop Link Channel Number
+    pub mod LINKCH {
+        pub const offset: u16 = 9;
+        pub const mask: u16 = 0x1f << offset;
+        pub mod R {}
+        pub mod W {}
+        pub mod RW {}
+    }
+
+    /// Iteration count, when minor loop linking is enabled
+    pub mod ITER_ELINKYES {
+        pub const offset: u16 = 0;
+        pub const mask: u16 = 0x1ff << offset;
+        pub mod R {}
+        pub mod W {}
+        pub mod RW {}
+    }
 }
 
 /// Throttles the amount of bus bandwidth consumed by the eDMA
@@ -196,6 +442,8 @@ pub mod BITER {
 /// Some stalls may not occur to minimize startup latency. See the
 /// reference manual for more details.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u16)]
 pub enum BandwidthControl {
     /// DMA engine stalls for 4 cycles after each R/W.
@@ -0,0 +1,285 @@
+//! Reshape elements with a pair of linked channels.
+//!
+//! A single DMA channel reads and writes with one pair of offsets and sizes. Some
+//! transformations need two: a byte swap reads each element forward, and writes
+//! it backward. A [`ReshapeTransfer`] runs two channels in lockstep. The first
+//! channel copies one element from the source into a small [`Bounce`] buffer. It
+//! links to the second channel, which copies the element out of the bounce buffer
+//! with the offsets and sizes of the [`Reshape`]. The second channel links back to
+//! the first channel for the next element. The CPU does no work until the last
+//! element lands.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, reshape::{self, Bounce, Reshape}};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # async fn f() -> imxrt_dma::Result<()> {
+//! let mut channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let mut channel_8: Channel = // DMA channel 8
+//!     # unsafe { DMA.channel(8) };
+//!
+//! // Convert big-endian samples from a network packet.
+//! let packet = [0x1234_5678u32, 0x9ABC_DEF0];
+//! let mut samples = [0u32; 2];
+//! let mut bounce = Bounce::new();
+//! reshape::reshape(
+//!     &packet,
+//!     &mut samples,
+//!     Reshape::SwapBytes,
+//!     &mut bounce,
+//!     &mut channel_7,
+//!     &mut channel_8,
+//! )
+//! .await?;
+//! assert_eq!(samples, [0x7856_3412, 0xF0DE_BC9A]);
+//! # Ok(()) }
+//! ```
+//!
+//! Channel links limit a reshape to 511 elements. The second channel signals the
+//! transfer's completion; if you use interrupts, configure the second channel to
+//! interrupt on completion, and route its interrupt handler to
+//! [`on_interrupt()`](crate::Dma::on_interrupt).
+
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    cache,
+    channel::{Channel, Configuration, MinorLoopOffset},
+    interrupt::Transfer,
+    tcd::Tcd,
+    Element, Error,
+};
+
+/// How the second channel writes each element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Reshape {
+    /// Reverse the bytes of each element
+    ///
+    /// Converts between little-endian and big-endian elements.
+    SwapBytes,
+    /// Read every `source`-th element, and write every `destination`-th element
+    ///
+    /// Use this to pick one channel out of interleaved samples, or to spread
+    /// packed samples into an interleaved frame. The elements in between are
+    /// untouched.
+    Stride {
+        /// The distance between source elements, in elements
+        source: usize,
+        /// The distance between destination elements, in elements
+        destination: usize,
+    },
+}
+
+/// Holds one element between the two channels of a reshape
+///
+/// The bounce buffer must stay in place while the reshape runs. The reshape
+/// borrows it to guarantee this.
+///
+/// Only the DMA engine reads and writes the bounce buffer, so the data cache must
+/// not hold any of its lines while the reshape runs. The bounce buffer fills one
+/// cache line. With the `cache` feature, the reshape cleans and invalidates that
+/// line before it starts. Otherwise, place the bounce buffer in memory that isn't
+/// cached, like DTCM, or [`clean_invalidate`](cache::clean_invalidate) it before each
+/// reshape.
+#[derive(Debug, Default)]
+#[repr(C, align(32))]
+pub struct Bounce([u8; cache::LINE_SIZE]);
+
+impl Bounce {
+    /// Create a bounce buffer
+    pub const fn new() -> Self {
+        Bounce([0; cache::LINE_SIZE])
+    }
+}
+
+/// A reshaping DMA transfer
+///
+/// `ReshapeTransfer` yields once the second channel writes the last element. Use
+/// [`reshape`] to define the transfer.
+pub struct ReshapeTransfer<'a, E> {
+    first: &'a mut Channel,
+    second: &'a Channel,
+    transfer: Transfer<'a>,
+    started: bool,
+    cache: cache::Destination,
+    _elem: PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Copy `source` into `destination` through `bounce`, reshaping each element
+///
+/// `first` reads the source, and `second` writes the destination. The reshape moves
+/// as many elements as both buffers hold, in the shape's strides.
+///
+/// # Panics
+///
+/// Panics if both channels are the same channel, if a stride is zero, if a stride
+/// spans more than 32767 bytes, or if the reshape would move more than 511
+/// elements.
+pub fn reshape<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    shape: Reshape,
+    bounce: &'a mut Bounce,
+    first: &'a mut Channel,
+    second: &'a mut Channel,
+) -> ReshapeTransfer<'a, E> {
+    assert_ne!(
+        first.channel(),
+        second.channel(),
+        "DMA reshape needs two channels"
+    );
+    let (source_stride, destination_stride) = match shape {
+        Reshape::SwapBytes => (1, 1),
+        Reshape::Stride {
+            source,
+            destination,
+        } => (source, destination),
+    };
+    let size = core::mem::size_of::<E>();
+    assert!(
+        [source_stride, destination_stride]
+            .iter()
+            .all(|&stride| stride > 0 && stride * size <= i16::MAX as usize),
+        "DMA reshape stride is out of range"
+    );
+    let elements = source
+        .len()
+        .div_ceil(source_stride)
+        .min(destination.len().div_ceil(destination_stride));
+    assert!(elements <= 511, "DMA reshape is too large to link");
+    if elements == 0 {
+        return ReshapeTransfer {
+            transfer: Transfer::empty(second),
+            second,
+            first,
+            started: true,
+            cache: cache::Destination::none(),
+            _elem: PhantomData,
+        };
+    }
+
+    // A dirty line evicted during the reshape would overwrite the element in flight.
+    #[cfg(feature = "cache")]
+    cache::clean_invalidate(&mut bounce.0);
+    let bounce: *mut u8 = bounce.0.as_mut_ptr();
+    let interrupt = Tcd::capture(second).interrupt_on_completion();
+    for channel in [&mut *first, &mut *second] {
+        channel.disable();
+        while channel.is_active() {}
+        channel.reset();
+        channel.clear_complete();
+        channel.clear_error();
+        channel.set_channel_configuration(Configuration::Off);
+        channel.set_disable_on_completion(true);
+    }
+    second.set_interrupt_on_completion(interrupt);
+
+    // Safety: both channels move `elements` elements within the borrowed buffers,
+    // and the bounce buffer holds an element. Each channel starts only the other
+    // channel, which describes its half of the reshape.
+    unsafe {
+        first.set_source_address(source.as_ptr());
+        first.set_source_offset((source_stride * size) as i16);
+        first.set_source_attributes::<E>(0);
+        first.set_destination_address(bounce.cast_const().cast::<E>());
+        first.set_destination_offset(0);
+        first.set_destination_attributes::<E>(0);
+        first.set_minor_loop_bytes(size as u32);
+        first.set_transfer_iterations(elements as u16);
+        first.set_minor_loop_link(Some(second.channel()));
+        first.set_major_loop_link(Some(second.channel()));
+
+        match shape {
+            Reshape::SwapBytes => {
+                // Read the bounce buffer backward, a byte at a time, and return
+                // to its last byte after each element.
+                second.set_source_address(bounce.add(size - 1).cast_const());
+                second.set_source_offset(-1);
+                second.set_source_attributes::<u8>(0);
+                second.set_destination_address(destination.as_ptr().cast::<u8>());
+                second.set_destination_offset(1);
+                second.set_destination_attributes::<u8>(0);
+                second.set_minor_loop_bytes_with_offset(
+                    size as u32,
+                    MinorLoopOffset::Source(size as i32),
+                );
+            }
+            Reshape::Stride { .. } => {
+                second.set_source_address(bounce.cast_const().cast::<E>());
+                second.set_source_offset(0);
+                second.set_source_attributes::<E>(0);
+                second.set_destination_address(destination.as_ptr());
+                second.set_destination_offset((destination_stride * size) as i16);
+                second.set_destination_attributes::<E>(0);
+                second.set_minor_loop_bytes(size as u32);
+            }
+        }
+        second.set_transfer_iterations(elements as u16);
+        // The link doesn't run after the last element, so the first channel stops.
+        second.set_minor_loop_link(Some(first.channel()));
+    }
+
+    cache::prepare_source(source);
+    ReshapeTransfer {
+        cache: cache::Destination::prepare(destination),
+        // Safety: transfer is properly prepared. The second channel only runs
+        // when the first channel links to it.
+        transfer: unsafe { Transfer::new(second) },
+        second,
+        first,
+        started: false,
+        _elem: PhantomData,
+    }
+}
+
+impl<E> Future for ReshapeTransfer<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.first.is_error() {
+            let es = this.first.error_status();
+            this.first.clear_error();
+            return Poll::Ready(Err(es.into()));
+        }
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        let poll = transfer.poll(cx);
+        // Start the first element once. The channels start each other for the
+        // rest of the elements.
+        if poll.is_pending() && !this.started {
+            this.first.start();
+            this.started = true;
+        }
+        if let Poll::Ready(Ok(())) = poll {
+            this.cache.complete();
+        }
+        poll
+    }
+}
+
+impl<E> Drop for ReshapeTransfer<'_, E> {
+    fn drop(&mut self) {
+        if self.transfer.is_empty() {
+            return;
+        }
+        // Safety: removes the first channel's links, so it can't start the second
+        // channel again.
+        unsafe {
+            self.first.set_minor_loop_link(None);
+            self.first.set_major_loop_link(None);
+        }
+        // The second channel may finish an element, and start the first channel
+        // once more. Without links, the first channel stops after that element.
+        for channel in [&*self.first, self.second, &*self.first] {
+            while channel.is_active() || channel.is_trigger_pending() {}
+        }
+        // Drop `transfer` to finish cancellation...
+    }
+}
@@ -0,0 +1,145 @@
+//! Automatically retry transfers after bus errors.
+//!
+//! A bus error may be transient; for instance, an external memory might not be ready.
+//! Attach a [`RetryPolicy`] to a channel with
+//! [`set_retry_policy`](crate::channel::Channel::set_retry_policy), and the channel's
+//! transfer futures will restart the transfer when they observe a source or destination
+//! bus error. Once the policy runs out of attempts, the future resolves with the final
+//! error.
+//!
+//! Retries only apply to bus errors. The futures report configuration errors
+//! immediately, since retrying the same configuration would fail again.
+//!
+//! To decide for yourself, or to resume on another channel, let the future report
+//! the error. Then, [`capture`](crate::tcd::Tcd::capture) the channel's progress,
+//! and apply it to a channel.
+//!
+//! To wait before each retry, give the policy a [`Backoff`] hook and a clock. The
+//! future doesn't block while it waits; it returns `Poll::Pending`, and re-arms the
+//! transfer on a later poll.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, retry::{Resume, RetryPolicy}};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//!
+//! /// Wait 1000 ticks before the first retry, and double the wait each time.
+//! fn backoff(attempt: u32, _: imxrt_dma::ErrorStatus) -> u64 {
+//!     1_000 << attempt
+//! }
+//!
+//! fn now() -> u64 {
+//!     // Read your free-running timer...
+//!     # 0
+//! }
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! channel.set_retry_policy(Some(
+//!     RetryPolicy::new(3)
+//!         .resume(Resume::Progress)
+//!         .backoff(backoff, now),
+//! ));
+//! ```
+
+use crate::ErrorStatus;
+
+/// Where a retried transfer restarts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Resume {
+    /// Restart the transfer from the beginning
+    ///
+    /// The future records the channel's transfer before it first enables the channel,
+    /// and restores that transfer before each retry. This is the default.
+    Beginning,
+    /// Restart the transfer from the progress recorded in the channel
+    ///
+    /// The transfer continues with the addresses and iterations that the hardware
+    /// stored when it observed the error. Elements of the failed minor loop may be
+    /// transferred again.
+    Progress,
+}
+
+/// A hook called before each retry
+///
+/// The hook receives the retry attempt, starting at 1, and the error that
+/// caused the retry. It returns the number of clock ticks to wait before the
+/// retry. Use the hook to log the error, too. The hook runs while the future is
+/// polled, so it should return quickly.
+pub type Backoff = fn(attempt: u32, status: ErrorStatus) -> u64;
+
+/// Returns the current time, in ticks
+///
+/// The clock must not wrap. A 64-bit tick count of a free-running timer works.
+pub type Now = fn() -> u64;
+
+/// Describes how a transfer future retries after bus errors
+///
+/// See the [module documentation](crate::retry) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    retries: u32,
+    resume: Resume,
+    backoff: Option<(Backoff, Now)>,
+}
+
+impl RetryPolicy {
+    /// Retry a transfer at most `retries` times
+    ///
+    /// By default, the transfer restarts from the beginning without any backoff.
+    pub const fn new(retries: u32) -> Self {
+        RetryPolicy {
+            retries,
+            resume: Resume::Beginning,
+            backoff: None,
+        }
+    }
+
+    /// Set where a retried transfer restarts
+    pub const fn resume(mut self, resume: Resume) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Call `backoff` before each retry, and wait the ticks it returns
+    ///
+    /// `now` measures the wait. While it waits, the future returns `Poll::Pending`,
+    /// and wakes its task so that it's polled again. A transfer driven by
+    /// [`poll_complete`](crate::Transfer::poll_complete) re-arms on the first call
+    /// after the wait.
+    pub const fn backoff(mut self, backoff: Backoff, now: Now) -> Self {
+        self.backoff = Some((backoff, now));
+        self
+    }
+
+    /// Returns the maximum number of retries
+    pub const fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns where a retried transfer restarts
+    pub const fn resume_from(&self) -> Resume {
+        self.resume
+    }
+
+    /// Returns `true` if the policy should retry after `status`, having already
+    /// retried `attempts` times
+    #[cfg(feature = "async")]
+    pub(crate) fn should_retry(&self, attempts: u32, status: ErrorStatus) -> bool {
+        attempts < self.retries && (status.is_source_bus() || status.is_destination_bus())
+    }
+
+    /// Call the backoff hook, if there is one, and return the time of the retry
+    #[cfg(feature = "async")]
+    pub(crate) fn deadline(&self, attempt: u32, status: ErrorStatus) -> u64 {
+        self.backoff.map_or(0, |(backoff, now)| {
+            now().saturating_add(backoff(attempt, status))
+        })
+    }
+
+    /// Returns `true` if it's time to retry a transfer that waits for `deadline`
+    #[cfg(feature = "async")]
+    pub(crate) fn is_due(&self, deadline: u64) -> bool {
+        self.backoff.is_none_or(|(_, now)| now() >= deadline)
+    }
+}
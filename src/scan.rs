@@ -0,0 +1,251 @@
+//! Capture a sequence of results for every hardware trigger.
+//!
+//! A [`ScanCapture`] pairs a DMA channel with a triggered [`Source`], like an ADC
+//! that performs a scan of several inputs for every trigger. Each time the source
+//! requests DMA service, the DMA channel copies all results of the scan into a
+//! results array. The channel re-arms itself after every scan, so there's no CPU
+//! work per scan.
+//!
+//! Use [`ScanCapture::latest`] to sample the most recent results at any time. Use
+//! [`ScanCapture::next_scan`] to wait for the next scan to complete. To wake the
+//! executor, route the DMA channel's interrupt handler to
+//! [`on_interrupt()`](crate::Dma::on_interrupt).
+
+use crate::{
+    channel::{self, Channel, Configuration},
+    interrupt,
+    peripheral::Source,
+    ConfigError, Element, Error,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Describes how the source presents its scan results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResultRegisters {
+    /// All results are read from the source address, like a result FIFO
+    Fifo,
+    /// Results are in consecutive registers, starting at the source address
+    Consecutive,
+}
+
+/// Captures `N` results from a source for every hardware trigger
+///
+/// Use [`ScanCapture::new`] to start capturing. The capture runs until it's dropped.
+///
+/// # Example
+///
+/// Capture four ADC results per trigger. Each result is in its own result register.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, peripheral, scan::{ResultRegisters, ScanCapture}};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Source<u32> for X {
+/// #   fn source_signal(&self) -> u32 { 0 }
+/// #   fn source_address(&self) -> *const u32 { panic!() }
+/// #   fn enable_source(&mut self) { panic!() }
+/// #   fn disable_source(&mut self) { panic!() }
+/// # }
+///
+/// # async fn f() -> imxrt_dma::Result<()> {
+/// let mut adc_etc = // A triggered ADC
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// let mut results = [0u32; 4];
+/// let mut capture = ScanCapture::new(
+///     &mut channel_7,
+///     &mut adc_etc,
+///     &mut results,
+///     ResultRegisters::Consecutive,
+/// );
+///
+/// let fresh: [u32; 4] = capture.next_scan().await?;
+/// let whenever: [u32; 4] = capture.latest();
+/// # Ok(()) }
+/// ```
+pub struct ScanCapture<'a, S, E, const N: usize>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    results: &'a mut [E; N],
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, S, E, const N: usize> ScanCapture<'a, S, E, N>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Start capturing `N` results from `source` into `results` for every trigger
+    ///
+    /// Each DMA service request from the source transfers all `N` results.
+    ///
+    /// The build fails if `N` is zero, or if `N` results don't fit in a single
+    /// minor loop.
+    ///
+    /// ```compile_fail
+    /// # use imxrt_dma::scan::{ResultRegisters, ScanCapture};
+    /// # struct X;
+    /// # unsafe impl imxrt_dma::peripheral::Source<u32> for X {
+    /// #   fn source_signal(&self) -> u32 { 0 }
+    /// #   fn source_address(&self) -> *const u32 { panic!() }
+    /// #   fn enable_source(&mut self) { panic!() }
+    /// #   fn disable_source(&mut self) { panic!() }
+    /// # }
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # let mut channel = unsafe { DMA.channel(7) };
+    /// # let mut adc_etc = X;
+    /// let mut results: [u32; 0] = [];
+    /// // Error: a scan needs at least one result.
+    /// let capture = ScanCapture::new(&mut channel, &mut adc_etc, &mut results, ResultRegisters::Fifo);
+    /// ```
+    pub fn new(
+        channel: &'a mut Channel,
+        source: &'a mut S,
+        results: &'a mut [E; N],
+        registers: ResultRegisters,
+    ) -> Self {
+        let size = core::mem::size_of::<E>();
+        let nbytes = const {
+            match N.checked_mul(core::mem::size_of::<E>()) {
+                Some(nbytes) if nbytes > 0 && nbytes <= u32::MAX as usize => nbytes as u32,
+                _ => panic!("DMA scan size is invalid"),
+            }
+        };
+
+        channel.disable();
+        // The channel re-arms after every scan, until the capture is dropped.
+        channel.set_disable_on_completion(false);
+        channel.set_interrupt_on_completion(true);
+        channel.clear_complete();
+        channel.clear_error();
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+
+        // Safety: hardware source must be valid for all N results, otherwise the
+        // Source impl is unsound. Results are borrowed by the capture. All results
+        // in a single minor loop, and addresses return to the start after the loop.
+        let enable = unsafe {
+            match registers {
+                ResultRegisters::Fifo => {
+                    channel::set_source_hardware(channel, source.source_address());
+                }
+                ResultRegisters::Consecutive => {
+                    channel.set_source_address(source.source_address());
+                    channel.set_source_offset(size as i16);
+                    channel.set_source_attributes::<E>(0);
+                    channel.set_source_last_address_adjustment((nbytes as i32).wrapping_neg());
+                }
+            }
+            channel::set_destination_linear_buffer(channel, results);
+            channel.set_minor_loop_bytes(nbytes);
+            channel.set_transfer_iterations(1);
+            channel.try_enable()
+        };
+        if enable.is_ok() {
+            source.enable_source();
+        }
+
+        ScanCapture {
+            channel,
+            source,
+            results,
+            enable,
+        }
+    }
+
+    /// Returns a copy of the most recent results
+    ///
+    /// If a scan completes while copying, `latest` copies the results again. The
+    /// copy never mixes results from two different scans.
+    pub fn latest(&self) -> [E; N] {
+        loop {
+            while self.channel.is_active() {}
+            self.channel.clear_complete();
+            let results = self.copy_results();
+            if !self.channel.is_complete() && !self.channel.is_active() {
+                return results;
+            }
+        }
+    }
+
+    /// Poll for the results of the next scan
+    ///
+    /// Returns `Poll::Pending` if no scan has completed since the last call to
+    /// [`latest`](Self::latest) or `poll_scan`.
+    pub fn poll_scan(&mut self, cx: &mut Context<'_>) -> Poll<Result<[E; N], Error>> {
+        self.enable?;
+        interrupt::register_waker(self.channel, cx.waker());
+        if self.channel.is_error() {
+            let es = self.channel.error_status();
+            self.channel.clear_error();
+            Poll::Ready(Err(es.into()))
+        } else if self.channel.is_complete() {
+            Poll::Ready(Ok(self.latest()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Wait for the results of the next scan
+    ///
+    /// See [`poll_scan`](Self::poll_scan) for more information.
+    pub fn next_scan(&mut self) -> NextScan<'_, 'a, S, E, N> {
+        NextScan { capture: self }
+    }
+
+    fn copy_results(&self) -> [E; N] {
+        let results: *const [E; N] = &*self.results;
+        // Safety: pointer is valid, aligned, and initialized. Volatile, since
+        // the DMA channel writes the memory behind the compiler's back.
+        unsafe { core::ptr::read_volatile(results) }
+    }
+}
+
+impl<S, E, const N: usize> Drop for ScanCapture<'_, S, E, N>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        self.channel.disable();
+        while self.channel.is_hardware_signaling() {}
+        self.channel.clear_complete();
+        self.channel.clear_error();
+        interrupt::clear_waker(self.channel);
+    }
+}
+
+/// A future that resolves with the results of the next scan
+///
+/// Use [`ScanCapture::next_scan`] to create this future.
+pub struct NextScan<'s, 'a, S, E, const N: usize>
+where
+    S: Source<E>,
+    E: Element,
+{
+    capture: &'s mut ScanCapture<'a, S, E, N>,
+}
+
+impl<S, E, const N: usize> Future for NextScan<'_, '_, S, E, N>
+where
+    S: Source<E>,
+    E: Element,
+{
+    type Output = Result<[E; N], Error>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.capture.poll_scan(cx)
+    }
+}
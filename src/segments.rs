@@ -0,0 +1,228 @@
+//! Segment-complete events from scatter / gather chains.
+//!
+//! A chain of [`Tcd`]s runs one transfer, or segment, after another. Enable the
+//! interrupt on completion for the segments that should notify the application,
+//! like the end of a header, or the end of each frame. Then, run the chain with
+//! [`Segments`], and await each event with [`next_segment`](Segments::next_segment).
+//! Each event is the index of the segment that completed.
+//!
+//! Route the DMA channel's interrupt handler to [`on_interrupt()`](crate::Dma::on_interrupt)
+//! to wake the executor. If interrupts coalesce, `Segments` still reports every
+//! segment that completed, in order.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::{self, Channel}, segments::Segments, tcd::Tcd};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # async fn f() -> imxrt_dma::Result<()> {
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! // A hardware source requests service for each minor loop.
+//! channel.set_channel_configuration(channel::Configuration::enable(42));
+//!
+//! let header = [0u32; 4];
+//! let frames = [[0u32; 16]; 2];
+//! let mut destination = [0u32; 36];
+//!
+//! let mut tcds = [Tcd::new(); 3];
+//! // Describe the header, and the frames, in each TCD. Then, enable
+//! // the notifications.
+//! tcds[0].set_interrupt_on_completion(true);
+//! tcds[2].set_interrupt_on_completion(true);
+//! # let _ = (header, frames, &mut destination);
+//!
+//! // Safety: each TCD describes a valid transfer.
+//! let mut segments = unsafe { Segments::new(&mut channel, &mut tcds) };
+//! while let Some(segment) = segments.next_segment().await {
+//!     let _completed: usize = segment?;
+//! }
+//! # Ok(()) }
+//! ```
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{channel::Channel, interrupt, ral::tcd::CSR, tcd::Tcd, ConfigError, Error};
+
+/// Runs a chain of descriptors, and reports segment-complete events
+///
+/// Use [`Segments::new`] to load the chain. The chain runs until it completes, or
+/// until `Segments` is dropped.
+pub struct Segments<'a> {
+    channel: &'a mut Channel,
+    tcds: &'a mut [Tcd],
+    /// The next segment that may be reported.
+    reported: usize,
+    /// Set if no segment has transfer iterations. The channel is untouched.
+    empty: bool,
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a> Segments<'a> {
+    /// Link `tcds` into a chain, and load the chain into `channel`
+    ///
+    /// The chain inherits the channel's DMAMUX configuration. `new` enables the
+    /// channel, so a hardware source may start the chain. Use [`start`](Self::start)
+    /// for software-triggered segments.
+    ///
+    /// Segments without transfer iterations complete without running on the
+    /// channel. If every segment is empty, the chain completes on its first poll,
+    /// and `new` never touches the channel.
+    ///
+    /// ```
+    /// use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+    /// use imxrt_dma::{segments::Segments, tcd::Tcd};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # let mut channel = unsafe { DMA.channel(7) };
+    ///
+    /// let mut tcds = [Tcd::new(); 2];
+    /// tcds[1].set_interrupt_on_completion(true);
+    ///
+    /// let mut segments = unsafe { Segments::new(&mut channel, &mut tcds) };
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(segments.completed(), 2);
+    /// assert_eq!(segments.poll_segment(&mut cx), Poll::Ready(Some(Ok(1))));
+    /// assert_eq!(pin!(segments.next_segment()).poll(&mut cx), Poll::Ready(None));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tcds` is empty.
+    ///
+    /// # Safety
+    ///
+    /// Each descriptor must describe a valid transfer. You must ensure that each
+    /// transfer's source and destination remain valid until the chain completes,
+    /// or until `Segments` is dropped.
+    pub unsafe fn new(channel: &'a mut Channel, tcds: &'a mut [Tcd]) -> Self {
+        let Some(first) = crate::tcd::link(tcds) else {
+            return Segments {
+                channel,
+                tcds,
+                reported: 0,
+                empty: true,
+                enable: Ok(()),
+            };
+        };
+        channel.disable();
+        channel.clear_error();
+        tcds[first].apply(channel);
+        let enable = channel.try_enable();
+        Segments {
+            channel,
+            tcds,
+            reported: 0,
+            empty: false,
+            enable,
+        }
+    }
+
+    /// Request service for the active segment from software
+    pub fn start(&self) {
+        if !self.empty {
+            self.channel.start();
+        }
+    }
+
+    /// Returns the number of segments that have completed
+    pub fn completed(&self) -> usize {
+        if self.empty {
+            return self.tcds.len();
+        }
+        let live = self.channel.snapshot();
+        if live.CSR & CSR::ESG::mask == 0 {
+            // The last non-empty segment is loaded.
+            if live.CSR & CSR::DONE::mask != 0 {
+                return self.tcds.len();
+            }
+            return self.active_before(self.tcds.len());
+        }
+        // The active segment links to the next non-empty segment.
+        let next = self
+            .tcds
+            .iter()
+            .position(|tcd| core::ptr::eq(tcd, live.DLAST_SGA as *const Tcd))
+            .unwrap_or(0);
+        self.active_before(next)
+    }
+
+    /// Returns the index of the last non-empty segment before `next`
+    ///
+    /// Each segment before the active segment is complete.
+    fn active_before(&self, next: usize) -> usize {
+        self.tcds[..next]
+            .iter()
+            .rposition(|tcd| tcd.transfer_iterations() != 0)
+            .unwrap_or(0)
+    }
+
+    /// Poll for the next segment-complete event
+    ///
+    /// Returns the index of the next completed segment that generates an interrupt.
+    /// Returns `Poll::Ready(None)` once the chain completes, and all events are
+    /// reported.
+    pub fn poll_segment(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<usize, Error>>> {
+        if let Err(err) = self.enable {
+            return Poll::Ready(Some(Err(err.into())));
+        }
+        if !self.empty {
+            interrupt::register_waker(self.channel, cx.waker());
+        }
+        if !self.empty && self.channel.is_error() {
+            let es = self.channel.error_status();
+            self.channel.clear_error();
+            return Poll::Ready(Some(Err(es.into())));
+        }
+        let completed = self.completed();
+        while self.reported < completed {
+            let segment = self.reported;
+            self.reported += 1;
+            if self.tcds[segment].interrupt_on_completion() {
+                return Poll::Ready(Some(Ok(segment)));
+            }
+        }
+        if completed == self.tcds.len() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Wait for the next segment-complete event
+    ///
+    /// See [`poll_segment`](Self::poll_segment) for more information.
+    pub fn next_segment(&mut self) -> NextSegment<'_, 'a> {
+        NextSegment { segments: self }
+    }
+}
+
+impl Drop for Segments<'_> {
+    fn drop(&mut self) {
+        if self.empty {
+            return;
+        }
+        self.channel.disable();
+        while self.channel.is_hardware_signaling() {}
+        self.channel.clear_complete();
+        self.channel.clear_error();
+        interrupt::clear_waker(self.channel);
+    }
+}
+
+/// A future that resolves with the next segment-complete event
+///
+/// Use [`Segments::next_segment`] to create this future.
+pub struct NextSegment<'s, 'a> {
+    segments: &'s mut Segments<'a>,
+}
+
+impl Future for NextSegment<'_, '_> {
+    type Output = Option<Result<usize, Error>>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.segments.poll_segment(cx)
+    }
+}
@@ -0,0 +1,380 @@
+//! Share one DMA channel among multiple tasks.
+//!
+//! A [`SharedChannel`] lets multiple async tasks use the same hardware channel.
+//! A task [locks](SharedChannel::lock) the channel, uses the channel for one or more
+//! transfers, then drops the guard to release the channel. Tasks acquire the channel
+//! in the order that they asked for it. To let urgent transfers jump ahead of bulk
+//! transfers, lock the channel with a [`Priority`](SharedChannel::lock_with_priority).
+//!
+//! Use a `SharedChannel` for peripherals that rarely use DMA, so you don't need to
+//! dedicate a channel to each peripheral.
+
+use crate::{channel::Channel, sync::Mutex};
+
+use core::{
+    cell::{RefCell, UnsafeCell},
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// How soon a task acquires a [`SharedChannel`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Priority {
+    /// Acquire the channel before any bulk task
+    Urgent,
+    /// Acquire the channel after the urgent tasks
+    Bulk,
+}
+
+/// One priority class's wait line, implemented as a ticket lock
+///
+/// Tickets and wakers are indexed modulo `N`. At most `N` tasks hold a ticket.
+struct Line<const N: usize> {
+    /// The next ticket to hand out
+    next: usize,
+    /// The first ticket in line
+    head: usize,
+    /// Wakers for the tasks holding tickets
+    wakers: [Option<Waker>; N],
+    /// Tickets that were dropped before they acquired the channel
+    abandoned: [bool; N],
+    /// The waker of the latest task waiting for a ticket
+    overflow: Option<Waker>,
+}
+
+impl<const N: usize> Line<N> {
+    const fn new() -> Self {
+        Line {
+            next: 0,
+            head: 0,
+            wakers: [const { None }; N],
+            abandoned: [false; N],
+            overflow: None,
+        }
+    }
+
+    /// Skip any abandoned tickets at the head, then return `true` if a task
+    /// is waiting
+    fn is_waiting(&mut self) -> bool {
+        while self.head != self.next && self.abandoned[self.head % N] {
+            self.abandoned[self.head % N] = false;
+            self.head = self.head.wrapping_add(1);
+        }
+        self.head != self.next
+    }
+}
+
+/// The default number of urgent tasks served in a row while bulk tasks wait
+const DEFAULT_URGENT_LIMIT: usize = 4;
+
+/// The wait queue
+struct Queue<const N: usize> {
+    /// Urgent, then bulk, wait lines
+    lines: [Line<N>; 2],
+    /// The line whose head may hold the channel
+    owner: Option<Priority>,
+    /// The number of urgent tasks served since the last bulk task
+    streak: usize,
+}
+
+impl<const N: usize> Queue<N> {
+    const fn new() -> Self {
+        Queue {
+            lines: [Line::new(), Line::new()],
+            owner: None,
+            streak: 0,
+        }
+    }
+
+    fn line(&mut self, priority: Priority) -> &mut Line<N> {
+        &mut self.lines[priority as usize]
+    }
+
+    /// If no task holds the channel, give the channel to the next task, and wake it
+    ///
+    /// Urgent tasks go first, unless `limit` urgent tasks went in a row while bulk
+    /// tasks waited.
+    fn dispatch(&mut self, limit: usize) {
+        if self.owner.is_some() {
+            return;
+        }
+        let urgent = self.line(Priority::Urgent).is_waiting();
+        let bulk = self.line(Priority::Bulk).is_waiting();
+        let priority = match (urgent, bulk) {
+            (true, true) if self.streak >= limit => Priority::Bulk,
+            (true, _) => Priority::Urgent,
+            (false, true) => Priority::Bulk,
+            (false, false) => return,
+        };
+        self.streak = match priority {
+            Priority::Urgent if bulk => self.streak + 1,
+            _ => 0,
+        };
+        self.owner = Some(priority);
+        let line = self.line(priority);
+        if let Some(waker) = line.wakers[line.head % N].take() {
+            waker.wake();
+        }
+    }
+
+    /// Release the channel from the head of the owner's line, and give it to the
+    /// next task
+    fn advance(&mut self, limit: usize) {
+        if let Some(owner) = self.owner.take() {
+            let line = self.line(owner);
+            line.head = line.head.wrapping_add(1);
+        }
+        self.dispatch(limit);
+        for line in &mut self.lines {
+            if let Some(waker) = line.overflow.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// A DMA channel shared among tasks
+///
+/// `N` is the maximum number of tasks that can wait for the channel while keeping
+/// their place in line. Additional tasks still acquire the channel, but they may not
+/// be served in order.
+///
+/// # Example
+///
+/// Share DMA channel 7 between two tasks.
+///
+/// ```no_run
+/// use imxrt_dma::{memcpy, shared::SharedChannel};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+///
+/// async fn copy(shared: &SharedChannel<4>, source: &[u32], destination: &mut [u32]) -> imxrt_dma::Result<()> {
+///     let mut channel = shared.lock().await;
+///     memcpy::memcpy(source, destination, &mut channel).await
+/// }
+///
+/// let shared = SharedChannel::<4>::new(
+///     // DMA channel 7
+///     # unsafe { DMA.channel(7) }
+/// );
+///
+/// let (mut a, mut b) = ([0u32; 3], [0u32; 3]);
+/// let task_a = copy(&shared, &[1, 2, 3], &mut a);
+/// let task_b = copy(&shared, &[4, 5, 6], &mut b);
+/// // Run both tasks on your executor...
+/// # drop((task_a, task_b));
+/// ```
+pub struct SharedChannel<const N: usize> {
+    channel: UnsafeCell<Channel>,
+    queue: Mutex<RefCell<Queue<N>>>,
+    /// The number of urgent tasks served in a row while bulk tasks wait
+    limit: usize,
+}
+
+// Safety: the queue ensures that only one task accesses the channel at a time.
+// A channel may be sent across execution contexts.
+unsafe impl<const N: usize> Sync for SharedChannel<N> {}
+
+impl<const N: usize> SharedChannel<N> {
+    /// Share the DMA channel
+    ///
+    /// The build fails if `N` is zero.
+    pub const fn new(channel: Channel) -> Self {
+        const { assert!(N > 0, "SharedChannel needs room for at least one task") };
+        SharedChannel {
+            channel: UnsafeCell::new(channel),
+            queue: Mutex::new(RefCell::new(Queue::new())),
+            limit: DEFAULT_URGENT_LIMIT,
+        }
+    }
+
+    /// Serve at most `limit` urgent tasks in a row while bulk tasks wait
+    ///
+    /// After `limit` urgent tasks, the next bulk task acquires the channel before
+    /// any other urgent task, so a stream of urgent work never starves the bulk
+    /// tasks. The default limit is 4. With a limit of zero, urgent tasks only go
+    /// first when no bulk task waits.
+    pub const fn with_urgent_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Wait to acquire the channel
+    ///
+    /// The future resolves with a guard once the task may use the channel.
+    /// Drop the guard to release the channel to the next task. The task waits
+    /// as a [`Priority::Bulk`] task.
+    pub fn lock(&self) -> Lock<'_, N> {
+        self.lock_with_priority(Priority::Bulk)
+    }
+
+    /// Wait to acquire the channel as a `priority` task
+    ///
+    /// Each time the channel is released, the next urgent task acquires it ahead
+    /// of any bulk task, subject to the [urgent limit](Self::with_urgent_limit).
+    /// Tasks of the same priority acquire the channel in the order that they
+    /// asked for it. Urgent tasks don't preempt the task that holds the channel;
+    /// they go next.
+    ///
+    /// ```no_run
+    /// use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+    /// use imxrt_dma::shared::{Priority, SharedChannel};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let shared = SharedChannel::<4>::new(
+    ///     // DMA channel 7
+    ///     # unsafe { DMA.channel(7) }
+    /// );
+    /// let mut cx = Context::from_waker(Waker::noop());
+    ///
+    /// let Poll::Ready(guard) = pin!(shared.lock()).poll(&mut cx) else { panic!() };
+    /// let mut bulk = pin!(shared.lock());
+    /// let mut urgent = pin!(shared.lock_with_priority(Priority::Urgent));
+    /// assert!(bulk.as_mut().poll(&mut cx).is_pending());
+    /// assert!(urgent.as_mut().poll(&mut cx).is_pending());
+    ///
+    /// drop(guard);
+    /// assert!(bulk.as_mut().poll(&mut cx).is_pending());
+    /// assert!(urgent.as_mut().poll(&mut cx).is_ready());
+    /// ```
+    pub fn lock_with_priority(&self, priority: Priority) -> Lock<'_, N> {
+        Lock {
+            shared: self,
+            priority,
+            ticket: None,
+            displaced: None,
+        }
+    }
+
+    /// Release the shared channel
+    pub fn into_inner(self) -> Channel {
+        self.channel.into_inner()
+    }
+}
+
+/// A future that resolves once the task acquires a [`SharedChannel`]
+///
+/// Use [`SharedChannel::lock`] to create this future.
+pub struct Lock<'a, const N: usize> {
+    shared: &'a SharedChannel<N>,
+    priority: Priority,
+    ticket: Option<usize>,
+    /// The waker of a task that this task replaced in its line's overflow slot
+    ///
+    /// Once this task takes a ticket, or drops, it wakes the displaced task. Tasks
+    /// waiting for a ticket wake each other in turn, one for each released ticket.
+    displaced: Option<Waker>,
+}
+
+impl<'a, const N: usize> Future for Lock<'a, N> {
+    type Output = Guard<'a, N>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = self.shared;
+        let (priority, ticket) = (self.priority, self.ticket);
+        let (ticket, ready, displaced) = shared.queue.lock(|queue| {
+            let mut queue = queue.borrow_mut();
+            let line = queue.line(priority);
+            let ticket = match ticket {
+                Some(ticket) => ticket,
+                None if line.next.wrapping_sub(line.head) < N => {
+                    let ticket = line.next;
+                    line.next = line.next.wrapping_add(1);
+                    ticket
+                }
+                None => {
+                    // Take over the overflow slot. Keep the displaced waker,
+                    // and wake it once this task takes a ticket.
+                    let displaced = line.overflow.replace(cx.waker().clone());
+                    let displaced = displaced.filter(|waker| !waker.will_wake(cx.waker()));
+                    return (None, false, displaced);
+                }
+            };
+            let line = queue.line(priority);
+            line.wakers[ticket % N] = Some(cx.waker().clone());
+            queue.dispatch(shared.limit);
+            let owner = queue.owner;
+            let line = queue.line(priority);
+            let ready = owner == Some(priority) && line.head == ticket;
+            if ready {
+                line.wakers[ticket % N] = None;
+            }
+            (Some(ticket), ready, None)
+        });
+
+        if let Some(waker) = displaced {
+            if let Some(waker) = self.displaced.replace(waker) {
+                waker.wake();
+            }
+        } else if ticket.is_some() {
+            if let Some(waker) = self.displaced.take() {
+                waker.wake();
+            }
+        }
+
+        if ready {
+            // The guard releases the channel.
+            self.ticket = None;
+            Poll::Ready(Guard { shared })
+        } else {
+            self.ticket = ticket;
+            Poll::Pending
+        }
+    }
+}
+
+impl<const N: usize> Drop for Lock<'_, N> {
+    fn drop(&mut self) {
+        if let Some(waker) = self.displaced.take() {
+            waker.wake();
+        }
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        let priority = self.priority;
+        self.shared.queue.lock(|queue| {
+            let mut queue = queue.borrow_mut();
+            if queue.owner == Some(priority) && queue.line(priority).head == ticket {
+                // The channel was given to this task. Give it to the next task.
+                queue.advance(self.shared.limit);
+            } else {
+                let line = queue.line(priority);
+                line.abandoned[ticket % N] = true;
+                line.wakers[ticket % N] = None;
+            }
+        });
+    }
+}
+
+/// Exclusive access to a [`SharedChannel`]
+///
+/// The guard dereferences to the [`Channel`]. Drop the guard to release the
+/// channel to the next task.
+pub struct Guard<'a, const N: usize> {
+    shared: &'a SharedChannel<N>,
+}
+
+impl<const N: usize> Deref for Guard<'_, N> {
+    type Target = Channel;
+    fn deref(&self) -> &Channel {
+        // Safety: the guard has exclusive access to the channel.
+        unsafe { &*self.shared.channel.get() }
+    }
+}
+
+impl<const N: usize> DerefMut for Guard<'_, N> {
+    fn deref_mut(&mut self) -> &mut Channel {
+        // Safety: the guard has exclusive access to the channel.
+        unsafe { &mut *self.shared.channel.get() }
+    }
+}
+
+impl<const N: usize> Drop for Guard<'_, N> {
+    fn drop(&mut self) {
+        let limit = self.shared.limit;
+        self.shared
+            .queue
+            .lock(|queue| queue.borrow_mut().advance(limit));
+    }
+}
@@ -0,0 +1,133 @@
+//! DMA request signals for specific chips.
+//!
+//! Each chip routes different peripherals to the DMA multiplexer. Enable a chip
+//! feature to get a `Signal` enum that lists the chip's DMA request sources.
+//! Since each chip has its own enum, a signal that the chip doesn't support is a
+//! compile error, not an incorrect signal number at runtime.
+//!
+//! | Feature     | Chips                                |
+//! | ----------- | ------------------------------------ |
+//! | `imxrt1060` | i.MX RT 1061, i.MX RT 1062, i.MX RT 1064 |
+//!
+//! [`RegisterSource`](crate::peripheral::RegisterSource) and
+//! [`RegisterDestination`](crate::peripheral::RegisterDestination) accept a
+//! `Signal`. Use `Configuration::enable_signal` to configure a channel for a signal.
+//!
+//! The [`Source`](crate::peripheral::Source) and
+//! [`Destination`](crate::peripheral::Destination) traits, and the `const`
+//! constructors, still use `u32` signals, so that they build without a chip
+//! feature. Use `Signal::raw`, or `u32::from`, for those APIs.
+//!
+//! The enums only list the signals used by common DMA peripherals. Consult your
+//! reference manual for signals that aren't listed.
+
+/// Define a chip's `Signal` enum
+#[allow(unused_macros)]
+macro_rules! signals {
+    ($($(#[$meta:meta])* $name:ident = $value:literal,)*) => {
+        /// A DMA request signal
+        ///
+        /// See Table 4-3 of the reference manual.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[repr(u32)]
+        #[non_exhaustive]
+        #[allow(non_camel_case_types)]
+        pub enum Signal {
+            $($(#[$meta])* $name = $value,)*
+        }
+
+        impl Signal {
+            /// Returns the DMA multiplexer source number for this signal
+            pub const fn raw(self) -> u32 {
+                self as u32
+            }
+        }
+
+        impl From<Signal> for u32 {
+            fn from(signal: Signal) -> u32 {
+                signal.raw()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "imxrt1060")]
+signals! {
+    /// FlexIO1 requests 0 and 1
+    FLEXIO1_REQUEST0_1 = 0,
+    /// FlexIO2 requests 0 and 1
+    FLEXIO2_REQUEST0_1 = 1,
+    /// LPUART1 transmit
+    LPUART1_TX = 2,
+    /// LPUART1 receive
+    LPUART1_RX = 3,
+    /// LPUART3 transmit
+    LPUART3_TX = 4,
+    /// LPUART3 receive
+    LPUART3_RX = 5,
+    /// LPUART5 transmit
+    LPUART5_TX = 6,
+    /// LPUART5 receive
+    LPUART5_RX = 7,
+    /// LPUART7 transmit
+    LPUART7_TX = 8,
+    /// LPUART7 receive
+    LPUART7_RX = 9,
+    /// LPSPI1 receive
+    LPSPI1_RX = 13,
+    /// LPSPI1 transmit
+    LPSPI1_TX = 14,
+    /// LPSPI3 receive
+    LPSPI3_RX = 15,
+    /// LPSPI3 transmit
+    LPSPI3_TX = 16,
+    /// LPI2C1 requests
+    LPI2C1 = 17,
+    /// LPI2C3 requests
+    LPI2C3 = 18,
+    /// SAI1 receive
+    SAI1_RX = 19,
+    /// SAI1 transmit
+    SAI1_TX = 20,
+    /// SAI2 receive
+    SAI2_RX = 21,
+    /// SAI2 transmit
+    SAI2_TX = 22,
+    /// ADC1 conversion complete
+    ADC1 = 24,
+    /// LPUART2 transmit
+    LPUART2_TX = 66,
+    /// LPUART2 receive
+    LPUART2_RX = 67,
+    /// LPUART4 transmit
+    LPUART4_TX = 68,
+    /// LPUART4 receive
+    LPUART4_RX = 69,
+    /// LPUART6 transmit
+    LPUART6_TX = 70,
+    /// LPUART6 receive
+    LPUART6_RX = 71,
+    /// LPUART8 transmit
+    LPUART8_TX = 72,
+    /// LPUART8 receive
+    LPUART8_RX = 73,
+    /// LPSPI2 receive
+    LPSPI2_RX = 77,
+    /// LPSPI2 transmit
+    LPSPI2_TX = 78,
+    /// LPSPI4 receive
+    LPSPI4_RX = 79,
+    /// LPSPI4 transmit
+    LPSPI4_TX = 80,
+    /// LPI2C2 requests
+    LPI2C2 = 81,
+    /// LPI2C4 requests
+    LPI2C4 = 82,
+    /// SAI3 receive
+    SAI3_RX = 83,
+    /// SAI3 transmit
+    SAI3_TX = 84,
+    /// ADC2 conversion complete
+    ADC2 = 88,
+}
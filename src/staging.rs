@@ -0,0 +1,243 @@
+//! Stage peripheral data in RAM for a flash programmer.
+//!
+//! An over-the-air update receives its image from a peripheral, like a UART or
+//! an SPI link, and programs it into flash. The DMA engine can't program flash;
+//! a DMA transfer that targets flash silently writes nothing, and the update
+//! programs garbage. Instead, receive each block into a RAM staging buffer, then
+//! hand the block to the flash programmer.
+//!
+//! A [`Staging`] owns two staging buffers, and checks that neither overlaps the
+//! [`Flash`] region. [`Staging::receive`] receives the next block into a free
+//! buffer. The block arrives as a [`Staged`] token, which holds the buffer until
+//! you [release](Staged::release) it, or drop it. While the flash programmer holds
+//! one block, the DMA channel receives the next block into the other buffer.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, peripheral, staging::{Flash, Staging}};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct X;
+//! # unsafe impl peripheral::Source<u8> for X {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u8 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//! # async fn program(_: &[u8]) {}
+//! # async fn f() -> imxrt_dma::Result<()> {
+//! let mut lpuart = // A LPUART peripheral
+//!     # X;
+//! let mut channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//!
+//! // The FlexSPI flash, on an i.MX RT 1060.
+//! const FLASH: Flash = Flash::new(0x6000_0000, 8 * 1024 * 1024);
+//! let (mut a, mut b) = ([0u8; 256], [0u8; 256]);
+//! let staging = Staging::new(FLASH, &mut a, &mut b).unwrap();
+//!
+//! loop {
+//!     let block = staging.receive(&mut channel_7, &mut lpuart).await?;
+//!     program(&block).await;
+//!     block.release();
+//! }
+//! # }
+//! ```
+//!
+//! To receive while you program, join the next `receive` with the programming
+//! future of the last block.
+//!
+//! With the `imxrt1060` feature, `Staging::new` also checks that the buffers are
+//! in DMA-writable memory.
+
+use core::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    channel::Channel,
+    peripheral::{self, Read, Source},
+    ConfigError, Element, Error,
+};
+
+/// A flash region that DMA transfers must not target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Flash {
+    start: usize,
+    len: usize,
+}
+
+impl Flash {
+    /// Describe `len` bytes of flash, starting at address `start`
+    pub const fn new(start: usize, len: usize) -> Self {
+        Flash { start, len }
+    }
+
+    /// Returns `true` if any element of `buffer` is in this flash region
+    ///
+    /// ```
+    /// use imxrt_dma::staging::Flash;
+    ///
+    /// let flash = Flash::new(0x6000_0000, 0x1000);
+    /// let image = unsafe { core::slice::from_raw_parts(0x6000_0FF0 as *const u8, 32) };
+    /// assert!(flash.overlaps(image));
+    /// assert!(!flash.overlaps(&[0u8; 32]));
+    /// ```
+    pub fn overlaps<E>(&self, buffer: &[E]) -> bool {
+        let start = buffer.as_ptr() as usize;
+        let end = start.saturating_add(core::mem::size_of_val(buffer));
+        start < self.start.saturating_add(self.len) && self.start < end
+    }
+}
+
+/// Two RAM buffers that stage peripheral data for a flash programmer
+///
+/// See the [module documentation](crate::staging) for more information.
+pub struct Staging<'a, E> {
+    buffers: [Cell<Option<&'a mut [E]>>; 2],
+    /// The buffer that receives the next block.
+    next: Cell<usize>,
+    /// The receive that waits for a block's release.
+    waker: Cell<Option<Waker>>,
+}
+
+impl<'a, E: Element> Staging<'a, E> {
+    /// Stage blocks of data in `a` and `b`
+    ///
+    /// Returns [`ConfigError::ReadOnlyDestination`] if either buffer overlaps
+    /// `flash`. With a chip feature, returns an error if either buffer isn't in
+    /// DMA-writable memory.
+    pub fn new(flash: Flash, a: &'a mut [E], b: &'a mut [E]) -> Result<Self, ConfigError> {
+        for buffer in [&*a, &*b] {
+            if flash.overlaps(buffer) {
+                return Err(ConfigError::ReadOnlyDestination);
+            }
+            #[cfg(feature = "imxrt1060")]
+            crate::memory::check_destination(buffer)?;
+        }
+        Ok(Staging {
+            buffers: [Cell::new(Some(a)), Cell::new(Some(b))],
+            next: Cell::new(0),
+            waker: Cell::new(None),
+        })
+    }
+
+    /// Receive a block from `source` into the next staging buffer
+    ///
+    /// The block fills the whole buffer. The staging buffers alternate. The future
+    /// waits for the flash programmer to release the buffer's last block, then
+    /// receives into it. If the read fails, the buffer returns to the staging.
+    /// Only one receive should wait for a release at a time.
+    pub fn receive<'s, S: Source<E>>(
+        &'s self,
+        channel: &'s mut Channel,
+        source: &'s mut S,
+    ) -> Receive<'s, 'a, S, E> {
+        let index = self.next.get();
+        self.next.set(index ^ 1);
+        Receive {
+            staging: self,
+            index,
+            channel: Some(channel),
+            source: Some(source),
+            buffer: None,
+            read: None,
+        }
+    }
+}
+
+/// Receives a block into a staging buffer
+///
+/// Use [`Staging::receive`] to create this future.
+pub struct Receive<'s, 'a, S: Source<E>, E: Element> {
+    staging: &'s Staging<'a, E>,
+    index: usize,
+    channel: Option<&'s mut Channel>,
+    source: Option<&'s mut S>,
+    buffer: Option<core::ptr::NonNull<[E]>>,
+    read: Option<Read<'s, S, E>>,
+}
+
+impl<'s, 'a, S: Source<E>, E: Element> Future for Receive<'s, 'a, S, E> {
+    type Output = Result<Staged<'s, 'a, E>, Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the read is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.read.is_none() {
+            let Some(buffer) = this.staging.buffers[this.index].take() else {
+                // The flash programmer holds the buffer. Its release wakes us.
+                this.staging.waker.set(Some(cx.waker().clone()));
+                return Poll::Pending;
+            };
+            let mut buffer = core::ptr::NonNull::from(buffer);
+            this.buffer = Some(buffer);
+            let channel = this.channel.take().expect("polled after completion");
+            let source = this.source.take().expect("polled after completion");
+            // Safety: the staging buffer is out of the staging, and the read has
+            // the only reference until it's dropped.
+            this.read = Some(peripheral::read(channel, source, unsafe {
+                buffer.as_mut()
+            }));
+        }
+        let read = unsafe { Pin::new_unchecked(this.read.as_mut().unwrap()) };
+        let result = core::task::ready!(read.poll(cx));
+        this.read = None;
+        // Safety: the read is dropped, so nothing else references the buffer.
+        let buffer = unsafe { this.buffer.take().unwrap().as_mut() };
+        match result {
+            Ok(()) => Poll::Ready(Ok(Staged {
+                staging: this.staging,
+                index: this.index,
+                buffer: Some(buffer),
+            })),
+            Err(err) => {
+                this.staging.buffers[this.index].set(Some(buffer));
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+}
+
+impl<S: Source<E>, E: Element> Drop for Receive<'_, '_, S, E> {
+    fn drop(&mut self) {
+        // Cancel the read before the buffer returns to the staging.
+        self.read = None;
+        if let Some(mut buffer) = self.buffer.take() {
+            // Safety: the read is dropped, so nothing else references the buffer.
+            self.staging.buffers[self.index].set(Some(unsafe { buffer.as_mut() }));
+        }
+    }
+}
+
+/// A received block, held by the flash programmer
+///
+/// A `Staged` dereferences to its block. The next receive into the same staging
+/// buffer waits until you [release](Self::release) or drop the block.
+pub struct Staged<'s, 'a, E> {
+    staging: &'s Staging<'a, E>,
+    index: usize,
+    buffer: Option<&'a mut [E]>,
+}
+
+impl<E> Staged<'_, '_, E> {
+    /// Return the block's buffer to the staging
+    pub fn release(self) {}
+}
+
+impl<E> core::ops::Deref for Staged<'_, '_, E> {
+    type Target = [E];
+    fn deref(&self) -> &[E] {
+        self.buffer.as_deref().unwrap()
+    }
+}
+
+impl<E> Drop for Staged<'_, '_, E> {
+    fn drop(&mut self) {
+        self.staging.buffers[self.index].set(self.buffer.take());
+        if let Some(waker) = self.staging.waker.take() {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Suspend and resume all DMA channels
+
+use crate::ral;
+
+/// The DMA channels that were enabled when the controller suspended
+///
+/// Use [`Dma::suspend_all`](crate::Dma::suspend_all) to suspend the controller,
+/// and pass this to [`Dma::resume_all`](crate::Dma::resume_all) to restore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[must_use = "resume the DMA controller with Dma::resume_all"]
+pub struct Suspended {
+    requests: u32,
+}
+
+impl Suspended {
+    /// Returns a bitmask of the channels that were enabled
+    ///
+    /// Bit `n` is set if channel `n` was enabled for hardware requests.
+    pub const fn enabled_channels(&self) -> u32 {
+        self.requests
+    }
+}
+
+impl<const CHANNELS: usize> crate::Dma<CHANNELS> {
+    /// Pause all DMA channels
+    ///
+    /// `suspend_all` disables all channels, and stalls the start of new minor
+    /// loops. A channel that's executing a minor loop finishes that minor loop. If
+    /// `wait` is `true`, `suspend_all` waits for the DMA engine to go idle, and
+    /// no channel is active once it returns.
+    ///
+    /// Use `suspend_all` before entering a low-power mode, or before reprogramming
+    /// memory that channels may access, like internal flash. Then, use
+    /// [`resume_all`](Self::resume_all) to re-enable the channels that were
+    /// enabled.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let suspended = DMA.suspend_all(true);
+    /// // Enter stop mode, reflash, ...
+    /// DMA.resume_all(suspended);
+    /// ```
+    pub fn suspend_all(&self, wait: bool) -> Suspended {
+        // ERQ and CR are shared by all channels. Prevent a racing modify
+        // from another execution context.
+        let requests = crate::sync::free(|| {
+            let requests = self.controller.ERQ.read();
+            self.controller.ERQ.write(0);
+            ral::modify_reg!(crate::ral::dma, self.controller, CR, HALT: 1);
+            requests
+        });
+        if wait {
+            while ral::read_reg!(crate::ral::dma, self.controller, CR, ACTIVE == 1) {}
+        }
+        Suspended { requests }
+    }
+
+    /// Resume the DMA channels paused by [`suspend_all`](Self::suspend_all)
+    ///
+    /// Channels that were enabled before `suspend_all` are enabled again. Channels
+    /// that were enabled while the controller was suspended remain enabled.
+    pub fn resume_all(&self, suspended: Suspended) {
+        crate::channel::barrier();
+        crate::sync::free(|| {
+            ral::modify_reg!(crate::ral::dma, self.controller, CR, HALT: 0);
+            ral::modify_reg!(crate::ral::dma, self.controller, ERQ, |erq| erq
+                | suspended.requests);
+        });
+    }
+
+    /// Returns `true` if the DMA controller is suspended
+    pub fn is_suspended(&self) -> bool {
+        ral::read_reg!(crate::ral::dma, self.controller, CR, HALT == 1)
+    }
+}
+
+/// A snapshot of the channels that could keep the DMA engine busy
+///
+/// Use [`Dma::activity`](crate::Dma::activity) to take a snapshot. Each bitmask
+/// has bit `n` set for channel `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Activity {
+    enabled: u32,
+    signaling: u32,
+    pending: u32,
+    active: bool,
+}
+
+impl Activity {
+    /// Returns a bitmask of the channels that are enabled for hardware requests
+    pub const fn enabled_channels(&self) -> u32 {
+        self.enabled
+    }
+
+    /// Returns a bitmask of the channels that are receiving a service signal
+    /// from hardware
+    pub const fn signaling_channels(&self) -> u32 {
+        self.signaling
+    }
+
+    /// Returns a bitmask of the channels that have a pending software request
+    pub const fn pending_channels(&self) -> u32 {
+        self.pending
+    }
+
+    /// Returns `true` if the DMA engine was executing a minor loop
+    pub const fn is_engine_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns `true` if nothing could start a minor loop
+    ///
+    /// The engine is idle if it's not executing a minor loop, no channel has a
+    /// pending software request, and no enabled channel receives a hardware
+    /// signal. Channels may be enabled and awaiting a signal.
+    pub const fn is_idle(&self) -> bool {
+        !self.active && self.pending == 0 && self.enabled & self.signaling == 0
+    }
+}
+
+impl<const CHANNELS: usize> crate::Dma<CHANNELS> {
+    /// Report which channels are enabled, signaling, or have pending requests
+    ///
+    /// Use this to find the channels that keep the DMA engine busy before you gate
+    /// its clocks. The registers are read one after another, so a running transfer
+    /// may change between the reads; to get a stable report, take it while the
+    /// controller is [suspended](Self::suspend_all).
+    ///
+    /// ```no_run
+    /// use imxrt_dma::Dma;
+    /// static DMA: Dma<32> = // Handle to DMA driver.
+    /// # unsafe { Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let activity = DMA.activity();
+    /// if !activity.is_idle() {
+    ///     // Channels in activity.pending_channels() still need to run...
+    /// }
+    /// ```
+    pub fn activity(&self) -> Activity {
+        let pending = (0..CHANNELS.min(32))
+            .filter(|&index| {
+                let tcd = &self.controller.TCD[index];
+                ral::read_reg!(crate::ral::tcd, tcd, CSR, START == 1)
+            })
+            .fold(0, |mask, index| mask | 1 << index);
+        Activity {
+            enabled: self.controller.ERQ.read(),
+            signaling: self.controller.HRS.read(),
+            pending,
+            active: ral::read_reg!(crate::ral::dma, self.controller, CR, ACTIVE == 1),
+        }
+    }
+
+    /// Returns `true` if the DMA engine is quiescent, and its clocks may be gated
+    ///
+    /// See [`Activity::is_idle`] for what makes the engine idle. To keep it idle,
+    /// [suspend](Self::suspend_all) the controller, or disable the channels, before
+    /// you gate its clocks.
+    pub fn is_engine_idle(&self) -> bool {
+        self.activity().is_idle()
+    }
+}
@@ -0,0 +1,51 @@
+//! Synchronization for driver state shared across execution contexts
+//!
+//! By default, the driver masks interrupts with `cortex-m`. With the
+//! `critical-section` feature, the driver uses the `critical-section`
+//! crate, which supports multi-core systems and host environments.
+
+#[cfg(all(
+    not(feature = "critical-section"),
+    any(feature = "async", feature = "ring-buffers")
+))]
+use cortex_m::interrupt::Mutex as Inner;
+#[cfg(all(
+    feature = "critical-section",
+    any(feature = "async", feature = "ring-buffers")
+))]
+use critical_section::Mutex as Inner;
+
+/// A value that's only accessed in a critical section
+#[cfg(any(feature = "async", feature = "ring-buffers"))]
+pub(crate) struct Mutex<T>(Inner<T>);
+
+#[cfg(any(feature = "async", feature = "ring-buffers"))]
+impl<T> Mutex<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Mutex(Inner::new(value))
+    }
+
+    /// Access the value in a critical section
+    pub(crate) fn lock<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        #[cfg(not(feature = "critical-section"))]
+        {
+            cortex_m::interrupt::free(|cs| f(self.0.borrow(cs)))
+        }
+        #[cfg(feature = "critical-section")]
+        {
+            critical_section::with(|cs| f(self.0.borrow(cs)))
+        }
+    }
+}
+
+/// Run `f` in a critical section
+pub(crate) fn free<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(not(feature = "critical-section"))]
+    {
+        cortex_m::interrupt::free(|_| f())
+    }
+    #[cfg(feature = "critical-section")]
+    {
+        critical_section::with(|_| f())
+    }
+}
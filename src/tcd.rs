@@ -0,0 +1,458 @@
+//! Transfer control descriptors in memory.
+//!
+//! A [`Tcd`] describes a transfer without touching a channel. Build the transfer
+//! with the `Tcd` setters, then [`apply`](Tcd::apply) it to a channel. The setters
+//! are safe, since they only write memory, and they reject values that the hardware
+//! can't represent. The safety requirements move to `apply`.
+//!
+//! Use a `Tcd` when the [`Channel`] helpers don't cover the transfer you need.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::{self, Channel}, tcd::Tcd, Transfer};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # async fn f() -> imxrt_dma::Result<()> {
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let source = [1u16, 2, 3, 4, 5, 6, 7, 8];
+//! let mut destination = [0u16; 4];
+//!
+//! // Copy every other element of the source.
+//! let mut tcd = Tcd::new();
+//! tcd.set_source_address(source.as_ptr());
+//! tcd.set_source_offset(4);
+//! tcd.set_source_attributes::<u16>(0);
+//! tcd.set_destination_address(destination.as_mut_ptr());
+//! tcd.set_destination_offset(2);
+//! tcd.set_destination_attributes::<u16>(0);
+//! tcd.set_minor_loop_bytes(2);
+//! tcd.set_transfer_iterations(4);
+//! tcd.set_disable_on_completion(true);
+//!
+//! channel.set_channel_configuration(channel::Configuration::Off);
+//! // Safety: source and destination are valid for the transfer.
+//! unsafe { tcd.apply(&mut channel) };
+//!
+//! let transfer = unsafe { Transfer::new(&channel) };
+//! channel.start();
+//! transfer.await?;
+//! # Ok(()) }
+//! ```
+
+use crate::{
+    channel::{Channel, Configuration, Diagnosis, MinorLoopOffset},
+    ral::tcd::{Descriptor, CITER, CSR, NBYTES, SATTR as ATTR},
+    BandwidthControl, ConfigError, Element,
+};
+
+/// A transfer control descriptor
+///
+/// The layout matches the DMA engine's transfer control descriptor, so a `Tcd` is
+/// aligned to 32 bytes. A new `Tcd` describes no transfer.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tcd {
+    descriptor: Descriptor,
+}
+
+impl Tcd {
+    /// Create a transfer control descriptor with all fields cleared
+    pub const fn new() -> Self {
+        Tcd {
+            descriptor: Descriptor::EMPTY,
+        }
+    }
+
+    /// Set the source address
+    pub fn set_source_address<E: Element>(&mut self, saddr: *const E) {
+        self.descriptor.SADDR = saddr as u32;
+    }
+
+    /// Returns the source address
+    pub fn source_address(&self) -> *const () {
+        self.descriptor.SADDR as *const ()
+    }
+
+    /// Set the source offset *in bytes*
+    ///
+    /// `offset` could be negative, which would decrement the address.
+    pub fn set_source_offset(&mut self, offset: i16) {
+        self.descriptor.SOFF = offset;
+    }
+
+    /// Set the source element size, and the source address modulo
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulo` is greater than 31.
+    pub fn set_source_attributes<E: Element>(&mut self, modulo: u8) {
+        self.descriptor.SATTR = attributes::<E>(modulo);
+    }
+
+    /// Set the source last address adjustment *in bytes*
+    pub fn set_source_last_address_adjustment(&mut self, adjustment: i32) {
+        self.descriptor.SLAST = adjustment;
+    }
+
+    /// Set the destination address
+    pub fn set_destination_address<E: Element>(&mut self, daddr: *const E) {
+        self.descriptor.DADDR = daddr as u32;
+    }
+
+    /// Returns the destination address
+    pub fn destination_address(&self) -> *const () {
+        self.descriptor.DADDR as *const ()
+    }
+
+    /// Set the destination offset *in bytes*
+    ///
+    /// `offset` could be negative, which would decrement the address.
+    pub fn set_destination_offset(&mut self, offset: i16) {
+        self.descriptor.DOFF = offset;
+    }
+
+    /// Set the destination element size, and the destination address modulo
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulo` is greater than 31.
+    pub fn set_destination_attributes<E: Element>(&mut self, modulo: u8) {
+        self.descriptor.DATTR = attributes::<E>(modulo);
+    }
+
+    /// Set the destination last address adjustment *in bytes*
+    ///
+    /// The adjustment shares the descriptor field with a scatter / gather link.
+    /// Setting the adjustment disables scatter / gather.
+    pub fn set_destination_last_address_adjustment(&mut self, adjustment: i32) {
+        self.descriptor.CSR &= !CSR::ESG::mask;
+        self.descriptor.DLAST_SGA = adjustment;
+    }
+
+    /// Set the number of *bytes* to transfer per minor loop
+    ///
+    /// Note that `nbytes` of `0` is interpreted as a 4GB transfer. If another channel
+    /// enabled minor loop mapping, the hardware only uses the lower 30 bits.
+    pub fn set_minor_loop_bytes(&mut self, nbytes: u32) {
+        self.descriptor.NBYTES = nbytes;
+    }
+
+    /// Set the number of *bytes* to transfer per minor loop, and an address offset
+    /// applied after each minor loop
+    ///
+    /// [`apply`](Self::apply) enables minor loop mapping for the whole controller.
+    /// See [`Channel::set_minor_loop_bytes_with_offset`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nbytes` is greater than 1023, or if the offset cannot be represented
+    /// as a 20 bit signed integer.
+    pub fn set_minor_loop_bytes_with_offset(&mut self, nbytes: u32, offset: MinorLoopOffset) {
+        self.try_set_minor_loop_bytes_with_offset(nbytes, offset)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of *bytes* to transfer per minor loop, and an address offset
+    /// applied after each minor loop, or returns an error if the hardware can't
+    /// represent the minor loop
+    pub fn try_set_minor_loop_bytes_with_offset(
+        &mut self,
+        nbytes: u32,
+        offset: MinorLoopOffset,
+    ) -> Result<(), ConfigError> {
+        const MLOFF_MAX: i32 = (1 << 19) - 1;
+        const MLOFF_MIN: i32 = -(1 << 19);
+
+        let (mloff, enables) = match offset {
+            MinorLoopOffset::Source(mloff) => (mloff, NBYTES::SMLOE::mask),
+            MinorLoopOffset::Destination(mloff) => (mloff, NBYTES::DMLOE::mask),
+            MinorLoopOffset::Both(mloff) => (mloff, NBYTES::SMLOE::mask | NBYTES::DMLOE::mask),
+        };
+        if nbytes > NBYTES::NBYTES::mask {
+            return Err(ConfigError::MinorLoopTooLarge);
+        }
+        if !(MLOFF_MIN..=MLOFF_MAX).contains(&mloff) {
+            return Err(ConfigError::MinorLoopOffsetOutOfRange);
+        }
+        self.descriptor.NBYTES =
+            enables | (((mloff as u32) << NBYTES::MLOFF::offset) & NBYTES::MLOFF::mask) | nbytes;
+        Ok(())
+    }
+
+    /// Set the number of transfer iterations
+    ///
+    /// The maximum number of iterations is 2^15 - 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iterations` is greater than the maximum.
+    pub fn set_transfer_iterations(&mut self, iterations: u16) {
+        self.try_set_transfer_iterations(iterations)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of transfer iterations, or returns an error if there are too
+    /// many iterations
+    ///
+    /// ```
+    /// use imxrt_dma::{tcd::Tcd, ConfigError};
+    ///
+    /// let mut tcd = Tcd::new();
+    /// assert_eq!(tcd.try_set_transfer_iterations(1 << 15), Err(ConfigError::TooManyIterations));
+    /// tcd.try_set_transfer_iterations(512).unwrap();
+    /// assert_eq!(tcd.transfer_iterations(), 512);
+    /// ```
+    pub fn try_set_transfer_iterations(&mut self, iterations: u16) -> Result<(), ConfigError> {
+        if iterations > CITER::CITER::mask {
+            return Err(ConfigError::TooManyIterations);
+        }
+        self.descriptor.CITER = iterations;
+        self.descriptor.BITER = iterations;
+        Ok(())
+    }
+
+    /// Returns the number of transfer iterations
+    pub fn transfer_iterations(&self) -> u16 {
+        self.descriptor.BITER & CITER::CITER::mask
+    }
+
+    /// Returns the number of transfer iterations that haven't run
+    ///
+    /// This equals [`transfer_iterations`](Self::transfer_iterations) until the
+    /// descriptor is [captured](Self::capture) from a channel that's made progress.
+    pub fn remaining_iterations(&self) -> u16 {
+        self.descriptor.CITER & CITER::CITER::mask
+    }
+
+    /// Enable or disable 'disable on completion'
+    pub fn set_disable_on_completion(&mut self, dreq: bool) {
+        self.set_csr(CSR::DREQ::mask, dreq);
+    }
+
+    /// Enable or disable interrupt generation when the transfer completes
+    pub fn set_interrupt_on_completion(&mut self, intr: bool) {
+        self.set_csr(CSR::INTMAJOR::mask, intr);
+    }
+
+    /// Enable or disable interrupt generation when the transfer is half complete
+    pub fn set_interrupt_on_half_completion(&mut self, intr: bool) {
+        self.set_csr(CSR::INTHALF::mask, intr);
+    }
+
+    /// Set the bandwidth control
+    ///
+    /// `None` disables bandwidth control.
+    pub fn set_bandwidth_control(&mut self, bandwidth: Option<BandwidthControl>) {
+        let raw = BandwidthControl::raw(bandwidth) << CSR::BWC::offset;
+        self.descriptor.CSR = (self.descriptor.CSR & !CSR::BWC::mask) | raw;
+    }
+
+    fn set_csr(&mut self, mask: u16, set: bool) {
+        if set {
+            self.descriptor.CSR |= mask;
+        } else {
+            self.descriptor.CSR &= !mask;
+        }
+    }
+
+    /// Returns `true` if the transfer generates an interrupt when it completes
+    pub fn interrupt_on_completion(&self) -> bool {
+        self.descriptor.CSR & CSR::INTMAJOR::mask != 0
+    }
+
+    /// Returns the in-memory descriptor
+    #[cfg(feature = "scatter-gather")]
+    pub(crate) fn descriptor(&self) -> &Descriptor {
+        &self.descriptor
+    }
+
+    /// Copy the transfer configured on `channel`, without the status bits
+    ///
+    /// The copy includes the channel's progress: its current addresses, and its
+    /// [remaining iterations](Self::remaining_iterations). After a transfer fails
+    /// with a bus error, capture the channel to see how far the transfer got, then
+    /// [`apply`](Self::apply) the copy to the same channel, or to another channel, to
+    /// run the rest of the transfer. Elements of the failed minor loop may be
+    /// transferred again.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::Channel, tcd::Tcd, Transfer};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    /// # async fn f() -> imxrt_dma::Result<()> {
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// let mut spare: Channel = // DMA channel 8
+    ///     # unsafe { DMA.channel(8) };
+    /// // Prepare a long copy from external memory...
+    ///
+    /// let result = unsafe { Transfer::new(&channel) }.await;
+    /// if result.is_err() {
+    ///     let rest = Tcd::capture(&channel);
+    ///     let completed = rest.transfer_iterations() - rest.remaining_iterations();
+    ///     # let _ = completed;
+    ///     // Safety: the rest of the transfer uses the same buffers.
+    ///     unsafe { rest.apply(&mut spare) };
+    ///     let transfer = unsafe { Transfer::new(&spare) };
+    ///     spare.start();
+    ///     transfer.await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn capture(channel: &Channel) -> Self {
+        let mut descriptor = channel.snapshot();
+        descriptor.CSR &= !(CSR::START::mask | CSR::ACTIVE::mask | CSR::DONE::mask);
+        Tcd { descriptor }
+    }
+
+    /// Write the descriptor into the channel's transfer control descriptor
+    ///
+    /// If the descriptor uses a minor loop offset, this enables minor loop mapping
+    /// for the whole controller.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor must describe a valid transfer. You must ensure that the source
+    /// and destination are valid for the lifetime of the transfer, and that the offsets,
+    /// iterations, and adjustments stay within them.
+    pub unsafe fn apply(&self, channel: &mut Channel) {
+        if self.descriptor.NBYTES & (NBYTES::SMLOE::mask | NBYTES::DMLOE::mask) != 0 {
+            channel.enable_minor_loop_mapping();
+        }
+        // DONE must be clear before enabling scatter / gather.
+        channel.clear_complete();
+        channel.restore(&self.descriptor);
+    }
+}
+
+/// Everything that a channel needs to run a transfer
+///
+/// A `TransferConfig` pairs a [`Tcd`] with the channel's DMAMUX [`Configuration`].
+/// Keep one in your driver, and use it to [recover](Channel::recover) the channel
+/// after an error.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferConfig {
+    tcd: Tcd,
+    configuration: Configuration,
+}
+
+impl TransferConfig {
+    /// Describe a transfer with `tcd`, routed through the DMAMUX with
+    /// `configuration`
+    pub const fn new(tcd: Tcd, configuration: Configuration) -> Self {
+        TransferConfig { tcd, configuration }
+    }
+
+    /// Returns the transfer control descriptor
+    pub const fn tcd(&self) -> &Tcd {
+        &self.tcd
+    }
+
+    /// Returns the DMAMUX configuration
+    pub const fn configuration(&self) -> Configuration {
+        self.configuration
+    }
+}
+
+impl Channel {
+    /// Cancel whatever the channel is doing, and prepare it to run `config`
+    /// from a clean state
+    ///
+    /// `recover` runs the whole recovery sequence, in order:
+    ///
+    /// 1. Disable the channel, and wait for any active minor loop to finish.
+    /// 2. Clear the channel's interrupt, error, and completion flags.
+    /// 3. Reset the channel's transfer control descriptor.
+    /// 4. Program the DMAMUX with the configuration.
+    /// 5. Apply the configuration's descriptor.
+    /// 6. [Verify](Channel::verify_tcd) the descriptor.
+    ///
+    /// The channel stays disabled. If the descriptor passes verification, enable
+    /// the channel, or start a [`Transfer`](crate::Transfer), to run it. Otherwise,
+    /// `recover` returns the diagnosis.
+    ///
+    /// ```no_run
+    /// use imxrt_dma::{channel::{Channel, Configuration}, tcd::{Tcd, TransferConfig}};
+    /// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+    ///
+    /// let mut channel: Channel = // DMA channel 7
+    ///     # unsafe { DMA.channel(7) };
+    /// let mut tcd = Tcd::new();
+    /// // Describe the driver's transfer...
+    /// let config = TransferConfig::new(tcd, Configuration::enable(42));
+    ///
+    /// if channel.is_error() {
+    ///     // Safety: the driver's buffers are valid for the transfer.
+    ///     unsafe { channel.recover(&config) }.expect("valid transfer");
+    ///     unsafe { channel.enable() };
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration enables periodic triggering on a channel that
+    /// doesn't support it. See [`set_channel_configuration`](Channel::set_channel_configuration).
+    ///
+    /// # Safety
+    ///
+    /// See [`Tcd::apply`]. The configuration must describe a valid transfer.
+    pub unsafe fn recover(&mut self, config: &TransferConfig) -> Result<(), Diagnosis> {
+        self.disable();
+        while self.is_active() {}
+        self.clear_interrupt();
+        self.clear_error();
+        self.clear_complete();
+        self.reset();
+        self.set_channel_configuration(config.configuration);
+        config.tcd.apply(self);
+        self.verify_tcd()
+    }
+}
+
+/// Link `tcd` to `next` with scatter / gather
+#[cfg(all(feature = "scatter-gather", any(feature = "alloc", feature = "async")))]
+pub(crate) fn link_to(tcd: &mut Tcd, next: *const Tcd) {
+    tcd.descriptor.DLAST_SGA = next as i32;
+    tcd.descriptor.CSR |= CSR::ESG::mask;
+}
+
+/// Link each descriptor to the next descriptor with scatter / gather
+///
+/// Descriptors without transfer iterations are skipped, since the DMA engine rejects
+/// a major loop count of zero. The last non-empty descriptor doesn't link to another
+/// descriptor. Returns the index of the first non-empty descriptor, or `None` if
+/// every descriptor is empty.
+///
+/// # Panics
+///
+/// Panics if `tcds` is empty.
+#[cfg(all(feature = "scatter-gather", any(feature = "alloc", feature = "async")))]
+pub(crate) fn link(tcds: &mut [Tcd]) -> Option<usize> {
+    assert!(!tcds.is_empty(), "DMA descriptor chain is empty");
+    let mut next: Option<usize> = None;
+    for index in (0..tcds.len()).rev() {
+        if tcds[index].transfer_iterations() == 0 {
+            continue;
+        }
+        match next {
+            Some(next) => {
+                let next = core::ptr::addr_of!(tcds[next]);
+                link_to(&mut tcds[index], next);
+            }
+            None => {
+                let descriptor = &mut tcds[index].descriptor;
+                descriptor.DLAST_SGA = 0;
+                descriptor.CSR &= !CSR::ESG::mask;
+            }
+        }
+        next = Some(index);
+    }
+    next
+}
+
+fn attributes<E: Element>(modulo: u8) -> u8 {
+    assert!(
+        modulo <= ATTR::MOD::mask >> ATTR::MOD::offset,
+        "DMA address modulo is out of range"
+    );
+    (modulo << ATTR::MOD::offset) | (E::DATA_TRANSFER_ID << ATTR::SIZE::offset)
+}
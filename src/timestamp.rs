@@ -0,0 +1,124 @@
+//! Timestamp transfers with a monotonic clock.
+//!
+//! Sensor fusion correlates samples from many sources on one timebase. To place a
+//! DMA-captured buffer on that timebase, implement [`Clock`] for your monotonic
+//! timer, and wrap the transfer with [`timestamped`]. The transfer resolves with
+//! its result, plus the [`Timestamps`] of its start and completion.
+//!
+//! ```
+//! use core::{cell::Cell, future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use imxrt_dma::{memcpy, timestamp::{self, Clock, Timestamps}};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # let mut channel = unsafe { DMA.channel(7) };
+//!
+//! /// A clock that ticks every time it's read.
+//! struct Ticker(Cell<u64>);
+//! impl Clock for Ticker {
+//!     fn now(&self) -> u64 {
+//!         let now = self.0.get();
+//!         self.0.set(now + 1);
+//!         now
+//!     }
+//! }
+//!
+//! let clock = Ticker(Cell::new(100));
+//! let (source, mut destination) = ([0u32; 0], [0u32; 0]);
+//! let copy = memcpy::memcpy(&source, &mut destination, &mut channel);
+//!
+//! let timed = pin!(timestamp::timestamped(&clock, copy));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let Poll::Ready(Ok(((), timestamps))) = timed.poll(&mut cx) else { panic!() };
+//! assert_eq!(timestamps, Timestamps { start: 100, complete: 101 });
+//! assert_eq!(timestamps.duration(), 1);
+//! ```
+//!
+//! The start timestamp is taken just before the transfer's first poll, which starts
+//! the transfer. The completion timestamp is taken when the transfer resolves, so it
+//! includes the time it takes the executor to poll the transfer after the DMA
+//! interrupt. For the tightest timestamps, call [`on_interrupt()`](crate::Dma::on_interrupt)
+//! from the DMA interrupt handler, so the interrupt wakes the task right away.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::Error;
+
+/// A monotonic clock
+///
+/// Implement `Clock` for a free-running timer, like a GPT or the cycle counter.
+/// Ticks never decrease. Any function that returns ticks is a clock.
+pub trait Clock {
+    /// Returns the current time, in ticks
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now(&self) -> u64 {
+        self()
+    }
+}
+
+/// When a transfer started and completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamps {
+    /// The time that the transfer started, in ticks
+    pub start: u64,
+    /// The time that the transfer completed, in ticks
+    pub complete: u64,
+}
+
+impl Timestamps {
+    /// Returns the number of ticks between the start and completion
+    pub const fn duration(&self) -> u64 {
+        self.complete.saturating_sub(self.start)
+    }
+}
+
+/// Timestamp the start and completion of `transfer` with `clock`
+///
+/// The returned future resolves with the transfer's output and its [`Timestamps`].
+/// If the transfer fails, the future resolves with the error. See the
+/// [module documentation](crate::timestamp) for more information.
+pub fn timestamped<C, F, R>(clock: &C, transfer: F) -> Timestamped<'_, C, F>
+where
+    C: Clock + ?Sized,
+    F: Future<Output = Result<R, Error>>,
+{
+    Timestamped {
+        clock,
+        start: None,
+        transfer,
+    }
+}
+
+/// A transfer that records when it started and completed
+///
+/// Use [`timestamped`] to create this future.
+pub struct Timestamped<'c, C: ?Sized, F> {
+    clock: &'c C,
+    start: Option<u64>,
+    transfer: F,
+}
+
+impl<C, F, R> Future for Timestamped<'_, C, F>
+where
+    C: Clock + ?Sized,
+    F: Future<Output = Result<R, Error>>,
+{
+    type Output = Result<(R, Timestamps), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        let clock = this.clock;
+        let start = *this.start.get_or_insert_with(|| clock.now());
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        let output = core::task::ready!(transfer.poll(cx))?;
+        let complete = clock.now();
+        Poll::Ready(Ok((output, Timestamps { start, complete })))
+    }
+}
@@ -0,0 +1,431 @@
+//! Tune memory copies for the memories they connect.
+//!
+//! A [`memcpy()`](crate::memcpy::memcpy) moves elements of the buffers' element
+//! type, in one minor loop. That's a good default, but not the fastest copy for
+//! every pair of memories. A copy between DTCM and OCRAM may run faster with wider
+//! reads, and a copy out of SDRAM may run faster in short minor loops. The best
+//! [`Tuning`] depends on the chip, its clocks, and the other bus masters, so
+//! measure it.
+//!
+//! [`calibrate`] times a copy between two of your buffers with each candidate
+//! tuning, and returns the fastest. Record the result in a [`Tunings`] table,
+//! keyed by the memory pair, then run later copies between those memories with
+//! [`copy`].
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, tuning::{self, Tunings}};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # fn cycles() -> u64 { 0 }
+//! # async fn f(sdram: &mut [u32], ocram: &mut [u32]) -> imxrt_dma::Result<()> {
+//! let mut channel_7: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! channel_7.set_interrupt_on_completion(true);
+//!
+//! #[derive(Clone, Copy, PartialEq)]
+//! enum Pair { SdramToOcram }
+//! static TUNINGS: Tunings<Pair, 4> = Tunings::new();
+//!
+//! // At startup, measure with buffers in the real memories.
+//! let fastest = tuning::calibrate(
+//!     &cycles,
+//!     sdram,
+//!     ocram,
+//!     &tuning::CANDIDATES,
+//!     &mut channel_7,
+//! )
+//! .await?;
+//! TUNINGS.record(Pair::SdramToOcram, fastest.tuning);
+//!
+//! // Later, copy with the recorded tuning.
+//! let tuning = TUNINGS.get_or_default(Pair::SdramToOcram);
+//! tuning::copy(sdram, ocram, tuning, &mut channel_7).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! Calibrate with the clocks, caches, and bus traffic that the copies will see.
+//! Each calibration copy overwrites the destination buffer.
+
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    cache,
+    channel::{self, Channel},
+    interrupt::Transfer,
+    sync::Mutex,
+    timestamp::Clock,
+    BandwidthControl, Element, Error,
+};
+
+/// The size of each read and write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Width {
+    /// One byte
+    Byte,
+    /// Two bytes
+    HalfWord,
+    /// Four bytes
+    Word,
+    /// Eight bytes
+    DoubleWord,
+}
+
+impl Width {
+    /// Returns the width, in bytes
+    pub const fn bytes(self) -> usize {
+        match self {
+            Width::Byte => 1,
+            Width::HalfWord => 2,
+            Width::Word => 4,
+            Width::DoubleWord => 8,
+        }
+    }
+
+    /// Returns the width of elements of `size` bytes
+    const fn of(size: usize) -> Self {
+        match size {
+            1 => Width::Byte,
+            2 => Width::HalfWord,
+            4 => Width::Word,
+            _ => Width::DoubleWord,
+        }
+    }
+}
+
+/// How a tuned copy moves its bytes
+///
+/// A tuning describes the size of each read and write, the bytes in each minor
+/// loop, and the bandwidth control. A copy falls back to the element size if the
+/// buffers aren't aligned to the tuning's width, and to a single minor loop if the
+/// tuning's minor loop doesn't evenly divide the copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tuning {
+    width: Width,
+    minor_loop: usize,
+    bandwidth: Option<BandwidthControl>,
+}
+
+impl Tuning {
+    /// Word reads and writes, in one minor loop, without bandwidth control
+    pub const DEFAULT: Tuning = Tuning::new(Width::Word, 0, None);
+
+    /// Describe a tuning
+    ///
+    /// `minor_loop` is the number of bytes in each minor loop. Zero copies all
+    /// bytes in one minor loop.
+    pub const fn new(width: Width, minor_loop: usize, bandwidth: Option<BandwidthControl>) -> Self {
+        Tuning {
+            width,
+            minor_loop,
+            bandwidth,
+        }
+    }
+
+    /// Returns the size of each read and write
+    pub const fn width(&self) -> Width {
+        self.width
+    }
+
+    /// Returns the number of bytes in each minor loop, or zero for one minor loop
+    pub const fn minor_loop_bytes(&self) -> usize {
+        self.minor_loop
+    }
+
+    /// Returns the bandwidth control
+    pub const fn bandwidth_control(&self) -> Option<BandwidthControl> {
+        self.bandwidth
+    }
+
+    /// Returns the width, minor loop bytes, and iterations that copy `bytes`
+    /// between `source` and `destination`
+    fn plan(&self, source: usize, destination: usize, bytes: usize, size: usize) -> Plan {
+        let wide = self.width.bytes();
+        let width = if [source, destination, bytes, self.minor_loop]
+            .iter()
+            .all(|value| value.is_multiple_of(wide))
+        {
+            self.width
+        } else {
+            Width::of(size)
+        };
+        let minor_loop = match self.minor_loop {
+            0 => bytes,
+            minor_loop
+                if !bytes.is_multiple_of(minor_loop)
+                    || !minor_loop.is_multiple_of(width.bytes())
+                    || bytes / minor_loop > 0x7FFF =>
+            {
+                bytes
+            }
+            minor_loop => minor_loop,
+        };
+        Plan {
+            width,
+            minor_loop,
+            iterations: bytes / minor_loop,
+        }
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How a tuned copy programs the channel
+struct Plan {
+    width: Width,
+    minor_loop: usize,
+    iterations: usize,
+}
+
+/// A set of tunings to [`calibrate`] with
+///
+/// The candidates cover each width in one minor loop, short and long minor loops
+/// of wide reads and writes, and bandwidth control. Calibrate with your own
+/// candidates to search other combinations.
+pub const CANDIDATES: [Tuning; 9] = [
+    Tuning::new(Width::Byte, 0, None),
+    Tuning::new(Width::HalfWord, 0, None),
+    Tuning::new(Width::Word, 0, None),
+    Tuning::new(Width::DoubleWord, 0, None),
+    Tuning::new(Width::Word, 32, None),
+    Tuning::new(Width::DoubleWord, 32, None),
+    Tuning::new(Width::Word, 512, None),
+    Tuning::new(Width::DoubleWord, 512, None),
+    Tuning::new(Width::DoubleWord, 0, Some(BandwidthControl::Stall4Cycles)),
+];
+
+/// A tuned memcpy operation
+///
+/// `TunedCopy` yields when it's moved the minimum amount of elements between two
+/// linear buffers. Use [`copy`] to define the transfer.
+pub struct TunedCopy<'a, E> {
+    transfer: Transfer<'a>,
+    cache: cache::Destination,
+    _elem: core::marker::PhantomData<(&'a E, &'a mut E)>,
+}
+
+/// Perform a DMA-powered `memcpy` between `source` and `destination` with `tuning`
+///
+/// Like [`memcpy()`](crate::memcpy::memcpy), the copy moves the minimum number of
+/// elements between the two buffers. Enable the channel's interrupt on completion,
+/// and call [`on_interrupt`](crate::Dma::on_interrupt) when the interrupt fires.
+/// Otherwise, poll the copy until it completes.
+pub fn copy<'a, E: Element>(
+    source: &'a [E],
+    destination: &'a mut [E],
+    tuning: Tuning,
+    channel: &'a mut Channel,
+) -> TunedCopy<'a, E> {
+    let elements = source.len().min(destination.len());
+    if elements == 0 {
+        return TunedCopy {
+            transfer: Transfer::empty(channel),
+            cache: cache::Destination::none(),
+            _elem: core::marker::PhantomData,
+        };
+    }
+    let size = core::mem::size_of::<E>();
+    let bytes = elements * size;
+    let plan = tuning.plan(
+        source.as_ptr() as usize,
+        destination.as_ptr() as usize,
+        bytes,
+        size,
+    );
+
+    channel.disable();
+    channel.set_disable_on_completion(true);
+    channel.set_bandwidth_control(tuning.bandwidth);
+    channel::validate_source(source);
+    channel::validate_destination(destination);
+
+    // Safety: the channel moves `bytes` bytes within the borrowed buffers, which
+    // are valid while the transfer is in progress. The width divides the bytes,
+    // and both addresses.
+    unsafe {
+        let width = plan.width.bytes();
+        channel.set_source_address(source.as_ptr());
+        channel.set_source_offset(width as i16);
+        channel.set_source_last_address_adjustment((bytes as i32).wrapping_neg());
+        channel.set_destination_address(destination.as_ptr());
+        channel.set_destination_offset(width as i16);
+        channel.set_destination_last_address_adjustment((bytes as i32).wrapping_neg());
+        match plan.width {
+            Width::Byte => set_attributes::<u8>(channel),
+            Width::HalfWord => set_attributes::<u16>(channel),
+            Width::Word => set_attributes::<u32>(channel),
+            Width::DoubleWord => set_attributes::<u64>(channel),
+        }
+        channel.set_minor_loop_bytes(plan.minor_loop as u32);
+        channel.set_transfer_iterations(plan.iterations as u16);
+    }
+
+    // The always-on request signal runs every minor loop, and
+    // 'disable on completion' stops the channel after the last one.
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+
+    cache::prepare_source(&source[..elements]);
+    TunedCopy {
+        cache: cache::Destination::prepare(destination),
+        // Safety: transfer is properly prepared
+        transfer: unsafe { Transfer::new(channel) },
+        _elem: core::marker::PhantomData,
+    }
+}
+
+/// Set the source and destination sizes to the size of `T`
+///
+/// # Safety
+///
+/// The addresses, offsets, and minor loop bytes must be multiples of `T`'s size.
+unsafe fn set_attributes<T: Element>(channel: &Channel) {
+    channel.set_source_attributes::<T>(0);
+    channel.set_destination_attributes::<T>(0);
+}
+
+impl<E> Future for TunedCopy<'_, E> {
+    type Output = Result<(), Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        let poll = transfer.poll(cx);
+        if let Poll::Ready(Ok(())) = poll {
+            this.cache.complete();
+        }
+        poll
+    }
+}
+
+// Drop handled by Transfer impl
+
+/// The fastest tuning that [`calibrate`] measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    /// The fastest tuning
+    pub tuning: Tuning,
+    /// The ticks that the fastest tuning's copy took
+    pub ticks: u64,
+}
+
+/// Time a copy between `source` and `destination` with each candidate, and
+/// return the fastest
+///
+/// Each candidate runs one [`copy`], and `clock` times it from its first poll to
+/// its completion. If two candidates tie, the earlier candidate wins. Use
+/// [`CANDIDATES`] for a general search.
+///
+/// Returns the first copy error. See [`copy`] for the channel's interrupt
+/// requirements.
+///
+/// # Panics
+///
+/// Panics if there are no candidates.
+pub async fn calibrate<C: Clock, E: Element>(
+    clock: &C,
+    source: &[E],
+    destination: &mut [E],
+    candidates: &[Tuning],
+    channel: &mut Channel,
+) -> Result<Calibration, Error> {
+    assert!(!candidates.is_empty(), "DMA calibration needs a candidate");
+    let mut fastest: Option<Calibration> = None;
+    for &tuning in candidates {
+        let start = clock.now();
+        copy(source, destination, tuning, channel).await?;
+        let ticks = clock.now().saturating_sub(start);
+        if fastest.is_none_or(|fastest| ticks < fastest.ticks) {
+            fastest = Some(Calibration { tuning, ticks });
+        }
+    }
+    Ok(fastest.unwrap())
+}
+
+/// A table of tunings, keyed by memory pair
+///
+/// Use any `PartialEq` key that names a pair of memories. With a chip feature, a
+/// pair of `memory::Region`s is a good key. The table holds up to `N` tunings.
+///
+/// ```no_run
+/// use imxrt_dma::tuning::{Tuning, Tunings, Width};
+///
+/// static TUNINGS: Tunings<(u8, u8), 2> = Tunings::new();
+///
+/// let wide = Tuning::new(Width::DoubleWord, 0, None);
+/// assert!(TUNINGS.record((0, 1), wide));
+/// assert_eq!(TUNINGS.get((0, 1)), Some(wide));
+/// assert_eq!(TUNINGS.get_or_default((1, 0)), Tuning::DEFAULT);
+///
+/// assert!(TUNINGS.record((1, 0), Tuning::DEFAULT));
+/// assert!(!TUNINGS.record((2, 0), wide));
+/// ```
+pub struct Tunings<K, const N: usize> {
+    entries: Mutex<RefCell<Entries<K, N>>>,
+}
+
+/// Each memory pair's tuning, or `None` for a free entry
+type Entries<K, const N: usize> = [Option<(K, Tuning)>; N];
+
+impl<K: Copy + PartialEq, const N: usize> Tunings<K, N> {
+    /// Create an empty table
+    pub const fn new() -> Self {
+        Tunings {
+            entries: Mutex::new(RefCell::new([const { None }; N])),
+        }
+    }
+
+    /// Record the tuning of the memory pair `key`
+    ///
+    /// Replaces the pair's last tuning. Returns `false` if the pair has no tuning,
+    /// and the table is full.
+    pub fn record(&self, key: K, tuning: Tuning) -> bool {
+        self.entries.lock(|entries| {
+            let mut entries = entries.borrow_mut();
+            let slot = match entries
+                .iter()
+                .position(|entry| matches!(entry, Some((k, _)) if *k == key))
+            {
+                Some(slot) => slot,
+                None => match entries.iter().position(Option::is_none) {
+                    Some(slot) => slot,
+                    None => return false,
+                },
+            };
+            entries[slot] = Some((key, tuning));
+            true
+        })
+    }
+
+    /// Returns the tuning of the memory pair `key`, if it has one
+    pub fn get(&self, key: K) -> Option<Tuning> {
+        self.entries.lock(|entries| {
+            entries
+                .borrow()
+                .iter()
+                .flatten()
+                .find(|(k, _)| *k == key)
+                .map(|(_, tuning)| *tuning)
+        })
+    }
+
+    /// Returns the tuning of the memory pair `key`, or [`Tuning::DEFAULT`]
+    pub fn get_or_default(&self, key: K) -> Tuning {
+        self.get(key).unwrap_or(Tuning::DEFAULT)
+    }
+}
+
+impl<K: Copy + PartialEq, const N: usize> Default for Tunings<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
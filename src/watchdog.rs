@@ -0,0 +1,221 @@
+//! Detect transfers that stop making progress.
+//!
+//! A peripheral that stops requesting service leaves its DMA transfer waiting
+//! forever. The channel reports no error, and the transfer future never resolves.
+//! A [`Watchdog`] notices. Supervise a transfer, and the watchdog samples the
+//! channel's iteration count and destination address. If neither changes within
+//! a window of ticks, the supervised future resolves with [`Error::Stalled`].
+//!
+//! You supply the ticks. Call [`tick`](Watchdog::tick) from a periodic timer
+//! interrupt, or from any task that runs periodically. Each tick wakes the
+//! supervised tasks, so they check for progress even when the DMA channel is
+//! silent. A window of `n` ticks flags a stall after `n - 1` to `n` tick periods
+//! without progress.
+//!
+//! ```no_run
+//! use imxrt_dma::{channel::Channel, peripheral, watchdog::Watchdog, Error};
+//! # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+//! # struct Lpuart2;
+//! # unsafe impl peripheral::Source<u8> for Lpuart2 {
+//! #   fn source_signal(&self) -> u32 { 0 }
+//! #   fn source_address(&self) -> *const u8 { panic!() }
+//! #   fn enable_source(&mut self) { panic!() }
+//! #   fn disable_source(&mut self) { panic!() }
+//! # }
+//! # async fn f() -> imxrt_dma::Result<()> {
+//!
+//! static WATCHDOG: Watchdog = Watchdog::new();
+//!
+//! // #[cortex_m_rt::interrupt]
+//! fn PIT() {
+//!     // Clear the timer's interrupt flag, then...
+//!     WATCHDOG.tick();
+//! }
+//!
+//! let mut channel: Channel = // DMA channel 7
+//!     # unsafe { DMA.channel(7) };
+//! let mut lpuart2 = // A LPUART receiver
+//!     # Lpuart2;
+//! let mut buffer = [0u8; 32];
+//!
+//! // Flag a stall after 10 ticks without progress.
+//! let received = WATCHDOG
+//!     .supervise(&mut channel, 10, |channel| {
+//!         peripheral::read(channel, &mut lpuart2, &mut buffer)
+//!     })
+//!     .await;
+//! if received == Err(Error::Stalled) {
+//!     // Reset the peripheral...
+//! }
+//! # Ok(()) }
+//! ```
+
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    channel::{Channel, Probe, MAX_CHANNELS},
+    sync::Mutex,
+    Error, Instance,
+};
+
+/// The watchdog's tick count, and the wakers of supervised tasks
+struct State {
+    ticks: u32,
+    /// The controller of every supervised channel, once the watchdog supervises one.
+    instance: Option<Instance>,
+    /// Indexed by channel number.
+    wakers: [Option<Waker>; MAX_CHANNELS],
+}
+
+/// Flags supervised transfers that make no progress
+///
+/// See the [module documentation](crate::watchdog) for more information.
+pub struct Watchdog {
+    state: Mutex<RefCell<State>>,
+}
+
+impl Watchdog {
+    /// Create a watchdog
+    pub const fn new() -> Self {
+        Watchdog {
+            state: Mutex::new(RefCell::new(State {
+                ticks: 0,
+                instance: None,
+                wakers: [const { None }; MAX_CHANNELS],
+            })),
+        }
+    }
+
+    /// Advance the watchdog by one tick
+    ///
+    /// Wakes every supervised task.
+    pub fn tick(&self) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.ticks = state.ticks.wrapping_add(1);
+            state.wakers.iter().flatten().for_each(Waker::wake_by_ref);
+        });
+    }
+
+    /// Returns the number of ticks since the watchdog was created
+    ///
+    /// The count wraps around.
+    pub fn ticks(&self) -> u32 {
+        self.state.lock(|state| state.borrow().ticks)
+    }
+
+    /// Supervise the transfer that `transfer` starts on `channel`
+    ///
+    /// `supervise` calls `transfer` with `channel` to create the transfer's future.
+    /// The supervised future resolves with the transfer's result, or with
+    /// [`Error::Stalled`] if the channel makes no progress within `window` ticks.
+    /// After a stall, drop the supervised future to cancel the transfer.
+    ///
+    /// Supervise at most one transfer per channel at a time. A watchdog belongs to
+    /// the controller of the first channel it supervises. To supervise channels of
+    /// another controller, use another watchdog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero, or if `channel` belongs to a different controller
+    /// than the channels this watchdog already supervised.
+    pub fn supervise<'a, F, R>(
+        &'a self,
+        channel: &'a mut Channel,
+        window: u32,
+        transfer: impl FnOnce(&'a mut Channel) -> F,
+    ) -> Supervised<'a, F>
+    where
+        F: Future<Output = Result<R, Error>>,
+    {
+        assert!(window > 0, "Watchdog window must be at least one tick");
+        let instance = channel.instance();
+        let bound = self
+            .state
+            .lock(|state| *state.borrow_mut().instance.get_or_insert(instance));
+        assert!(
+            bound == instance,
+            "Watchdog supervises channels of one DMA controller"
+        );
+        let probe = channel.probe();
+        Supervised {
+            watchdog: self,
+            index: channel.channel(),
+            sample: probe.sample(),
+            since: self.ticks(),
+            probe,
+            window,
+            transfer: transfer(channel),
+        }
+    }
+
+    /// Forget the waker of the task that supervises channel `index`
+    fn clear_waker(&self, index: usize) {
+        self.state
+            .lock(|state| state.borrow_mut().wakers[index] = None);
+    }
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A transfer supervised by a [`Watchdog`]
+///
+/// Use [`Watchdog::supervise`] to create this future. Dropping the future drops
+/// the transfer.
+pub struct Supervised<'a, F> {
+    watchdog: &'a Watchdog,
+    index: usize,
+    probe: Probe,
+    /// The last sample that showed progress.
+    sample: (u16, u32),
+    /// The tick of the last sample that showed progress.
+    since: u32,
+    window: u32,
+    transfer: F,
+}
+
+impl<F, R> Future for Supervised<'_, F>
+where
+    F: Future<Output = Result<R, Error>>,
+{
+    type Output = Result<R, Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the transfer is structurally pinned. Nothing else moves.
+        let this = unsafe { self.get_unchecked_mut() };
+        let transfer = unsafe { Pin::new_unchecked(&mut this.transfer) };
+        if let Poll::Ready(result) = transfer.poll(cx) {
+            this.watchdog.clear_waker(this.index);
+            return Poll::Ready(result);
+        }
+
+        let ticks = this.watchdog.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.wakers[this.index] = Some(cx.waker().clone());
+            state.ticks
+        });
+        let sample = this.probe.sample();
+        if sample != this.sample {
+            this.sample = sample;
+            this.since = ticks;
+        } else if ticks.wrapping_sub(this.since) >= this.window {
+            this.watchdog.clear_waker(this.index);
+            return Poll::Ready(Err(Error::Stalled));
+        }
+        Poll::Pending
+    }
+}
+
+impl<F> Drop for Supervised<'_, F> {
+    fn drop(&mut self) {
+        self.watchdog.clear_waker(self.index);
+    }
+}
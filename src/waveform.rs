@@ -0,0 +1,138 @@
+//! Continuously stream a table of values to a peripheral.
+//!
+//! A [`Waveform`] repeatedly sends a precomputed table to a [`Destination`]. The
+//! destination paces the transfer with its DMA requests. For example, a PWM or TMR
+//! peripheral requests one new duty cycle value every period, and the DMA channel
+//! writes the next value from the table. When the DMA channel reaches the end of
+//! the table, it wraps around to the start. This generates an arbitrary, periodic
+//! waveform without any interrupts.
+
+use crate::{
+    channel::{self, Channel, Configuration},
+    peripheral::Destination,
+    ConfigError, Element, Error,
+};
+
+/// Repeatedly sends a table of values to a destination
+///
+/// Use [`Waveform::new`] to start the waveform. The waveform runs until it's dropped.
+///
+/// # Example
+///
+/// Drive a PWM duty cycle with a sine table.
+///
+/// ```no_run
+/// use imxrt_dma::{channel::Channel, peripheral, waveform::Waveform};
+/// # static DMA: imxrt_dma::Dma<32> = unsafe { imxrt_dma::Dma::new(core::ptr::null(), core::ptr::null()) };
+/// # struct X;
+/// # unsafe impl peripheral::Destination<u16> for X {
+/// #   fn destination_signal(&self) -> u32 { 0 }
+/// #   fn destination_address(&self) -> *const u16 { panic!() }
+/// #   fn enable_destination(&mut self) { panic!() }
+/// #   fn disable_destination(&mut self) { panic!() }
+/// # }
+/// # const SINE: [u16; 64] = [0; 64];
+/// let mut pwm = // A PWM submodule, requesting DMA every period
+///     # X;
+/// let mut channel_7: Channel = // DMA channel 7
+///     # unsafe { DMA.channel(7) };
+///
+/// static SINE_TABLE: [u16; 64] = // Precomputed duty cycles
+///     # SINE;
+/// let waveform = Waveform::new(&mut channel_7, &SINE_TABLE, &mut pwm);
+/// // Do other work while the waveform runs...
+/// # let _ = waveform.position();
+/// drop(waveform);
+/// ```
+pub struct Waveform<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    destination: &'a mut D,
+    table: &'a [E],
+    /// The result of enabling the channel
+    enable: Result<(), ConfigError>,
+}
+
+impl<'a, D, E> Waveform<'a, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    /// Start sending `table` to `destination`
+    ///
+    /// The DMA channel sends one element of the table for each DMA request from
+    /// the destination.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty, or if the table has more than 32767 elements.
+    pub fn new(channel: &'a mut Channel, table: &'a [E], destination: &'a mut D) -> Self {
+        assert!(!table.is_empty(), "DMA waveform table is empty");
+        assert!(table.len() <= 0x7FFF, "DMA waveform table is too large");
+
+        channel.disable();
+        // The channel runs until the waveform is dropped.
+        channel.set_disable_on_completion(false);
+        channel.clear_complete();
+        channel.clear_error();
+        channel.set_channel_configuration(Configuration::enable(destination.destination_signal()));
+
+        // Safety: the waveform borrows the table, and the source wraps around to
+        // the start of the table. Hardware destination must be valid, otherwise the
+        // Destination impl is unsound.
+        let enable = unsafe {
+            channel::set_source_linear_buffer(channel, table);
+            channel::set_destination_hardware(channel, destination.destination_address());
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(table.len() as u16);
+            channel.try_enable()
+        };
+        if enable.is_ok() {
+            destination.enable_destination();
+        }
+
+        Waveform {
+            channel,
+            destination,
+            table,
+            enable,
+        }
+    }
+
+    /// Returns the index of the next table element that the DMA channel will send
+    pub fn position(&self) -> usize {
+        let remaining = self.channel.current_transfer_iterations() as usize;
+        (self.table.len() - remaining.min(self.table.len())) % self.table.len()
+    }
+
+    /// Returns the DMA channel error, if one occurred
+    ///
+    /// If the DMA channel reports an error, the waveform stops. If the channel
+    /// wouldn't enable, the waveform never started. Drop the waveform, and create a
+    /// new one to restart.
+    pub fn error(&self) -> Option<Error> {
+        if let Err(err) = self.enable {
+            return Some(err.into());
+        }
+        self.channel
+            .is_error()
+            .then(|| self.channel.error_status().into())
+    }
+}
+
+impl<D, E> Drop for Waveform<'_, D, E>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.destination.disable_destination();
+        self.channel.disable();
+        while self.channel.is_hardware_signaling() {}
+        self.channel.clear_complete();
+        self.channel.clear_error();
+    }
+}